@@ -1,9 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use grass::StyleSheet;
 
 pub fn many_foo(c: &mut Criterion) {
     c.bench_function("many_foo", |b| {
-        b.iter(|| StyleSheet::new(black_box(include_str!("many_foo.scss").to_string())))
+        b.iter(|| grass::from_string(black_box(include_str!("many_foo.scss").to_string())))
     });
 }
 
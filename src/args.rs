@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, rc::Rc};
 
 use codemap::{Span, Spanned};
 
+use indexmap::IndexMap;
+
 use crate::{
-    common::Identifier,
-    error::SassResult,
-    value::Value,
+    common::{Identifier, QuoteKind},
+    error::{SassError, SassResult},
+    parse::Parser,
+    utils::{closest_match, did_you_mean},
+    value::{SassArgList, SassMap, Value},
     {Cow, Token},
 };
 
@@ -34,8 +38,30 @@ impl FuncArgs {
     }
 }
 
+/// The arguments to a function call, which are not evaluated until the
+/// function is actually called
+#[derive(Debug, Clone)]
+pub(crate) struct UnevaluatedCallArgs(pub Vec<UnevaluatedCallArg>, pub Span);
+
+#[derive(Debug, Clone)]
+pub(crate) struct UnevaluatedCallArg {
+    pub name: Option<Identifier>,
+    pub toks: Vec<Token>,
+    pub is_splat: bool,
+}
+
+/// The evaluated arguments to a function call, in the order they were passed
+///
+/// Builtin functions ask for their parameters by name as they run, so the
+/// names asked for are recorded in the last field. This is shared between
+/// clones, so that once a builtin function returns, keyword arguments which
+/// match none of its parameters can be reported
 #[derive(Debug, Clone)]
-pub(crate) struct CallArgs(pub HashMap<CallArg, SassResult<Spanned<Value>>>, pub Span);
+pub(crate) struct CallArgs(
+    pub IndexMap<CallArg, SassResult<Spanned<Value>>>,
+    pub Span,
+    Rc<RefCell<Vec<Identifier>>>,
+);
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub(crate) enum CallArg {
@@ -61,7 +87,11 @@ impl CallArg {
 
 impl CallArgs {
     pub fn new(span: Span) -> Self {
-        CallArgs(HashMap::new(), span)
+        CallArgs::from_args(IndexMap::new(), span)
+    }
+
+    pub fn from_args(args: IndexMap<CallArg, SassResult<Spanned<Value>>>, span: Span) -> Self {
+        CallArgs(args, span, Rc::default())
     }
 
     pub fn to_css_string(self) -> SassResult<Spanned<String>> {
@@ -76,12 +106,11 @@ impl CallArgs {
             });
         }
 
-        let args = match self.get_variadic() {
-            Ok(v) => v,
-            Err(..) => {
-                return Err(("Plain CSS functions don't support keyword arguments.", span).into())
-            }
-        };
+        if self.0.keys().any(|arg| matches!(arg, CallArg::Named(..))) {
+            return Err(("Plain CSS functions don't support keyword arguments.", span).into());
+        }
+
+        let args = self.get_variadic()?;
 
         string.push_str(
             &args
@@ -101,14 +130,16 @@ impl CallArgs {
     ///
     /// Removes the argument
     pub fn get_named<T: Into<Identifier>>(&mut self, val: T) -> Option<SassResult<Spanned<Value>>> {
-        self.0.remove(&CallArg::Named(val.into()))
+        let name = val.into();
+        self.2.borrow_mut().push(name);
+        self.0.shift_remove(&CallArg::Named(name))
     }
 
     /// Get a positional argument by 0-indexed position
     ///
     /// Removes the argument
     pub fn get_positional(&mut self, val: usize) -> Option<SassResult<Spanned<Value>>> {
-        self.0.remove(&CallArg::Positional(val))
+        self.0.shift_remove(&CallArg::Positional(val))
     }
 
     pub fn get<T: Into<Identifier>>(
@@ -127,17 +158,39 @@ impl CallArgs {
             Some(v) => Ok(v?.node),
             None => match self.get_positional(position) {
                 Some(v) => Ok(v?.node),
-                None => Err((format!("Missing argument ${}.", name), self.span()).into()),
+                None => Err(self.missing(name)),
             },
         }
     }
 
+    /// The error for a missing required argument `name`
+    ///
+    /// If a keyword argument was passed which looks like a typo of `name`,
+    /// that is reported instead, along with the suggestion to use `name`
+    fn missing(&self, name: &'static str) -> Box<SassError> {
+        let typo = self.0.keys().find_map(|arg| match arg {
+            CallArg::Named(arg) => closest_match(arg.as_str(), [name]).map(|_| arg),
+            CallArg::Positional(..) => None,
+        });
+
+        match typo {
+            Some(typo) => (
+                format!("No argument named ${}. Did you mean ${}?", typo, name),
+                self.span(),
+            )
+                .into(),
+            None => (format!("Missing argument ${}.", name), self.span()).into(),
+        }
+    }
+
     /// Decrement all positional arguments by 1
     ///
     /// This is used by builtin function `call` to pass
-    /// positional arguments to the other function
+    /// positional arguments to the other function, which is
+    /// left to check the keyword arguments
     pub fn decrement(self) -> Self {
-        CallArgs(
+        self.accept_named();
+        CallArgs::from_args(
             self.0
                 .into_iter()
                 .map(|(k, v)| (k.decrement(), v))
@@ -146,6 +199,52 @@ impl CallArgs {
         )
     }
 
+    /// The names of the keyword arguments
+    fn named(&self) -> impl Iterator<Item = Identifier> + '_ {
+        self.0.keys().filter_map(|arg| match arg {
+            CallArg::Named(name) => Some(*name),
+            CallArg::Positional(..) => None,
+        })
+    }
+
+    /// Records every keyword argument as matching a parameter
+    fn accept_named(&self) {
+        self.2.borrow_mut().extend(self.named());
+    }
+
+    /// Call the builtin function `f` with these arguments
+    ///
+    /// Once `f` returns, this errors if a keyword argument was passed which
+    /// doesn't match any of the parameters `f` asked for, suggesting the
+    /// closest one, as `CallArgs::check_named` does for `@function`s
+    pub fn call_builtin(
+        self,
+        f: fn(CallArgs, &mut Parser<'_>) -> SassResult<Value>,
+        parser: &mut Parser<'_>,
+    ) -> SassResult<Value> {
+        let span = self.span();
+        let named: Vec<Identifier> = self.named().collect();
+        let params = Rc::clone(&self.2);
+
+        let value = f(self, parser)?;
+
+        let params = params.borrow();
+        if let Some(name) = named.into_iter().find(|name| !params.contains(name)) {
+            return Err((
+                did_you_mean(
+                    &format!("No argument named ${}.", name),
+                    name.as_str(),
+                    params.iter().map(Identifier::as_str),
+                    "$",
+                ),
+                span,
+            )
+                .into());
+        }
+
+        Ok(value)
+    }
+
     pub const fn span(&self) -> Span {
         self.1
     }
@@ -164,7 +263,11 @@ impl CallArgs {
             if min == 1 {
                 return Err(("At least one argument must be passed.", self.span()).into());
             }
-            todo!("min args greater than one")
+            return Err((
+                format!("At least {} arguments must be passed.", min),
+                self.span(),
+            )
+                .into());
         }
         Ok(())
     }
@@ -190,6 +293,28 @@ impl CallArgs {
         Ok(())
     }
 
+    /// Errors if a keyword argument was passed which doesn't match any of
+    /// `params`, suggesting the closest parameter name
+    pub fn check_named(&self, params: &[Identifier]) -> SassResult<()> {
+        for arg in self.0.keys() {
+            if let CallArg::Named(name) = arg {
+                if !params.contains(name) {
+                    return Err((
+                        did_you_mean(
+                            &format!("No argument named ${}.", name),
+                            name.as_str(),
+                            params.iter().map(Identifier::as_str),
+                            "$",
+                        ),
+                        self.span(),
+                    )
+                        .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn default_arg(
         &mut self,
         position: usize,
@@ -235,4 +360,32 @@ impl CallArgs {
         }
         Ok(vals)
     }
+
+    /// Collect the remaining arguments into the value of a variadic parameter,
+    /// keeping any keyword arguments
+    pub fn get_arg_list(self) -> SassResult<SassArgList> {
+        self.accept_named();
+
+        let mut positional = Vec::new();
+        let mut keywords = SassMap::new();
+
+        for (arg, value) in self.0 {
+            match arg {
+                CallArg::Positional(position) => positional.push((position, value?)),
+                CallArg::Named(name) => {
+                    keywords.insert(
+                        Value::String(name.as_str().into(), QuoteKind::None),
+                        value?.node,
+                    );
+                }
+            }
+        }
+
+        positional.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(SassArgList::new(
+            positional.into_iter().map(|(_, value)| value).collect(),
+            keywords,
+        ))
+    }
 }
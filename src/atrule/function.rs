@@ -35,4 +35,8 @@ impl Function {
             declared_at_root,
         }
     }
+
+    pub const fn pos(&self) -> Span {
+        self.pos
+    }
 }
@@ -46,11 +46,6 @@ impl MediaQuery {
             features,
         }
     }
-
-    #[allow(dead_code, unused_variables)]
-    pub fn merge(other: &Self) -> Self {
-        todo!()
-    }
 }
 
 impl fmt::Display for MediaQuery {
@@ -1,3 +1,7 @@
+use std::hash::{Hash, Hasher};
+
+use codemap::Span;
+
 use crate::{args::FuncArgs, Token};
 
 #[derive(Debug, Clone)]
@@ -6,20 +10,37 @@ pub(crate) struct Mixin {
     pub body: Vec<Token>,
     pub accepts_content_block: bool,
     pub declared_at_root: bool,
+    pos: Span,
+}
+
+impl Hash for Mixin {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pos.hash(state)
+    }
 }
 
+impl PartialEq for Mixin {
+    fn eq(&self, other: &Self) -> bool {
+        self.pos == other.pos
+    }
+}
+
+impl Eq for Mixin {}
+
 impl Mixin {
     pub fn new(
         args: FuncArgs,
         body: Vec<Token>,
         accepts_content_block: bool,
         declared_at_root: bool,
+        pos: Span,
     ) -> Self {
         Mixin {
             args,
             body,
             accepts_content_block,
             declared_at_root,
+            pos,
         }
     }
 }
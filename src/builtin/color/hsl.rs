@@ -1,4 +1,4 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{parse_alpha_channel, plain_css_color, split_slash_alpha, Builtin, GlobalFunctionMap};
 
 use num_traits::One;
 
@@ -18,11 +18,17 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
     }
 
     if args.len() == 1 {
-        let mut channels = match args.get_err(0, "channels")? {
-            Value::List(v, ..) => v,
+        let channels = match args.get_err(0, "channels")? {
+            v @ Value::List(..) => v.as_list(),
             _ => return Err(("Missing argument $channels.", args.span()).into()),
         };
 
+        let (mut channels, alpha) = split_slash_alpha(channels);
+
+        if let Some(alpha) = alpha.as_ref().filter(|alpha| alpha.is_special_function()) {
+            return plain_css_color(name, &channels, Some(alpha), &args);
+        }
+
         if channels.len() > 3 {
             return Err((
                 format!(
@@ -74,7 +80,7 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
             hue,
             saturation,
             lightness,
-            Number::one(),
+            parse_alpha_channel(alpha, &args)?,
         ))))
     } else {
         let hue = match args.get_err(0, "hue")? {
@@ -94,7 +100,7 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
                 }
                 string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -120,7 +126,7 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
                 }
                 string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -148,7 +154,7 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
                 }
                 string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -187,7 +193,8 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                         saturation,
                         lightness,
                         v.to_css_string(args.span())?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ));
             }
@@ -340,7 +347,8 @@ fn saturate(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
             format!(
                 "saturate({})",
                 args.get_err(0, "amount")?.to_css_string(args.span())?
-            ),
+            )
+            .into(),
             QuoteKind::None,
         ));
     }
@@ -362,7 +370,7 @@ fn saturate(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         Value::Color(c) => c,
         Value::Dimension(n, u, _) => {
             return Ok(Value::String(
-                format!("saturate({}{})", n, u),
+                format!("saturate({}{})", n, u).into(),
                 QuoteKind::None,
             ))
         }
@@ -411,7 +419,7 @@ fn grayscale(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         Value::Color(c) => c,
         Value::Dimension(n, u, _) => {
             return Ok(Value::String(
-                format!("grayscale({}{})", n, u),
+                format!("grayscale({}{})", n, u).into(),
                 QuoteKind::None,
             ))
         }
@@ -462,9 +470,10 @@ fn invert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     };
     match args.get_err(0, "color")? {
         Value::Color(c) => Ok(Value::Color(Box::new(c.invert(weight)))),
-        Value::Dimension(n, Unit::Percent, _) => {
-            Ok(Value::String(format!("invert({}%)", n), QuoteKind::None))
-        }
+        Value::Dimension(n, Unit::Percent, _) => Ok(Value::String(
+            format!("invert({}%)", n).into(),
+            QuoteKind::None,
+        )),
         Value::Dimension(..) => Err((
             "Only one argument may be passed to the plain-CSS invert() function.",
             args.span(),
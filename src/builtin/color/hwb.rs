@@ -0,0 +1,155 @@
+use super::{parse_alpha_channel, parse_channels, Builtin, GlobalFunctionMap, ParsedChannels};
+
+use crate::{
+    args::CallArgs,
+    color::Color,
+    error::SassResult,
+    parse::Parser,
+    unit::{Unit, UnitKind},
+    value::{Number, Value},
+};
+
+fn hwb(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(4)?;
+
+    if args.is_empty() {
+        return Err(("Missing argument $channels.", args.span()).into());
+    }
+
+    if args.len() == 1 {
+        let channels = args.get_err(0, "channels")?;
+        return match parse_channels(
+            "hwb",
+            "channels",
+            channels,
+            &["hue", "whiteness", "blackness"],
+            &args,
+        )? {
+            ParsedChannels::PlainCss(v) => Ok(v),
+            ParsedChannels::Channels(mut channels, alpha) => {
+                let blackness = channels.pop().unwrap();
+                let whiteness = channels.pop().unwrap();
+                let hue = channels.pop().unwrap();
+
+                hwb_from_values(&args, hue, whiteness, blackness, alpha)
+            }
+        };
+    }
+
+    let hue = args.get_err(0, "hue")?;
+    let whiteness = args.get_err(1, "whiteness")?;
+    let blackness = args.get_err(2, "blackness")?;
+    let alpha = match args.default_arg(3, "alpha", Value::Null)? {
+        Value::Null => None,
+        v => Some(v),
+    };
+
+    hwb_from_values(&args, hue, whiteness, blackness, alpha)
+}
+
+fn hwb_from_values(
+    args: &CallArgs,
+    hue: Value,
+    whiteness: Value,
+    blackness: Value,
+    alpha: Option<Value>,
+) -> SassResult<Value> {
+    let hue = match hue {
+        Value::Dimension(n, u, _) if u.kind() == UnitKind::Angle => {
+            n * Unit::Deg.conversion_factor(&u)
+        }
+        Value::Dimension(n, ..) => n,
+        v => {
+            return Err((
+                format!("$hue: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let whiteness = match whiteness {
+        Value::Dimension(n, Unit::Percent, _) => {
+            bound!(args, "whiteness", n, Unit::Percent, 0, 100) / Number::from(100)
+        }
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$whiteness: Expected {} to have unit \"%\".",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$whiteness: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let blackness = match blackness {
+        Value::Dimension(n, Unit::Percent, _) => {
+            bound!(args, "blackness", n, Unit::Percent, 0, 100) / Number::from(100)
+        }
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$blackness: Expected {} to have unit \"%\".",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$blackness: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let alpha = parse_alpha_channel(alpha, args)?;
+
+    Ok(Value::Color(Box::new(Color::from_hwb(
+        hue, whiteness, blackness, alpha,
+    ))))
+}
+
+fn whiteness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(c.whiteness(), Unit::Percent, true)),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn blackness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(c.blackness(), Unit::Percent, true)),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn declare(f: &mut GlobalFunctionMap) {
+    f.insert("hwb", Builtin::new(hwb));
+}
+
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    f.insert("whiteness", Builtin::new(whiteness));
+    f.insert("blackness", Builtin::new(blackness));
+}
@@ -1,13 +1,195 @@
 use super::{Builtin, GlobalFunctionMap};
 
+use num_traits::One;
+
+use crate::{
+    args::CallArgs,
+    common::{Brackets, ListSeparator, QuoteKind},
+    error::SassResult,
+    unit::Unit,
+    value::{css_function::is_special_function, Number, Value},
+};
+
 mod hsl;
+mod hwb;
 mod opacity;
 mod other;
 mod rgb;
+mod space;
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     hsl::declare(f);
+    hwb::declare(f);
     opacity::declare(f);
     other::declare(f);
     rgb::declare(f);
+    space::declare(f);
+}
+
+/// Functions which are only available through `sass:color`
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    hwb::declare_module(f);
+    other::declare_module(f);
+    space::declare_module(f);
+}
+
+/// Split the alpha channel from the channels of the space separated color
+/// syntax, e.g. `hwb(120deg 30% 40% / 0.5)`
+fn split_slash_alpha(mut channels: Vec<Value>) -> (Vec<Value>, Option<Value>) {
+    match channels.pop() {
+        Some(Value::SlashNumber(slash)) => {
+            let (channel, alpha) = slash.operands();
+            channels.push(channel.clone());
+            (channels, Some(alpha.clone()))
+        }
+        // dividing by a special function, as in `1 2 3 / var(--alpha)`, results
+        // in an unquoted string
+        Some(Value::String(text, QuoteKind::None)) if !is_special_function(&text) => {
+            match text.find('/') {
+                Some(idx)
+                    if is_special_function(&text[..idx])
+                        || is_special_function(&text[idx + 1..]) =>
+                {
+                    channels.push(Value::String(text[..idx].into(), QuoteKind::None));
+                    let alpha = Value::String(text[idx + 1..].into(), QuoteKind::None);
+                    (channels, Some(alpha))
+                }
+                _ => {
+                    channels.push(Value::String(text, QuoteKind::None));
+                    (channels, None)
+                }
+            }
+        }
+        Some(last) => {
+            channels.push(last);
+            (channels, None)
+        }
+        None => (channels, None),
+    }
+}
+
+/// Parse an alpha channel that was split from a space separated color, which
+/// may be either unitless or a percentage
+fn parse_alpha_channel(alpha: Option<Value>, args: &CallArgs) -> SassResult<Number> {
+    match alpha {
+        None => Ok(Number::one()),
+        Some(Value::Dimension(n, Unit::None, _)) => Ok(n),
+        Some(Value::Dimension(n, Unit::Percent, _)) => Ok(n / Number::from(100)),
+        Some(v @ Value::Dimension(..)) => Err((
+            format!(
+                "$alpha: Expected {} to have no units or \"%\".",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+        Some(v) => Err((
+            format!("$alpha: {} is not a number.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+/// Emit a space separated color as a plain CSS function, because one of its
+/// channels is a special function such as `var(--alpha)`
+fn plain_css_color(
+    name: &str,
+    channels: &[Value],
+    alpha: Option<&Value>,
+    args: &CallArgs,
+) -> SassResult<Value> {
+    let mut string = format!("{}(", name);
+    for (idx, channel) in channels.iter().enumerate() {
+        if idx != 0 {
+            string.push(' ');
+        }
+        string.push_str(&channel.to_css_string(args.span())?);
+    }
+    if let Some(alpha) = alpha {
+        string.push_str(" / ");
+        string.push_str(&alpha.to_css_string(args.span())?);
+    }
+    string.push(')');
+    Ok(Value::String(string.into(), QuoteKind::None))
+}
+
+/// The channels of a color written with the space separated syntax
+enum ParsedChannels {
+    Channels(Vec<Value>, Option<Value>),
+    /// The color contains a special function, and so is emitted as is
+    PlainCss(Value),
+}
+
+/// Parse the single argument of a space separated color function such as
+/// `hwb(120deg 30% 40% / 0.5)`, which must have one element for each of
+/// `channel_names`
+fn parse_channels(
+    name: &str,
+    arg_name: &str,
+    value: Value,
+    channel_names: &[&str],
+    args: &CallArgs,
+) -> SassResult<ParsedChannels> {
+    let channels = match value {
+        v @ Value::List(_, ListSeparator::Space, Brackets::None) => v.as_list(),
+        Value::List(_, separator, brackets) => {
+            let mut err = format!("${} must be", arg_name);
+            if brackets == Brackets::Bracketed {
+                err.push_str(" an unbracketed");
+            }
+            if separator != ListSeparator::Space {
+                err.push_str(if brackets == Brackets::Bracketed {
+                    ","
+                } else {
+                    " a"
+                });
+                err.push_str(" space-separated");
+            }
+            err.push_str(" list.");
+            return Err((err, args.span()).into());
+        }
+        v if v.is_special_function() => {
+            return Ok(ParsedChannels::PlainCss(Value::String(
+                format!("{}({})", name, v.to_css_string(args.span())?).into(),
+                QuoteKind::None,
+            )))
+        }
+        v => vec![v],
+    };
+
+    let (channels, alpha) = split_slash_alpha(channels);
+
+    if channels.len() > channel_names.len() {
+        return Err((
+            format!(
+                "Only {} elements allowed, but {} were passed.",
+                channel_names.len(),
+                channels.len()
+            ),
+            args.span(),
+        )
+            .into());
+    }
+
+    if channels.iter().any(Value::is_special_function)
+        || alpha.as_ref().map_or(false, Value::is_special_function)
+    {
+        return Ok(ParsedChannels::PlainCss(plain_css_color(
+            name,
+            &channels,
+            alpha.as_ref(),
+            args,
+        )?));
+    }
+
+    if channels.len() < channel_names.len() {
+        return Err((
+            format!("Missing element ${}.", channel_names[channels.len()]),
+            args.span(),
+        )
+            .into());
+    }
+
+    Ok(ParsedChannels::Channels(channels, alpha))
 }
@@ -22,7 +22,7 @@ fn opacity(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     match args.get_err(0, "color")? {
         Value::Color(c) => Ok(Value::Dimension(c.alpha(), Unit::None, true)),
         Value::Dimension(num, unit, _) => Ok(Value::String(
-            format!("opacity({}{})", num, unit),
+            format!("opacity({}{})", num, unit).into(),
             QuoteKind::None,
         )),
         v => Err((
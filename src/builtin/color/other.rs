@@ -5,7 +5,7 @@ use num_traits::{One, Signed, Zero};
 use crate::{
     args::CallArgs,
     color::Color,
-    common::QuoteKind,
+    common::{Brackets, ListSeparator, QuoteKind},
     error::SassResult,
     parse::Parser,
     unit::Unit,
@@ -46,6 +46,35 @@ macro_rules! opt_hsl {
     };
 }
 
+macro_rules! opt_hwb {
+    ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
+        let $name = match $args.default_named_arg($arg, Value::Null)? {
+            Value::Dimension(n, Unit::Percent, _) => {
+                Some(bound!($args, $arg, n, Unit::Percent, $low, $high) / Number::from(100))
+            }
+            v @ Value::Dimension(..) => {
+                return Err((
+                    format!(
+                        "${}: Expected {} to have unit \"%\".",
+                        $arg,
+                        v.inspect($args.span())?
+                    ),
+                    $args.span(),
+                )
+                    .into())
+            }
+            Value::Null => None,
+            v => {
+                return Err((
+                    format!("${}: {} is not a number.", $arg, v.inspect($args.span())?),
+                    $args.span(),
+                )
+                    .into())
+            }
+        };
+    };
+}
+
 fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     if args.positional_arg(1).is_some() {
         return Err((
@@ -70,6 +99,18 @@ fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
     opt_rgba!(args, red, "red", 0, 255);
     opt_rgba!(args, green, "green", 0, 255);
     opt_rgba!(args, blue, "blue", 0, 255);
+    opt_hwb!(args, whiteness, "whiteness", 0, 100);
+    opt_hwb!(args, blackness, "blackness", 0, 100);
+
+    if (red.is_some() || green.is_some() || blue.is_some())
+        && (whiteness.is_some() || blackness.is_some())
+    {
+        return Err((
+            "RGB parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
 
     if red.is_some() || green.is_some() || blue.is_some() {
         return Ok(Value::Color(Box::new(Color::from_rgba(
@@ -95,6 +136,25 @@ fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
     opt_hsl!(args, saturation, "saturation", 0, 100);
     opt_hsl!(args, luminance, "lightness", 0, 100);
 
+    if (saturation.is_some() || luminance.is_some()) && (whiteness.is_some() || blackness.is_some())
+    {
+        return Err((
+            "HSL parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
+
+    if whiteness.is_some() || blackness.is_some() {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwba();
+        return Ok(Value::Color(Box::new(Color::from_hwb(
+            hue.unwrap_or(this_hue),
+            whiteness.unwrap_or(this_whiteness),
+            blackness.unwrap_or(this_blackness),
+            alpha.unwrap_or(this_alpha),
+        ))));
+    }
+
     if hue.is_some() || saturation.is_some() || luminance.is_some() {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
@@ -129,6 +189,18 @@ fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
     opt_rgba!(args, red, "red", -255, 255);
     opt_rgba!(args, green, "green", -255, 255);
     opt_rgba!(args, blue, "blue", -255, 255);
+    opt_hwb!(args, whiteness, "whiteness", -100, 100);
+    opt_hwb!(args, blackness, "blackness", -100, 100);
+
+    if (red.is_some() || green.is_some() || blue.is_some())
+        && (whiteness.is_some() || blackness.is_some())
+    {
+        return Err((
+            "RGB parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
 
     if red.is_some() || green.is_some() || blue.is_some() {
         return Ok(Value::Color(Box::new(Color::from_rgba(
@@ -154,6 +226,25 @@ fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
     opt_hsl!(args, saturation, "saturation", -100, 100);
     opt_hsl!(args, luminance, "lightness", -100, 100);
 
+    if (saturation.is_some() || luminance.is_some()) && (whiteness.is_some() || blackness.is_some())
+    {
+        return Err((
+            "HSL parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
+
+    if whiteness.is_some() || blackness.is_some() {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwba();
+        return Ok(Value::Color(Box::new(Color::from_hwb(
+            this_hue + hue.unwrap_or_else(Number::zero),
+            this_whiteness + whiteness.unwrap_or_else(Number::zero),
+            this_blackness + blackness.unwrap_or_else(Number::zero),
+            this_alpha + alpha.unwrap_or_else(Number::zero),
+        ))));
+    }
+
     if hue.is_some() || saturation.is_some() || luminance.is_some() {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
@@ -174,13 +265,13 @@ fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
 }
 
 #[allow(clippy::cognitive_complexity)]
-// todo: refactor into rgb and hsl?
+// todo: refactor into rgb, hsl, and hwb?
 fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     fn scale(val: Number, by: Number, max: Number) -> Number {
         if by.is_zero() {
             return val;
         }
-        val.clone() + (if by.is_positive() { max - val } else { val }) * by
+        val + (if by.is_positive() { max - val } else { val }) * by
     }
 
     let span = args.span();
@@ -228,6 +319,18 @@ fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
     opt_scale_arg!(args, red, "red", -100, 100);
     opt_scale_arg!(args, green, "green", -100, 100);
     opt_scale_arg!(args, blue, "blue", -100, 100);
+    opt_scale_arg!(args, whiteness, "whiteness", -100, 100);
+    opt_scale_arg!(args, blackness, "blackness", -100, 100);
+
+    if (red.is_some() || green.is_some() || blue.is_some())
+        && (whiteness.is_some() || blackness.is_some())
+    {
+        return Err((
+            "RGB parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
 
     if red.is_some() || green.is_some() || blue.is_some() {
         return Ok(Value::Color(Box::new(Color::from_rgba(
@@ -257,6 +360,37 @@ fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
     opt_scale_arg!(args, saturation, "saturation", -100, 100);
     opt_scale_arg!(args, luminance, "lightness", -100, 100);
 
+    if (saturation.is_some() || luminance.is_some()) && (whiteness.is_some() || blackness.is_some())
+    {
+        return Err((
+            "HSL parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
+
+    if whiteness.is_some() || blackness.is_some() {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwba();
+        return Ok(Value::Color(Box::new(Color::from_hwb(
+            this_hue,
+            scale(
+                this_whiteness,
+                whiteness.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+            scale(
+                this_blackness,
+                blackness.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+            scale(
+                this_alpha,
+                alpha.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+        ))));
+    }
+
     if saturation.is_some() || luminance.is_some() {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
@@ -300,7 +434,156 @@ fn ie_hex_str(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
                 .into())
         }
     };
-    Ok(Value::String(color.to_ie_hex_str(), QuoteKind::None))
+    Ok(Value::String(color.to_ie_hex_str().into(), QuoteKind::None))
+}
+
+fn luminance(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(c.relative_luminance(), Unit::None, true)),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn contrast(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color1 = match args.get_err(0, "color1")? {
+        Value::Color(c) => c,
+        v => {
+            return Err((
+                format!("$color1: {} is not a color.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let color2 = match args.get_err(1, "color2")? {
+        Value::Color(c) => c,
+        v => {
+            return Err((
+                format!("$color2: {} is not a color.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    Ok(Value::Dimension(
+        color1.contrast_ratio(&color2),
+        Unit::None,
+        true,
+    ))
+}
+
+/// Pick the candidate with the greatest contrast against the background,
+/// either as `color-contrast($background, $candidates...)` or with the CSS
+/// syntax `color-contrast(wheat vs tan, sienna)`
+///
+/// If `$target` is passed, the first candidate to reach that contrast ratio
+/// is chosen instead. `AA` and `AAA` may be used for the WCAG 2 thresholds
+/// for normal text
+fn color_contrast(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+
+    let target = match args.default_named_arg("target", Value::Null)? {
+        Value::Null => None,
+        Value::Dimension(n, Unit::None, _) => Some(n),
+        Value::String(s, ..) if s.eq_ignore_ascii_case("aa") => Some(Number(4.5)),
+        Value::String(s, ..) if s.eq_ignore_ascii_case("aaa") => Some(Number::from(7)),
+        v => {
+            return Err((
+                format!(
+                    "$target: Expected {} to be a unitless number, AA, or AAA.",
+                    v.inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let mut values: Vec<Value> = args.get_variadic()?.into_iter().map(|v| v.node).collect();
+
+    let is_special_function = |value: &Value| match value {
+        Value::List(v, ..) => v.iter().any(Value::is_special_function),
+        v => v.is_special_function(),
+    };
+
+    if values.iter().any(is_special_function) {
+        let mut string = String::from("color-contrast(");
+        for (idx, value) in values.iter().enumerate() {
+            if idx != 0 {
+                string.push_str(", ");
+            }
+            string.push_str(&value.to_css_string(span)?);
+        }
+        string.push(')');
+        return Ok(Value::String(string.into(), QuoteKind::None));
+    }
+
+    if values.is_empty() {
+        return Err(("Missing argument $background.", span).into());
+    }
+
+    let (background, candidates) = match values.remove(0) {
+        Value::List(v, ListSeparator::Space, Brackets::None)
+            if v.len() == 3 && v[1] == Value::String("vs".into(), QuoteKind::None) =>
+        {
+            values.insert(0, v[2].clone());
+            (v[0].clone(), values)
+        }
+        background if values.len() == 1 => (background, values.pop().unwrap().as_list()),
+        background => (background, values),
+    };
+
+    let background = match background {
+        Value::Color(c) => c,
+        v => {
+            return Err((
+                format!("$background: {} is not a color.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    if candidates.is_empty() {
+        return Err(("$candidates: At least one color must be passed.", span).into());
+    }
+
+    let mut best: Option<(Box<Color>, Number)> = None;
+
+    for candidate in candidates {
+        let candidate = match candidate {
+            Value::Color(c) => c,
+            v => {
+                return Err((
+                    format!("$candidates: {} is not a color.", v.inspect(span)?),
+                    span,
+                )
+                    .into())
+            }
+        };
+
+        let ratio = background.contrast_ratio(&candidate);
+
+        if let Some(target) = target {
+            if ratio >= target {
+                return Ok(Value::Color(candidate));
+            }
+        }
+
+        match &best {
+            Some((_, best_ratio)) if *best_ratio >= ratio => {}
+            _ => best = Some((candidate, ratio)),
+        }
+    }
+
+    // if no candidate reaches `$target`, the best candidate is still chosen
+    Ok(Value::Color(best.unwrap().0))
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
@@ -309,3 +592,11 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("scale-color", Builtin::new(scale_color));
     f.insert("ie-hex-str", Builtin::new(ie_hex_str));
 }
+
+/// `contrast()` and `color-contrast()` are also plain CSS functions, so these
+/// are only available through `sass:color`
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    f.insert("luminance", Builtin::new(luminance));
+    f.insert("contrast", Builtin::new(contrast));
+    f.insert("color-contrast", Builtin::new(color_contrast));
+}
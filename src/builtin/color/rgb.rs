@@ -1,4 +1,7 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{
+    parse_alpha_channel, plain_css_color, space::parse_interpolation_method, split_slash_alpha,
+    Builtin, GlobalFunctionMap,
+};
 
 use num_traits::One;
 
@@ -21,11 +24,17 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
     }
 
     if args.len() == 1 {
-        let mut channels = match args.get_err(0, "channels")? {
-            Value::List(v, ..) => v,
+        let channels = match args.get_err(0, "channels")? {
+            v @ Value::List(..) => v.as_list(),
             _ => return Err(("Missing argument $channels.", args.span()).into()),
         };
 
+        let (mut channels, alpha) = split_slash_alpha(channels);
+
+        if let Some(alpha) = alpha.as_ref().filter(|alpha| alpha.is_special_function()) {
+            return plain_css_color(name, &channels, Some(alpha), &args);
+        }
+
         if channels.len() > 3 {
             return Err((
                 format!(
@@ -52,7 +61,8 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                         red.to_css_string(args.span())?,
                         green.to_css_string(args.span())?,
                         v.to_css_string(args.span())?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ));
             }
@@ -82,7 +92,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     ),
                     None => format!("{}({} {})", name, v.to_css_string(args.span())?, blue),
                 };
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             Some(v) => {
                 return Err((
@@ -107,7 +117,8 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                         v.to_css_string(args.span())?,
                         green,
                         blue
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ));
             }
@@ -121,7 +132,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
             None => return Err(("Missing element $red.", args.span()).into()),
         };
 
-        let color = Color::from_rgba(red, green, blue, Number::one());
+        let color = Color::from_rgba(red, green, blue, parse_alpha_channel(alpha, &args)?);
 
         Ok(Value::Color(Box::new(color)))
     } else if args.len() == 2 {
@@ -135,7 +146,8 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                         name,
                         v.to_css_string(args.span())?,
                         alpha.to_css_string(args.span())?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ));
             }
@@ -169,7 +181,8 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                         color.green(),
                         color.blue(),
                         v.to_css_string(args.span())?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ));
             }
@@ -211,7 +224,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
                 }
                 string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -248,7 +261,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
                 }
                 string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -284,7 +297,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
                 }
                 string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -320,7 +333,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                     blue,
                     v.to_css_string(args.span())?
                 );
-                return Ok(Value::String(string, QuoteKind::None));
+                return Ok(Value::String(string.into(), QuoteKind::None));
             }
             v => {
                 return Err((
@@ -381,7 +394,7 @@ fn blue(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 }
 
 fn mix(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(3)?;
+    args.max_args(4)?;
     let color1 = match args.get_err(0, "color1")? {
         Value::Color(c) => c,
         v => {
@@ -421,7 +434,31 @@ fn mix(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
                 .into())
         }
     };
-    Ok(Value::Color(Box::new(color1.mix(&color2, weight))))
+
+    match args.default_arg(3, "method", Value::Null)? {
+        Value::Null => {
+            for (name, color) in [("color1", &color1), ("color2", &color2)].iter() {
+                if !color.is_legacy() {
+                    return Err((
+                        format!(
+                            "${}: To use color.mix() with non-legacy color {}, you must provide a $method.",
+                            name, color
+                        ),
+                        args.span(),
+                    )
+                        .into());
+                }
+            }
+
+            Ok(Value::Color(Box::new(color1.mix(&color2, weight))))
+        }
+        method => {
+            let (space, hue_method) = parse_interpolation_method(method, &args)?;
+            Ok(Value::Color(Box::new(
+                color1.interpolate(&color2, weight, space, hue_method),
+            )))
+        }
+    }
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
@@ -0,0 +1,404 @@
+use super::{parse_alpha_channel, parse_channels, Builtin, GlobalFunctionMap, ParsedChannels};
+
+use crate::{
+    args::CallArgs,
+    color::{Color, ColorSpace, GamutMapMethod, HueInterpolationMethod},
+    common::{ListSeparator, QuoteKind},
+    error::SassResult,
+    parse::Parser,
+    unit::{Unit, UnitKind},
+    value::{Number, Value},
+};
+
+/// The value that `100%` represents for each channel of `space`
+fn percent_reference(space: ColorSpace, channel: usize) -> f64 {
+    match (space, channel) {
+        (ColorSpace::Lab, 0) | (ColorSpace::Lch, 0) => 100.0,
+        (ColorSpace::Lab, _) => 125.0,
+        (ColorSpace::Lch, _) => 150.0,
+        (ColorSpace::Oklab, 0) | (ColorSpace::Oklch, 0) => 1.0,
+        (ColorSpace::Oklab, _) | (ColorSpace::Oklch, _) => 0.4,
+        _ => 1.0,
+    }
+}
+
+/// Parse a single channel of a color in a non-legacy space, returning `None`
+/// for missing channels written as `none`
+fn parse_channel(
+    space: ColorSpace,
+    channel: usize,
+    value: Value,
+    args: &CallArgs,
+) -> SassResult<Option<Number>> {
+    let name = space.channel_names()[channel];
+
+    let n = match value {
+        Value::String(s, QuoteKind::None) if s.eq_ignore_ascii_case("none") => return Ok(None),
+        Value::Dimension(n, Unit::None, _) => n,
+        Value::Dimension(n, u, _) if space.hue_index() == Some(channel) => {
+            if u.kind() != UnitKind::Angle {
+                return Err((
+                    format!(
+                        "${}: Expected {}{} to have an angle unit (deg, grad, rad, turn).",
+                        name, n, u
+                    ),
+                    args.span(),
+                )
+                    .into());
+            }
+            n * Unit::Deg.conversion_factor(&u)
+        }
+        Value::Dimension(n, Unit::Percent, _) => {
+            n / Number::from(100) * Number(percent_reference(space, channel))
+        }
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "${}: Expected {} to have no units or \"%\".",
+                    name,
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("${}: {} is not a number.", name, v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    Ok(Some(match (space, channel) {
+        (ColorSpace::Lab, 0) | (ColorSpace::Lch, 0) => n.clamp(0, 100),
+        (ColorSpace::Oklab, 0) | (ColorSpace::Oklch, 0) => n.clamp(0, 1),
+        (ColorSpace::Lch, 1) | (ColorSpace::Oklch, 1) => n.clamp(0, f64::INFINITY),
+        _ => n,
+    }))
+}
+
+fn color_from_channels(
+    space: ColorSpace,
+    channels: Vec<Value>,
+    alpha: Option<Value>,
+    args: &CallArgs,
+) -> SassResult<Value> {
+    let mut parsed = [None; 3];
+    for (idx, channel) in channels.into_iter().enumerate() {
+        parsed[idx] = parse_channel(space, idx, channel, args)?;
+    }
+
+    let alpha = parse_alpha_channel(alpha, args)?;
+
+    Ok(Value::Color(Box::new(Color::from_space(
+        space, parsed, alpha,
+    ))))
+}
+
+fn space_separated_color(
+    name: &'static str,
+    space: ColorSpace,
+    mut args: CallArgs,
+) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let channels = args.get_err(0, "channels")?;
+    match parse_channels(name, "channels", channels, &space.channel_names(), &args)? {
+        ParsedChannels::PlainCss(v) => Ok(v),
+        ParsedChannels::Channels(channels, alpha) => {
+            color_from_channels(space, channels, alpha, &args)
+        }
+    }
+}
+
+fn lab(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    space_separated_color("lab", ColorSpace::Lab, args)
+}
+
+fn lch(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    space_separated_color("lch", ColorSpace::Lch, args)
+}
+
+fn oklab(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    space_separated_color("oklab", ColorSpace::Oklab, args)
+}
+
+fn oklch(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    space_separated_color("oklch", ColorSpace::Oklch, args)
+}
+
+/// `color(display-p3 1 0 0 / 0.5)`
+fn color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let description = args.get_err(0, "description")?;
+
+    let space = match &description {
+        Value::List(v, ListSeparator::Space, ..) => match v.first() {
+            Some(v @ Value::String(name, QuoteKind::None)) if !v.is_special_function() => {
+                match ColorSpace::from_name(name) {
+                    Some(space) if space.is_predefined() => space,
+                    _ => {
+                        return Err((
+                            format!("$description: Unknown color space \"{}\".", name),
+                            args.span(),
+                        )
+                            .into())
+                    }
+                }
+            }
+            _ => ColorSpace::Srgb,
+        },
+        _ => ColorSpace::Srgb,
+    };
+
+    let [red, green, blue] = space.channel_names();
+
+    match parse_channels(
+        "color",
+        "description",
+        description,
+        &["space", red, green, blue],
+        &args,
+    )? {
+        ParsedChannels::PlainCss(v) => Ok(v),
+        ParsedChannels::Channels(mut channels, alpha) => {
+            if !matches!(channels.first(), Some(Value::String(_, QuoteKind::None))) {
+                return Err((
+                    format!(
+                        "$description: Expected {} to be an unquoted string.",
+                        channels[0].inspect(args.span())?
+                    ),
+                    args.span(),
+                )
+                    .into());
+            }
+            channels.remove(0);
+            color_from_channels(space, channels, alpha, &args)
+        }
+    }
+}
+
+fn get_color(args: &mut CallArgs, position: usize, name: &'static str) -> SassResult<Box<Color>> {
+    match args.get_err(position, name)? {
+        Value::Color(c) => Ok(c),
+        v => Err((
+            format!("${}: {} is not a color.", name, v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+/// Get the `$space` argument of the color space functions, which defaults
+/// to the space of the color
+fn get_space(args: &mut CallArgs, position: usize, color: &Color) -> SassResult<ColorSpace> {
+    match args.default_arg(position, "space", Value::Null)? {
+        Value::Null => Ok(color.space()),
+        v => parse_space(v, args),
+    }
+}
+
+fn parse_space(space: Value, args: &CallArgs) -> SassResult<ColorSpace> {
+    match space {
+        Value::String(name, ..) => ColorSpace::from_name(&name).ok_or_else(|| {
+            (
+                format!("$space: Unknown color space \"{}\".", name),
+                args.span(),
+            )
+                .into()
+        }),
+        v => Err((
+            format!("$space: {} is not a string.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn to_space(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color = get_color(&mut args, 0, "color")?;
+    let space = parse_space(args.get_err(1, "space")?, &args)?;
+    Ok(Value::Color(Box::new(color.to_space(space))))
+}
+
+fn is_in_gamut(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color = get_color(&mut args, 0, "color")?;
+    let space = get_space(&mut args, 1, &color)?;
+    Ok(Value::bool(color.is_in_gamut(space)))
+}
+
+fn to_gamut(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(3)?;
+    let color = get_color(&mut args, 0, "color")?;
+    let space = get_space(&mut args, 1, &color)?;
+
+    let method = match args.default_arg(2, "method", Value::Null)? {
+        Value::Null => return Err((
+            "color.to-gamut() requires a $method argument for forwards-compatibility with changes in the CSS spec. Suggestion:\n\n$method: local-minde",
+            args.span(),
+        )
+            .into()),
+        Value::String(name, ..) => GamutMapMethod::from_name(&name).ok_or_else(|| {
+            (
+                format!("$method: Unknown gamut map method \"{}\".", name),
+                args.span(),
+            )
+        })?,
+        v => {
+            return Err((
+                format!("$method: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    Ok(Value::Color(Box::new(color.to_gamut(space, method))))
+}
+
+fn channel(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(3)?;
+    let color = get_color(&mut args, 0, "color")?;
+
+    let channel = match args.get_err(1, "channel")? {
+        Value::String(s, QuoteKind::Quoted) => s,
+        v => {
+            return Err((
+                format!(
+                    "$channel: Expected {} to be a quoted string.",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let space = get_space(&mut args, 2, &color)?;
+
+    if &*channel == "alpha" {
+        return Ok(Value::Dimension(color.alpha(), Unit::None, true));
+    }
+
+    let idx = match space
+        .channel_names()
+        .iter()
+        .position(|&name| name == &*channel)
+    {
+        Some(idx) => idx,
+        None => {
+            return Err((
+                format!(
+                    "$channel: Color {} has no channel named {}.",
+                    color.to_space(space),
+                    channel
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let unit = match (space, idx) {
+        (_, idx) if space.hue_index() == Some(idx) => Unit::Deg,
+        (ColorSpace::Hsl, _) | (ColorSpace::Hwb, _) => Unit::Percent,
+        (ColorSpace::Lab, 0) | (ColorSpace::Lch, 0) => Unit::Percent,
+        _ => Unit::None,
+    };
+
+    Ok(Value::Dimension(color.channels_in(space)[idx], unit, true))
+}
+
+/// Parse the `$method` argument of `mix()`, such as `oklch longer hue`
+pub(super) fn parse_interpolation_method(
+    method: Value,
+    args: &CallArgs,
+) -> SassResult<(ColorSpace, HueInterpolationMethod)> {
+    let parts = match method {
+        v @ Value::List(_, ListSeparator::Space, ..) => v.as_list(),
+        v => vec![v],
+    };
+
+    let mut names = Vec::with_capacity(parts.len());
+    for part in &parts {
+        match part {
+            Value::String(s, QuoteKind::None) => names.push(&**s),
+            v => {
+                return Err((
+                    format!(
+                        "$method: Expected {} to be an unquoted string.",
+                        v.inspect(args.span())?
+                    ),
+                    args.span(),
+                )
+                    .into())
+            }
+        }
+    }
+
+    let space = match names.first() {
+        Some(name) => ColorSpace::from_name(name).ok_or_else(|| {
+            (
+                format!("$method: Unknown color space \"{}\".", name),
+                args.span(),
+            )
+        })?,
+        None => return Err(("$method: Expected a color space.", args.span()).into()),
+    };
+
+    match names.as_slice() {
+        [_] => Ok((space, HueInterpolationMethod::Shorter)),
+        [_, method, hue] if hue.eq_ignore_ascii_case("hue") => {
+            if space.hue_index().is_none() {
+                return Err((
+                    format!(
+                        "$method: Hue interpolation method \"{} hue\" may not be set for rectangular color space {}.",
+                        method, space
+                    ),
+                    args.span(),
+                )
+                    .into());
+            }
+
+            match HueInterpolationMethod::from_name(method) {
+                Some(hue_method) => Ok((space, hue_method)),
+                None => Err((
+                    format!(
+                        "$method: Unknown hue interpolation method {}.",
+                        method
+                    ),
+                    args.span(),
+                )
+                    .into()),
+            }
+        }
+        _ => Err((
+            format!(
+                "$method: Expected a color space optionally followed by a hue interpolation method, was {}.",
+                names.join(" ")
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn declare(f: &mut GlobalFunctionMap) {
+    f.insert("lab", Builtin::new(lab));
+    f.insert("lch", Builtin::new(lch));
+    f.insert("oklab", Builtin::new(oklab));
+    f.insert("oklch", Builtin::new(oklch));
+    f.insert("color", Builtin::new(color));
+}
+
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    f.insert("to-space", Builtin::new(to_space));
+    f.insert("is-in-gamut", Builtin::new(is_in_gamut));
+    f.insert("to-gamut", Builtin::new(to_gamut));
+    f.insert("channel", Builtin::new(channel));
+}
@@ -1,5 +1,7 @@
 use super::{Builtin, GlobalFunctionMap};
 
+use std::rc::Rc;
+
 use num_traits::{Signed, ToPrimitive, Zero};
 
 use crate::{
@@ -14,7 +16,7 @@ use crate::{
 fn length(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     Ok(Value::Dimension(
-        Number::from(args.get_err(0, "list")?.as_list().len()),
+        Number::from(args.get_err(0, "list")?.as_slice().len()),
         Unit::None,
         true,
     ))
@@ -22,7 +24,8 @@ fn length(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 
 fn nth(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(2)?;
-    let mut list = args.get_err(0, "list")?.as_list();
+    let list = args.get_err(0, "list")?;
+    let list = list.as_slice();
     let n = match args.get_err(1, "n")? {
         Value::Dimension(num, ..) => num,
         v => {
@@ -54,11 +57,12 @@ fn nth(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         return Err((format!("$n: {} is not an int.", n), args.span()).into());
     }
 
-    Ok(list.remove(if n.is_positive() {
+    Ok(list[if n.is_positive() {
         n.to_integer().to_usize().unwrap_or(std::usize::MAX) - 1
     } else {
         list.len() - n.abs().to_integer().to_usize().unwrap_or(std::usize::MAX)
-    }))
+    }]
+    .clone())
 }
 
 fn list_separator(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -68,7 +72,7 @@ fn list_separator(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Val
             Value::List(_, sep, ..) => sep.name(),
             _ => ListSeparator::Space.name(),
         }
-        .to_owned(),
+        .into(),
         QuoteKind::None,
     ))
 }
@@ -77,8 +81,8 @@ fn set_nth(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(3)?;
     let (mut list, sep, brackets) = match args.get_err(0, "list")? {
         Value::List(v, sep, b) => (v, sep, b),
-        Value::Map(m) => (m.as_list(), ListSeparator::Comma, Brackets::None),
-        v => (vec![v], ListSeparator::Space, Brackets::None),
+        Value::Map(m) => (Rc::new(m.as_list()), ListSeparator::Comma, Brackets::None),
+        v => (Rc::new(vec![v]), ListSeparator::Space, Brackets::None),
     };
     let n = match args.get_err(1, "n")? {
         Value::Dimension(num, ..) => num,
@@ -111,10 +115,12 @@ fn set_nth(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 
     let val = args.get_err(2, "value")?;
 
+    let list_mut = Rc::make_mut(&mut list);
+
     if n.is_positive() {
-        list[n.to_integer().to_usize().unwrap_or(std::usize::MAX) - 1] = val;
+        list_mut[n.to_integer().to_usize().unwrap_or(std::usize::MAX) - 1] = val;
     } else {
-        list[len - n.abs().to_integer().to_usize().unwrap_or(std::usize::MAX)] = val;
+        list_mut[len - n.abs().to_integer().to_usize().unwrap_or(std::usize::MAX)] = val;
     }
 
     Ok(Value::List(list, sep, brackets))
@@ -124,21 +130,22 @@ fn append(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(3)?;
     let (mut list, sep, brackets) = match args.get_err(0, "list")? {
         Value::List(v, sep, b) => (v, sep, b),
-        v => (vec![v], ListSeparator::Space, Brackets::None),
+        v => (Rc::new(vec![v]), ListSeparator::Space, Brackets::None),
     };
     let val = args.get_err(1, "val")?;
     let sep = match args.default_arg(
         2,
         "separator",
-        Value::String("auto".to_owned(), QuoteKind::None),
+        Value::String("auto".into(), QuoteKind::None),
     )? {
-        Value::String(s, ..) => match s.as_str() {
+        Value::String(s, ..) => match &*s {
             "auto" => sep,
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -153,7 +160,7 @@ fn append(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         }
     };
 
-    list.push(val);
+    Rc::make_mut(&mut list).push(val);
 
     Ok(Value::List(list, sep, brackets))
 }
@@ -162,20 +169,20 @@ fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(4)?;
     let (mut list1, sep1, brackets) = match args.get_err(0, "list1")? {
         Value::List(v, sep, brackets) => (v, sep, brackets),
-        Value::Map(m) => (m.as_list(), ListSeparator::Comma, Brackets::None),
-        v => (vec![v], ListSeparator::Space, Brackets::None),
+        Value::Map(m) => (Rc::new(m.as_list()), ListSeparator::Comma, Brackets::None),
+        v => (Rc::new(vec![v]), ListSeparator::Space, Brackets::None),
     };
     let (list2, sep2) = match args.get_err(1, "list2")? {
         Value::List(v, sep, ..) => (v, sep),
-        Value::Map(m) => (m.as_list(), ListSeparator::Comma),
-        v => (vec![v], ListSeparator::Space),
+        Value::Map(m) => (Rc::new(m.as_list()), ListSeparator::Comma),
+        v => (Rc::new(vec![v]), ListSeparator::Space),
     };
     let sep = match args.default_arg(
         2,
         "separator",
-        Value::String("auto".to_owned(), QuoteKind::None),
+        Value::String("auto".into(), QuoteKind::None),
     )? {
-        Value::String(s, ..) => match s.as_str() {
+        Value::String(s, ..) => match &*s {
             "auto" => {
                 if list1.is_empty() || (list1.len() == 1 && sep1 == ListSeparator::Space) {
                     sep2
@@ -185,9 +192,10 @@ fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
             }
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -205,9 +213,9 @@ fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     let brackets = match args.default_arg(
         3,
         "bracketed",
-        Value::String("auto".to_owned(), QuoteKind::None),
+        Value::String("auto".into(), QuoteKind::None),
     )? {
-        Value::String(s, ..) => match s.as_str() {
+        Value::String(s, ..) => match &*s {
             "auto" => brackets,
             _ => Brackets::Bracketed,
         },
@@ -220,7 +228,7 @@ fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         }
     };
 
-    list1.extend(list2);
+    Rc::make_mut(&mut list1).extend(list2.iter().cloned());
 
     Ok(Value::List(list1, sep, brackets))
 }
@@ -238,9 +246,9 @@ fn is_bracketed(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
 
 fn index(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(2)?;
-    let list = args.get_err(0, "list")?.as_list();
+    let list = args.get_err(0, "list")?;
     let value = args.get_err(1, "value")?;
-    let index = match list.into_iter().position(|v| v == value) {
+    let index = match list.as_slice().iter().position(|v| *v == value) {
         Some(v) => Number::from(v + 1),
         None => return Ok(Value::Null),
     };
@@ -248,17 +256,18 @@ fn index(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 }
 
 fn zip(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    let lists = args
+    let values = args
         .get_variadic()?
         .into_iter()
-        .map(|x| x.node.as_list())
-        .collect::<Vec<Vec<Value>>>();
+        .map(|x| x.node)
+        .collect::<Vec<Value>>();
+    let lists = values.iter().map(Value::as_slice).collect::<Vec<_>>();
 
-    let len = lists.iter().map(Vec::len).min().unwrap_or(0);
+    let len = lists.iter().map(|list| list.len()).min().unwrap_or(0);
 
     if len == 0 {
         return Ok(Value::List(
-            Vec::new(),
+            Rc::new(Vec::new()),
             ListSeparator::Comma,
             Brackets::None,
         ));
@@ -267,11 +276,34 @@ fn zip(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     let result = (0..len)
         .map(|i| {
             let items = lists.iter().map(|v| v[i].clone()).collect();
-            Value::List(items, ListSeparator::Space, Brackets::None)
+            Value::List(Rc::new(items), ListSeparator::Space, Brackets::None)
         })
         .collect();
 
-    Ok(Value::List(result, ListSeparator::Comma, Brackets::None))
+    Ok(Value::List(
+        Rc::new(result),
+        ListSeparator::Comma,
+        Brackets::None,
+    ))
+}
+
+fn slash(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+    let elements = args
+        .get_variadic()?
+        .into_iter()
+        .map(|x| x.node)
+        .collect::<Vec<Value>>();
+
+    if elements.len() < 2 {
+        return Err(("At least two elements are required.", span).into());
+    }
+
+    Ok(Value::List(
+        Rc::new(elements),
+        ListSeparator::Slash,
+        Brackets::None,
+    ))
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
@@ -285,3 +317,8 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("index", Builtin::new(index));
     f.insert("zip", Builtin::new(zip));
 }
+
+/// Functions which are only available through `sass:list`
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    f.insert("slash", Builtin::new(slash));
+}
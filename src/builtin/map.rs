@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use super::{Builtin, GlobalFunctionMap};
 
 use crate::{
@@ -59,7 +61,7 @@ fn map_keys(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         }
     };
     Ok(Value::List(
-        map.keys(),
+        Rc::new(map.keys()),
         ListSeparator::Comma,
         Brackets::None,
     ))
@@ -80,7 +82,7 @@ fn map_values(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
         }
     };
     Ok(Value::List(
-        map.values(),
+        Rc::new(map.values()),
         ListSeparator::Comma,
         Brackets::None,
     ))
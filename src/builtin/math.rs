@@ -1,15 +1,16 @@
 use super::{Builtin, GlobalFunctionMap};
 
+use num_traits::One;
 #[cfg(feature = "random")]
-use num_traits::{One, Signed, ToPrimitive, Zero};
+use num_traits::{Signed, ToPrimitive, Zero};
 #[cfg(feature = "random")]
 use rand::Rng;
 
 use crate::{
     args::CallArgs,
     error::SassResult,
-    parse::{HigherIntermediateValue, Parser, ValueVisitor},
-    unit::Unit,
+    parse::{Parser, ValueVisitor},
+    unit::{Unit, UnitKind},
     value::{Number, Value},
 };
 
@@ -116,6 +117,15 @@ fn comparable(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
 #[cfg(feature = "random")]
 fn random(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
+    if parser.options.deterministic {
+        return Err((
+            "random() is not available in deterministic mode.",
+            args.span(),
+        )
+            .into());
+    }
+    // the result of a function calling `random()` must not be memoized
+    parser.global_scope.function_cache().taint();
     let limit = match args.default_arg(0, "limit", Value::Null)? {
         Value::Dimension(n, ..) => n,
         Value::Null => {
@@ -189,16 +199,8 @@ fn min(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     for num in nums {
         if ValueVisitor::new(parser, span)
             .less_than(
-                HigherIntermediateValue::Literal(Value::Dimension(
-                    num.0.clone(),
-                    num.1.clone(),
-                    true,
-                )),
-                HigherIntermediateValue::Literal(Value::Dimension(
-                    min.0.clone(),
-                    min.1.clone(),
-                    true,
-                )),
+                Value::Dimension(num.0, num.1.clone(), true),
+                Value::Dimension(min.0, min.1.clone(), true),
             )?
             .is_true()
         {
@@ -227,16 +229,8 @@ fn max(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     for num in nums {
         if ValueVisitor::new(parser, span)
             .greater_than(
-                HigherIntermediateValue::Literal(Value::Dimension(
-                    num.0.clone(),
-                    num.1.clone(),
-                    true,
-                )),
-                HigherIntermediateValue::Literal(Value::Dimension(
-                    max.0.clone(),
-                    max.1.clone(),
-                    true,
-                )),
+                Value::Dimension(num.0, num.1.clone(), true),
+                Value::Dimension(max.0, max.1.clone(), true),
             )?
             .is_true()
         {
@@ -246,6 +240,352 @@ fn max(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     Ok(Value::Dimension(max.0, max.1, true))
 }
 
+/// Get the argument at `position` as a unitless number
+fn get_unitless(args: &mut CallArgs, position: usize, name: &'static str) -> SassResult<Number> {
+    match args.get_err(position, name)? {
+        Value::Dimension(n, Unit::None, _) => Ok(n),
+        v @ Value::Dimension(..) => Err((
+            format!(
+                "${}: Expected {} to have no units.",
+                name,
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+        v => Err((
+            format!("${}: {} is not a number.", name, v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+/// Describe the unit of a number for errors about mixing unitless numbers
+/// with numbers that have units
+fn describe_unit(unit: &Unit) -> String {
+    if unit == &Unit::None {
+        "is unitless".to_owned()
+    } else {
+        format!("has unit {}", unit)
+    }
+}
+
+fn sqrt(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let number = get_unitless(&mut args, 0, "number")?;
+    Ok(Value::Dimension(Number(number.0.sqrt()), Unit::None, true))
+}
+
+fn pow(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let base = get_unitless(&mut args, 0, "base")?;
+    let exponent = get_unitless(&mut args, 1, "exponent")?;
+    Ok(Value::Dimension(
+        Number(base.0.powf(exponent.0)),
+        Unit::None,
+        true,
+    ))
+}
+
+fn log(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let number = get_unitless(&mut args, 0, "number")?;
+
+    let base = match args.default_arg(1, "base", Value::Null)? {
+        Value::Null => None,
+        Value::Dimension(n, Unit::None, _) => Some(n),
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$base: Expected {} to have no units.",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$base: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    Ok(Value::Dimension(
+        match base {
+            // a base of exactly `1` would produce `NaN` or infinity, but a
+            // base that is only fuzzily `1` should behave the same way
+            Some(base) if base.is_one() => Number(number.0.ln() / base.round().0.ln()),
+            Some(base) => Number(number.0.ln() / base.0.ln()),
+            None => Number(number.0.ln()),
+        },
+        Unit::None,
+        true,
+    ))
+}
+
+/// Get the argument `$number` as an angle in radians. Unitless numbers are
+/// assumed to already be in radians
+fn get_radians(args: &mut CallArgs) -> SassResult<f64> {
+    match args.get_err(0, "number")? {
+        Value::Dimension(n, Unit::None, _) => Ok(n.0),
+        Value::Dimension(n, u, _) if u.kind() == UnitKind::Angle => {
+            Ok((n * Unit::Rad.conversion_factor(&u)).0)
+        }
+        v @ Value::Dimension(..) => Err((
+            format!(
+                "$number: Expected {} to have an angle unit (deg, grad, rad, turn).",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+        v => Err((
+            format!("$number: {} is not a number.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn sin(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let radians = get_radians(&mut args)?;
+    Ok(Value::Dimension(Number(radians.sin()), Unit::None, true))
+}
+
+fn cos(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let radians = get_radians(&mut args)?;
+    Ok(Value::Dimension(Number(radians.cos()), Unit::None, true))
+}
+
+fn tan(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let radians = get_radians(&mut args)?;
+    Ok(Value::Dimension(Number(radians.tan()), Unit::None, true))
+}
+
+fn asin(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let number = get_unitless(&mut args, 0, "number")?;
+    Ok(Value::Dimension(
+        Number(number.0.asin().to_degrees()),
+        Unit::Deg,
+        true,
+    ))
+}
+
+fn acos(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let number = get_unitless(&mut args, 0, "number")?;
+    Ok(Value::Dimension(
+        Number(number.0.acos().to_degrees()),
+        Unit::Deg,
+        true,
+    ))
+}
+
+fn atan(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let number = get_unitless(&mut args, 0, "number")?;
+    Ok(Value::Dimension(
+        Number(number.0.atan().to_degrees()),
+        Unit::Deg,
+        true,
+    ))
+}
+
+fn atan2(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let (y, y_unit) = match args.get_err(0, "y")? {
+        Value::Dimension(n, u, _) => (n, u),
+        v => {
+            return Err((
+                format!("$y: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let (x, x_unit) = match args.get_err(1, "x")? {
+        Value::Dimension(n, u, _) => (n, u),
+        v => {
+            return Err((
+                format!("$x: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    if (y_unit == Unit::None) != (x_unit == Unit::None) {
+        return Err((
+            format!(
+                "$y {} but $x {}. Arguments must all have units or all be unitless.",
+                describe_unit(&y_unit),
+                describe_unit(&x_unit)
+            ),
+            args.span(),
+        )
+            .into());
+    }
+
+    if !y_unit.comparable(&x_unit) {
+        return Err((
+            format!("$x: Incompatible units {} and {}.", x_unit, y_unit),
+            args.span(),
+        )
+            .into());
+    }
+
+    let x = x * y_unit.conversion_factor(&x_unit);
+
+    Ok(Value::Dimension(
+        Number(y.0.atan2(x.0).to_degrees()),
+        Unit::Deg,
+        true,
+    ))
+}
+
+fn hypot(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+    let numbers = args
+        .get_variadic()?
+        .into_iter()
+        .map(|val| match val.node {
+            Value::Dimension(number, unit, _) => Ok((number, unit)),
+            v => Err((format!("{} is not a number.", v.inspect(span)?), span).into()),
+        })
+        .collect::<SassResult<Vec<(Number, Unit)>>>()?;
+
+    let first_unit = match numbers.first() {
+        Some((_, unit)) => unit.clone(),
+        None => return Err(("At least one argument must be passed.", span).into()),
+    };
+
+    let mut subtotal = 0.0;
+
+    for (idx, (number, unit)) in numbers.into_iter().enumerate() {
+        if (unit == Unit::None) != (first_unit == Unit::None) {
+            return Err((
+                format!(
+                    "Argument 1 {} but argument {} {}. Arguments must all have units or all be unitless.",
+                    describe_unit(&first_unit),
+                    idx + 1,
+                    describe_unit(&unit)
+                ),
+                span,
+            )
+                .into());
+        }
+
+        if !unit.comparable(&first_unit) {
+            return Err((
+                format!("Incompatible units {} and {}.", unit, first_unit),
+                span,
+            )
+                .into());
+        }
+
+        let value = number * first_unit.conversion_factor(&unit);
+        subtotal += value.0 * value.0;
+    }
+
+    Ok(Value::Dimension(Number(subtotal.sqrt()), first_unit, true))
+}
+
+fn clamp(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(3)?;
+    let span = args.span();
+
+    let (min, min_unit) = match args.get_err(0, "min")? {
+        Value::Dimension(n, u, _) => (n, u),
+        v => return Err((format!("$min: {} is not a number.", v.inspect(span)?), span).into()),
+    };
+    let (number, number_unit) = match args.get_err(1, "number")? {
+        Value::Dimension(n, u, _) => (n, u),
+        v => {
+            return Err((
+                format!("$number: {} is not a number.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+    let (max, max_unit) = match args.get_err(2, "max")? {
+        Value::Dimension(n, u, _) => (n, u),
+        v => return Err((format!("$max: {} is not a number.", v.inspect(span)?), span).into()),
+    };
+
+    if (min_unit == Unit::None) != (number_unit == Unit::None)
+        || (number_unit == Unit::None) != (max_unit == Unit::None)
+    {
+        let (arg2_name, arg2_unit) = if (min_unit == Unit::None) != (number_unit == Unit::None) {
+            ("number", number_unit)
+        } else {
+            ("max", max_unit)
+        };
+        return Err((
+            format!(
+                "$min {} but ${} {}. Arguments must all have units or all be unitless.",
+                describe_unit(&min_unit),
+                arg2_name,
+                describe_unit(&arg2_unit)
+            ),
+            span,
+        )
+            .into());
+    }
+
+    let min = Value::Dimension(min, min_unit, true);
+    let number = Value::Dimension(number, number_unit, true);
+    let max = Value::Dimension(max, max_unit, true);
+
+    let visitor = ValueVisitor::new(parser, span);
+
+    if visitor
+        .greater_than_or_equal(min.clone(), number.clone())?
+        .is_true()
+    {
+        return Ok(min);
+    }
+
+    if visitor
+        .greater_than_or_equal(number.clone(), max.clone())?
+        .is_true()
+    {
+        return Ok(max);
+    }
+
+    Ok(number)
+}
+
+fn div(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let number1 = args.get_err(0, "number1")?;
+    let number2 = args.get_err(1, "number2")?;
+
+    ValueVisitor::new(parser, args.span()).div(number1, number2, true)
+}
+
+/// The variables exposed by `sass:math`
+pub(crate) fn module_variables() -> Vec<(&'static str, Value)> {
+    let number = |n: f64| Value::Dimension(Number(n), Unit::None, true);
+    vec![
+        ("e", number(std::f64::consts::E)),
+        ("epsilon", number(f64::EPSILON)),
+        ("max-number", number(f64::MAX)),
+        ("max-safe-integer", number(9_007_199_254_740_991.0)),
+        ("min-number", number(5e-324)),
+        ("min-safe-integer", number(-9_007_199_254_740_991.0)),
+        ("pi", number(std::f64::consts::PI)),
+    ]
+}
+
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("percentage", Builtin::new(percentage));
     f.insert("round", Builtin::new(round));
@@ -258,3 +598,20 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     #[cfg(feature = "random")]
     f.insert("random", Builtin::new(random));
 }
+
+/// Functions which are only available through `sass:math`
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    f.insert("sqrt", Builtin::new(sqrt));
+    f.insert("pow", Builtin::new(pow));
+    f.insert("log", Builtin::new(log));
+    f.insert("sin", Builtin::new(sin));
+    f.insert("cos", Builtin::new(cos));
+    f.insert("tan", Builtin::new(tan));
+    f.insert("asin", Builtin::new(asin));
+    f.insert("acos", Builtin::new(acos));
+    f.insert("atan", Builtin::new(atan));
+    f.insert("atan2", Builtin::new(atan2));
+    f.insert("hypot", Builtin::new(hypot));
+    f.insert("clamp", Builtin::new(clamp));
+    f.insert("div", Builtin::new(div));
+}
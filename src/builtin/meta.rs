@@ -1,16 +1,21 @@
-use super::{Builtin, GlobalFunctionMap, GLOBAL_FUNCTIONS};
+use std::rc::Rc;
+
+use super::{Builtin, GlobalFunctionMap, Module, GLOBAL_FUNCTIONS};
 
 use codemap::Spanned;
 
 use crate::{
     args::CallArgs,
-    common::{Identifier, QuoteKind},
+    common::{Brackets, Identifier, ListSeparator, QuoteKind},
     error::SassResult,
     parse::Parser,
     unit::Unit,
-    value::{SassFunction, Value},
+    utils::did_you_mean,
+    value::{SassCalculation, SassFunction, SassMixin, Value},
 };
 
+/// `if()` is usually evaluated lazily as a special form by the parser, so this
+/// is only reached through `call()` or when arguments are splatted
 fn if_(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(3)?;
     if args.get_err(0, "condition")?.is_true() {
@@ -24,7 +29,7 @@ fn feature_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Val
     args.max_args(1)?;
     match args.get_err(0, "feature")? {
         #[allow(clippy::match_same_arms)]
-        Value::String(s, _) => Ok(match s.as_str() {
+        Value::String(s, _) => Ok(match &*s {
             // A local variable will shadow a global variable unless
             // `!global` is used.
             "global-variable-shadowing" => Value::True,
@@ -62,13 +67,13 @@ fn unit(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
                 .into())
         }
     };
-    Ok(Value::String(unit, QuoteKind::Quoted))
+    Ok(Value::String(unit.into(), QuoteKind::Quoted))
 }
 
 fn type_of(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     let value = args.get_err(0, "value")?;
-    Ok(Value::String(value.kind().to_owned(), QuoteKind::None))
+    Ok(Value::String(value.kind().into(), QuoteKind::None))
 }
 
 fn unitless(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -84,7 +89,10 @@ fn unitless(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 fn inspect(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     Ok(Value::String(
-        args.get_err(0, "value")?.inspect(args.span())?.into_owned(),
+        args.get_err(0, "value")?
+            .inspect(args.span())?
+            .into_owned()
+            .into(),
         QuoteKind::None,
     ))
 }
@@ -104,14 +112,21 @@ fn variable_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Va
 }
 
 fn global_variable_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(1)?;
-    match args.get_err(0, "name")? {
-        Value::String(s, _) => Ok(Value::bool(parser.global_scope.var_exists(s.into()))),
-        v => Err((
-            format!("$name: {} is not a string.", v.inspect(args.span())?),
-            args.span(),
-        )
-            .into()),
+    args.max_args(2)?;
+    let name: Identifier = match args.get_err(0, "name")? {
+        Value::String(s, _) => s.into(),
+        v => {
+            return Err((
+                format!("$name: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    match get_module(&mut args, 1, parser)? {
+        Some(module) => Ok(Value::bool(module.get_var(name).is_some())),
+        None => Ok(Value::bool(parser.global_scope.var_exists(name))),
     }
 }
 
@@ -131,12 +146,39 @@ fn mixin_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
 
 fn function_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(2)?;
-    match args.get_err(0, "name")? {
-        Value::String(s, _) => Ok(Value::bool(
-            parser.scopes.fn_exists(s.into(), parser.global_scope),
+    let name: Identifier = match args.get_err(0, "name")? {
+        Value::String(s, _) => s.into(),
+        v => {
+            return Err((
+                format!("$name: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    match get_module(&mut args, 1, parser)? {
+        Some(module) => Ok(Value::bool(module.fn_exists(name))),
+        None => Ok(Value::bool(
+            parser.scopes.fn_exists(name, parser.global_scope),
         )),
+    }
+}
+
+/// Look up the module whose namespace is passed as `$module`, if any
+fn get_module(
+    args: &mut CallArgs,
+    position: usize,
+    parser: &mut Parser<'_>,
+) -> SassResult<Option<Module>> {
+    match args.default_arg(position, "module", Value::Null)? {
+        Value::String(s, ..) => Ok(Some(parser.global_scope.get_module(Spanned {
+            node: s.into(),
+            span: args.span(),
+        })?)),
+        Value::Null => Ok(None),
         v => Err((
-            format!("$name: {} is not a string.", v.inspect(args.span())?),
+            format!("$module: {} is not a string.", v.inspect(args.span())?),
             args.span(),
         )
             .into()),
@@ -156,17 +198,7 @@ fn get_function(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
         }
     };
     let css = args.default_arg(1, "css", Value::False)?.is_true();
-    let module = match args.default_arg(2, "module", Value::Null)? {
-        Value::String(s, ..) => Some(s),
-        Value::Null => None,
-        v => {
-            return Err((
-                format!("$module: {} is not a string.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let module = get_module(&mut args, 2, parser)?;
 
     if module.is_some() && css {
         return Err((
@@ -176,6 +208,22 @@ fn get_function(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
             .into());
     }
 
+    if let Some(module) = module {
+        return match module.get_fn(name) {
+            Some(f) => Ok(Value::FunctionRef(SassFunction::Builtin(f, name))),
+            None => Err((
+                did_you_mean(
+                    &format!("Function not found: {}", name),
+                    name.as_str(),
+                    module.fn_names(),
+                    "",
+                ),
+                args.span(),
+            )
+                .into()),
+        };
+    }
+
     let func = match parser.scopes.get_fn(
         Spanned {
             node: name,
@@ -186,7 +234,17 @@ fn get_function(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value
         Some(f) => SassFunction::UserDefined(Box::new(f), name),
         None => match GLOBAL_FUNCTIONS.get(name.as_str()) {
             Some(f) => SassFunction::Builtin(f.clone(), name),
-            None => return Err((format!("Function not found: {}", name), args.span()).into()),
+            None => {
+                return Err((
+                    parser.scopes.undefined_fn(
+                        &format!("Function not found: {}", name),
+                        name,
+                        parser.global_scope,
+                    ),
+                    args.span(),
+                )
+                    .into())
+            }
         },
     };
 
@@ -210,6 +268,62 @@ fn call(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     func.call(args.decrement(), parser)
 }
 
+fn get_mixin(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let name: Identifier = match args.get_err(0, "name")? {
+        Value::String(s, _) => s.into(),
+        v => {
+            return Err((
+                format!("$name: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    // no builtin module exposes its mixins as values
+    if get_module(&mut args, 1, parser)?.is_some() {
+        return Err((format!("Mixin not found: {}", name), args.span()).into());
+    }
+
+    if !parser.scopes.mixin_exists(name, parser.global_scope) {
+        return Err((
+            parser.scopes.undefined_mixin(
+                &format!("Mixin not found: {}", name),
+                name,
+                parser.global_scope,
+            ),
+            args.span(),
+        )
+            .into());
+    }
+
+    let mixin = parser.scopes.get_mixin(
+        Spanned {
+            node: name,
+            span: args.span(),
+        },
+        parser.global_scope,
+    )?;
+
+    Ok(Value::MixinRef(SassMixin::new(mixin, name)))
+}
+
+fn accepts_content(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "mixin")? {
+        Value::MixinRef(mixin) => Ok(Value::bool(mixin.accepts_content())),
+        v => Err((
+            format!(
+                "$mixin: {} is not a mixin reference.",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 fn content_exists(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(0)?;
@@ -225,6 +339,48 @@ fn content_exists(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
     ))
 }
 
+fn keywords(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "args")? {
+        Value::ArgList(list) => Ok(Value::Map(list.keywords())),
+        v => Err((
+            format!(
+                "$args: {} is not an argument list.",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn get_calculation(args: &mut CallArgs) -> SassResult<SassCalculation> {
+    match args.get_err(0, "calc")? {
+        Value::Calculation(calc) => Ok(calc),
+        v => Err((
+            format!("$calc: {} is not a calculation.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn calc_name(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let calc = get_calculation(&mut args)?;
+    Ok(Value::String(calc.name.as_str().into(), QuoteKind::Quoted))
+}
+
+fn calc_args(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let calc = get_calculation(&mut args)?;
+    Ok(Value::List(
+        Rc::new(calc.args.into_iter().map(|arg| arg.into_value()).collect()),
+        ListSeparator::Comma,
+        Brackets::None,
+    ))
+}
+
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("if", Builtin::new(if_));
     f.insert("feature-exists", Builtin::new(feature_exists));
@@ -242,4 +398,13 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("get-function", Builtin::new(get_function));
     f.insert("call", Builtin::new(call));
     f.insert("content-exists", Builtin::new(content_exists));
+    f.insert("keywords", Builtin::new(keywords));
+    f.insert("calc-name", Builtin::new(calc_name));
+    f.insert("calc-args", Builtin::new(calc_args));
+}
+
+/// Functions which are only available through `sass:meta`
+pub(crate) fn declare_module(f: &mut GlobalFunctionMap) {
+    f.insert("accepts-content", Builtin::new(accepts_content));
+    f.insert("get-mixin", Builtin::new(get_mixin));
 }
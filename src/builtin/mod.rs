@@ -10,6 +10,8 @@ use once_cell::sync::Lazy;
 
 use crate::{args::CallArgs, error::SassResult, parse::Parser, value::Value};
 
+pub(crate) use modules::Module;
+
 #[macro_use]
 mod macros;
 
@@ -18,6 +20,7 @@ mod list;
 mod map;
 mod math;
 mod meta;
+mod modules;
 mod selector;
 mod string;
 
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+
+use crate::{common::Identifier, value::Value};
+
+use super::{color, list, math, meta, Builtin, GlobalFunctionMap, GLOBAL_FUNCTIONS};
+
+/// A built-in module, loaded with `@use "sass:<name>"`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Module {
+    Color,
+    List,
+    Map,
+    Math,
+    Meta,
+    Selector,
+    String,
+}
+
+impl Module {
+    const ALL: [Self; 7] = [
+        Self::Color,
+        Self::List,
+        Self::Map,
+        Self::Math,
+        Self::Meta,
+        Self::Selector,
+        Self::String,
+    ];
+
+    /// The module member which the global function `name` is an alias of,
+    /// e.g. `(Module::Map, "get")` for `map-get`
+    pub fn member_for_global(name: &str) -> Option<(Self, &'static str)> {
+        let global = GLOBAL_FUNCTIONS.get(name)?;
+
+        Self::ALL.iter().find_map(|&module| {
+            module
+                .functions()
+                .iter()
+                .find(|(_, f)| *f == global)
+                .map(|(member, _)| (module, *member))
+        })
+    }
+
+    pub fn from_url(url: &str) -> Option<Self> {
+        Some(match url {
+            "sass:color" => Self::Color,
+            "sass:list" => Self::List,
+            "sass:map" => Self::Map,
+            "sass:math" => Self::Math,
+            "sass:meta" => Self::Meta,
+            "sass:selector" => Self::Selector,
+            "sass:string" => Self::String,
+            _ => return None,
+        })
+    }
+
+    /// The namespace used for this module when `@use` has no `as` clause
+    pub fn default_namespace(self) -> &'static str {
+        match self {
+            Self::Color => "color",
+            Self::List => "list",
+            Self::Map => "map",
+            Self::Math => "math",
+            Self::Meta => "meta",
+            Self::Selector => "selector",
+            Self::String => "string",
+        }
+    }
+
+    fn functions(self) -> &'static GlobalFunctionMap {
+        match self {
+            Self::Color => &COLOR_MODULE,
+            Self::List => &LIST_MODULE,
+            Self::Map => &MAP_MODULE,
+            Self::Math => &MATH_MODULE,
+            Self::Meta => &META_MODULE,
+            Self::Selector => &SELECTOR_MODULE,
+            Self::String => &STRING_MODULE,
+        }
+    }
+
+    pub fn get_fn(self, name: Identifier) -> Option<Builtin> {
+        self.functions().get(name.as_str()).cloned()
+    }
+
+    /// The names of the functions this module exposes
+    pub fn fn_names(self) -> impl Iterator<Item = &'static str> {
+        self.functions().keys().copied()
+    }
+
+    /// The names of the variables this module exposes, without the `$`
+    pub fn var_names(self) -> Vec<&'static str> {
+        self.variables().into_iter().map(|(name, _)| name).collect()
+    }
+
+    pub fn fn_exists(self, name: Identifier) -> bool {
+        self.functions().contains_key(name.as_str())
+    }
+
+    /// Whether this module exposes the builtin mixin `name`
+    ///
+    /// `meta.apply()` is the only builtin mixin, and is included by
+    /// `@include` directly
+    pub fn mixin_exists(self, name: Identifier) -> bool {
+        matches!((self, name.as_str()), (Self::Meta, "apply"))
+    }
+
+    /// The variables this module exposes, such as `math.$pi`
+    pub fn variables(self) -> Vec<(&'static str, Value)> {
+        match self {
+            Self::Math => math::module_variables(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn get_var(self, name: Identifier) -> Option<Value> {
+        self.variables()
+            .into_iter()
+            .find(|(var_name, _)| *var_name == name.as_str())
+            .map(|(_, value)| value)
+    }
+}
+
+/// Expose the global function `global` as `name` inside a module
+///
+/// Module members share their `Builtin` with the global function of the same
+/// behavior, so that e.g. `get-function(round)` and `math.round` are equal
+fn alias(f: &mut GlobalFunctionMap, names: &[(&'static str, &'static str)]) {
+    for &(name, global) in names {
+        f.insert(name, GLOBAL_FUNCTIONS[global].clone());
+    }
+}
+
+static COLOR_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("adjust", "adjust-color"),
+            ("alpha", "alpha"),
+            ("blue", "blue"),
+            ("change", "change-color"),
+            ("complement", "complement"),
+            ("grayscale", "grayscale"),
+            ("green", "green"),
+            ("hue", "hue"),
+            ("hwb", "hwb"),
+            ("ie-hex-str", "ie-hex-str"),
+            ("invert", "invert"),
+            ("lightness", "lightness"),
+            ("mix", "mix"),
+            ("opacity", "opacity"),
+            ("red", "red"),
+            ("saturation", "saturation"),
+            ("scale", "scale-color"),
+        ],
+    );
+    color::declare_module(&mut m);
+    m
+});
+
+static LIST_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("append", "append"),
+            ("index", "index"),
+            ("is-bracketed", "is-bracketed"),
+            ("join", "join"),
+            ("length", "length"),
+            ("nth", "nth"),
+            ("separator", "list-separator"),
+            ("set-nth", "set-nth"),
+            ("zip", "zip"),
+        ],
+    );
+    list::declare_module(&mut m);
+    m
+});
+
+static MAP_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("get", "map-get"),
+            ("has-key", "map-has-key"),
+            ("keys", "map-keys"),
+            ("merge", "map-merge"),
+            ("remove", "map-remove"),
+            ("values", "map-values"),
+        ],
+    );
+    m
+});
+
+static MATH_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("abs", "abs"),
+            ("ceil", "ceil"),
+            ("compatible", "comparable"),
+            ("floor", "floor"),
+            ("is-unitless", "unitless"),
+            ("max", "max"),
+            ("min", "min"),
+            ("percentage", "percentage"),
+            ("round", "round"),
+            ("unit", "unit"),
+        ],
+    );
+    #[cfg(feature = "random")]
+    alias(&mut m, &[("random", "random")]);
+    math::declare_module(&mut m);
+    m
+});
+
+static META_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("calc-args", "calc-args"),
+            ("calc-name", "calc-name"),
+            ("call", "call"),
+            ("content-exists", "content-exists"),
+            ("feature-exists", "feature-exists"),
+            ("function-exists", "function-exists"),
+            ("get-function", "get-function"),
+            ("global-variable-exists", "global-variable-exists"),
+            ("inspect", "inspect"),
+            ("keywords", "keywords"),
+            ("mixin-exists", "mixin-exists"),
+            ("type-of", "type-of"),
+            ("variable-exists", "variable-exists"),
+        ],
+    );
+    meta::declare_module(&mut m);
+    m
+});
+
+static SELECTOR_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("append", "selector-append"),
+            ("extend", "selector-extend"),
+            ("is-superselector", "is-superselector"),
+            ("nest", "selector-nest"),
+            ("parse", "selector-parse"),
+            ("replace", "selector-replace"),
+            ("simple-selectors", "simple-selectors"),
+            ("unify", "selector-unify"),
+        ],
+    );
+    m
+});
+
+static STRING_MODULE: Lazy<GlobalFunctionMap> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    alias(
+        &mut m,
+        &[
+            ("index", "str-index"),
+            ("insert", "str-insert"),
+            ("length", "str-length"),
+            ("quote", "quote"),
+            ("slice", "str-slice"),
+            ("to-lower-case", "to-lower-case"),
+            ("to-upper-case", "to-upper-case"),
+            ("unquote", "unquote"),
+        ],
+    );
+    #[cfg(feature = "random")]
+    alias(&mut m, &[("unique-id", "unique-id")]);
+    m
+});
@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use super::{Builtin, GlobalFunctionMap};
 
 use crate::{
@@ -37,15 +39,17 @@ fn simple_selectors(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
     {
         compound
     } else {
-        todo!()
+        return Err(("$selector: expected selector.", args.span()).into());
     };
 
     Ok(Value::List(
-        compound
-            .components
-            .into_iter()
-            .map(|simple| Value::String(simple.to_string(), QuoteKind::None))
-            .collect(),
+        Rc::new(
+            compound
+                .components
+                .into_iter()
+                .map(|simple| Value::String(simple.to_string().into(), QuoteKind::None))
+                .collect(),
+        ),
         ListSeparator::Comma,
         Brackets::None,
     ))
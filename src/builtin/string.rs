@@ -1,6 +1,5 @@
 use super::{Builtin, GlobalFunctionMap};
 
-use num_bigint::BigInt;
 use num_traits::{Signed, ToPrimitive, Zero};
 
 #[cfg(feature = "random")]
@@ -18,10 +17,7 @@ use crate::{
 fn to_upper_case(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "string")? {
-        Value::String(mut i, q) => {
-            i.make_ascii_uppercase();
-            Ok(Value::String(i, q))
-        }
+        Value::String(i, q) => Ok(Value::String(i.to_ascii_uppercase().into(), q)),
         v => Err((
             format!("$string: {} is not a string.", v.inspect(args.span())?),
             args.span(),
@@ -33,10 +29,7 @@ fn to_upper_case(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Valu
 fn to_lower_case(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "string")? {
-        Value::String(mut i, q) => {
-            i.make_ascii_lowercase();
-            Ok(Value::String(i, q))
-        }
+        Value::String(i, q) => Ok(Value::String(i.to_ascii_lowercase().into(), q)),
         v => Err((
             format!("$string: {} is not a string.", v.inspect(args.span())?),
             args.span(),
@@ -107,9 +100,9 @@ fn str_slice(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         }
         Value::Dimension(n, Unit::None, _) if n.is_zero() => 1_usize,
         Value::Dimension(n, Unit::None, _) if n < -Number::from(str_len) => 1_usize,
-        Value::Dimension(n, Unit::None, _) => (n.to_integer() + BigInt::from(str_len + 1))
-            .to_usize()
-            .unwrap(),
+        Value::Dimension(n, Unit::None, _) => {
+            (n.to_integer() + str_len as i64 + 1).to_usize().unwrap()
+        }
         v @ Value::Dimension(..) => {
             return Err((
                 format!(
@@ -137,7 +130,7 @@ fn str_slice(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         }
         Value::Dimension(n, Unit::None, _) if n.is_zero() => 0_usize,
         Value::Dimension(n, Unit::None, _) if n < -Number::from(str_len) => 0_usize,
-        Value::Dimension(n, Unit::None, _) => (n.to_integer() + BigInt::from(str_len + 1))
+        Value::Dimension(n, Unit::None, _) => (n.to_integer() + str_len as i64 + 1)
             .to_usize()
             .unwrap_or(str_len + 1),
         v @ Value::Dimension(..) => {
@@ -165,14 +158,15 @@ fn str_slice(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     }
 
     if start > end || start > str_len {
-        Ok(Value::String(String::new(), quotes))
+        Ok(Value::String("".into(), quotes))
     } else {
         Ok(Value::String(
             string
                 .chars()
                 .skip(start - 1)
                 .take(end - start + 1)
-                .collect(),
+                .collect::<String>()
+                .into(),
             quotes,
         ))
     }
@@ -202,7 +196,7 @@ fn str_index(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         }
     };
 
-    Ok(match s1.find(&substr) {
+    Ok(match s1.find(&*substr) {
         Some(v) => Value::Dimension(Number::from(v + 1), Unit::None, true),
         None => Value::Null,
     })
@@ -263,7 +257,7 @@ fn str_insert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
     let len = s1.chars().count();
 
     // Insert substring at char position, rather than byte position
-    let insert = |idx, s1: String, s2| {
+    let insert = |idx, s1: &str, s2| {
         s1.chars()
             .enumerate()
             .map(|(i, c)| {
@@ -286,33 +280,42 @@ fn str_insert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
                 .unwrap_or(len + 1)
                 .min(len + 1)
                 - 1,
-            s1,
+            &s1,
             &substr,
         )
     } else if index.is_zero() {
-        insert(0, s1, &substr)
+        insert(0, &s1, &substr)
     } else {
         let idx = index.abs().to_integer().to_usize().unwrap_or(len + 1);
         if idx > len {
-            insert(0, s1, &substr)
+            insert(0, &s1, &substr)
         } else {
-            insert(len - idx + 1, s1, &substr)
+            insert(len - idx + 1, &s1, &substr)
         }
     };
 
-    Ok(Value::String(string, quotes))
+    Ok(Value::String(string.into(), quotes))
 }
 
 #[cfg(feature = "random")]
 #[allow(clippy::needless_pass_by_value)]
-fn unique_id(args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+fn unique_id(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(0)?;
+    if parser.options.deterministic {
+        return Err((
+            "unique-id() is not available in deterministic mode.",
+            args.span(),
+        )
+            .into());
+    }
+    // the result of a function calling `unique-id()` must not be memoized
+    parser.global_scope.function_cache().taint();
     let mut rng = thread_rng();
     let string = std::iter::repeat(())
         .map(|()| rng.sample(Alphanumeric))
         .take(7)
-        .collect();
-    Ok(Value::String(string, QuoteKind::None))
+        .collect::<String>();
+    Ok(Value::String(string.into(), QuoteKind::None))
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
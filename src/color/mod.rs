@@ -1,7 +1,7 @@
 //! A color is internally represented as either RGBA or HSLA.
 //!
 //! Colors can be constructed in Sass through names (e.g. red, blue, aqua)
-//! or the builtin functions `rgb()`, `rgba()`, `hsl()`, and `hsla()`,
+//! or the builtin functions `rgb()`, `rgba()`, `hsl()`, `hsla()`, and `hwb()`,
 //! all of which can accept 1-4 arguments.
 //!
 //! It is necessary to retain the original values with which the
@@ -14,34 +14,65 @@
 //!
 //! Named colors retain their original casing,
 //! so `rEd` should be emitted as `rEd`.
+//!
+//! Colors may also be defined in the spaces introduced by CSS Color Level 4,
+//! e.g. `lab()`, `oklch()`, or `color(display-p3 ...)`, in which case they are
+//! emitted in their original space. See the `space` module.
 
 use std::{
     cmp::{max, min},
     fmt::{self, Display},
+    hash::{Hash, Hasher},
 };
 
 use crate::value::Number;
 pub(crate) use name::NAMED_COLORS;
+pub(crate) use space::{ColorSpace, GamutMapMethod, HueInterpolationMethod};
+
+use space::SpaceChannels;
 
 use num_traits::{One, Signed, ToPrimitive, Zero};
 
 mod name;
+mod space;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Color {
     rgba: Rgba,
     hsla: Option<Hsla>,
     repr: String,
+    /// The channels of colors that are not in a legacy color space
+    space: Option<SpaceChannels>,
 }
 
 impl PartialEq for Color {
     fn eq(&self, other: &Self) -> bool {
-        self.rgba == other.rgba
+        match (&self.space, &other.space) {
+            (None, None) => self.rgba == other.rgba,
+            (Some(space1), Some(space2)) => space1 == space2 && self.alpha() == other.alpha(),
+            _ => false,
+        }
     }
 }
 
 impl Eq for Color {}
 
+/// Consistent with `PartialEq`, which considers channels that are out of
+/// range in the same direction to be equal
+impl Hash for Color {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.space {
+            None => {
+                min(self.rgba.red, Number::from(255)).hash(state);
+                min(self.rgba.green, Number::from(255)).hash(state);
+                min(self.rgba.blue, Number::from(255)).hash(state);
+                min(self.rgba.alpha, Number::one()).hash(state);
+            }
+            Some(..) => self.alpha().hash(state),
+        }
+    }
+}
+
 impl Color {
     pub const fn new_rgba(
         red: Number,
@@ -54,6 +85,7 @@ impl Color {
             rgba: Rgba::new(red, green, blue, alpha),
             hsla: None,
             repr,
+            space: None,
         }
     }
 
@@ -69,8 +101,15 @@ impl Color {
             rgba: Rgba::new(red, green, blue, alpha),
             hsla: Some(hsla),
             repr,
+            space: None,
         }
     }
+
+    /// Whether the colors are equal and are also written the same way, e.g.
+    /// `red` and `#ff0000` are equal but not identical
+    pub fn is_identical(&self, other: &Color) -> bool {
+        self == other && self.repr == other.repr
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -120,7 +159,7 @@ impl Rgba {
     }
 
     pub fn alpha(&self) -> Number {
-        self.alpha.clone()
+        self.alpha
     }
 }
 
@@ -143,19 +182,19 @@ impl Hsla {
     }
 
     pub fn hue(&self) -> Number {
-        self.hue.clone()
+        self.hue
     }
 
     pub fn saturation(&self) -> Number {
-        self.saturation.clone()
+        self.saturation
     }
 
     pub fn luminance(&self) -> Number {
-        self.luminance.clone()
+        self.luminance
     }
 
     pub fn alpha(&self) -> Number {
-        self.alpha.clone()
+        self.alpha
     }
 }
 
@@ -166,6 +205,7 @@ impl Color {
             rgba: Rgba::new(red.into(), green.into(), blue.into(), alpha.into()),
             hsla: None,
             repr,
+            space: None,
         }
     }
 
@@ -187,15 +227,15 @@ impl Color {
     }
 
     pub fn red(&self) -> Number {
-        self.rgba.red.clone().round()
+        self.rgba.red.round()
     }
 
     pub fn blue(&self) -> Number {
-        self.rgba.blue.clone().round()
+        self.rgba.blue.round()
     }
 
     pub fn green(&self) -> Number {
-        self.rgba.green.clone().round()
+        self.rgba.green.round()
     }
 
     /// Mix two colors together with weight
@@ -203,24 +243,23 @@ impl Color {
     /// <https://github.com/sass/dart-sass/blob/0d0270cb12a9ac5cce73a4d0785fecb00735feee/lib/src/functions/color.dart#L718>
     pub fn mix(self, other: &Color, weight: Number) -> Self {
         let weight = weight.clamp(0, 100);
-        let normalized_weight = weight.clone() * Number::from(2) - Number::one();
+        let normalized_weight = weight * Number::from(2) - Number::one();
         let alpha_distance = self.alpha() - other.alpha();
 
-        let combined_weight1 =
-            if normalized_weight.clone() * alpha_distance.clone() == Number::from(-1) {
-                normalized_weight
-            } else {
-                (normalized_weight.clone() + alpha_distance.clone())
-                    / (Number::one() + normalized_weight * alpha_distance)
-            };
+        let combined_weight1 = if normalized_weight * alpha_distance == Number::from(-1) {
+            normalized_weight
+        } else {
+            (normalized_weight + alpha_distance)
+                / (Number::one() + normalized_weight * alpha_distance)
+        };
         let weight1 = (combined_weight1 + Number::one()) / Number::from(2);
-        let weight2 = Number::one() - weight1.clone();
+        let weight2 = Number::one() - weight1;
 
         Color::from_rgba(
-            self.red() * weight1.clone() + other.red() * weight2.clone(),
-            self.green() * weight1.clone() + other.green() * weight2.clone(),
+            self.red() * weight1 + other.red() * weight2,
+            self.green() * weight1 + other.green() * weight2,
             self.blue() * weight1 + other.blue() * weight2,
-            self.alpha() * weight.clone() + other.alpha() * (Number::one() - weight),
+            self.alpha() * weight + other.alpha() * (Number::one() - weight),
         )
     }
 }
@@ -237,8 +276,8 @@ impl Color {
         let red = self.red() / Number::from(255);
         let green = self.green() / Number::from(255);
         let blue = self.blue() / Number::from(255);
-        let min = min(&red, min(&green, &blue)).clone();
-        let max = max(&red, max(&green, &blue)).clone();
+        let min = *min(&red, min(&green, &blue));
+        let max = *max(&red, max(&green, &blue));
         if min == max {
             return Number::zero();
         }
@@ -268,14 +307,14 @@ impl Color {
         let green = self.green() / Number::from(255);
         let blue = self.blue() / Number::from(255);
 
-        let min = min(&red, min(&green, &blue)).clone();
+        let min = *min(&red, min(&green, &blue));
         let max = red.max(green.max(blue));
 
         if min == max {
             return Number::zero();
         }
 
-        let d = max.clone() - min.clone();
+        let d = max - min;
         let mm = max + min;
         let s = d / if mm > Number::one() {
             Number::from(2) - mm
@@ -294,7 +333,7 @@ impl Color {
         let red = self.red() / Number::from(255);
         let green = self.green() / Number::from(255);
         let blue = self.blue() / Number::from(255);
-        let min = min(&red, min(&green, &blue)).clone();
+        let min = *min(&red, min(&green, &blue));
         let max = red.max(green.max(blue));
         (((min + max) / Number::from(2)) * Number::from(100)).round()
     }
@@ -307,16 +346,16 @@ impl Color {
         let red = self.red() / Number::from(255);
         let green = self.green() / Number::from(255);
         let blue = self.blue() / Number::from(255);
-        let min = min(&red, min(&green, &blue)).clone();
-        let max = max(&red, max(&green, &blue)).clone();
+        let min = *min(&red, min(&green, &blue));
+        let max = *max(&red, max(&green, &blue));
 
-        let lightness = (min.clone() + max.clone()) / Number::from(2);
+        let lightness = (min + max) / Number::from(2);
 
         let saturation = if min == max {
             Number::zero()
         } else {
-            let d = max.clone() - min.clone();
-            let mm = max.clone() + min.clone();
+            let d = max - min;
+            let mm = max + min;
             d / if mm > Number::one() {
                 Number::from(2) - mm
             } else {
@@ -384,12 +423,7 @@ impl Color {
         let luminance = luminance.clamp(0, 1);
         let alpha = alpha.clamp(0, 1);
 
-        let hsla = Hsla::new(
-            hue.clone(),
-            saturation.clone(),
-            luminance.clone(),
-            alpha.clone(),
-        );
+        let hsla = Hsla::new(hue, saturation, luminance, alpha);
 
         if saturation.is_zero() {
             let luminance = if luminance > Number::from(100) {
@@ -399,18 +433,18 @@ impl Color {
             };
             let val = luminance * Number::from(255);
             let repr = repr(&val, &val, &val, &alpha);
-            return Color::new_hsla(val.clone(), val.clone(), val, alpha, hsla, repr);
+            return Color::new_hsla(val, val, val, alpha, hsla, repr);
         }
 
         let temporary_1 = if luminance < Number::small_ratio(1, 2) {
-            luminance.clone() * (Number::one() + saturation)
+            luminance * (Number::one() + saturation)
         } else {
-            luminance.clone() + saturation.clone() - luminance.clone() * saturation
+            luminance + saturation - luminance * saturation
         };
-        let temporary_2 = Number::from(2) * luminance - temporary_1.clone();
+        let temporary_2 = Number::from(2) * luminance - temporary_1;
         hue /= Number::from(360);
-        let mut temporary_r = hue.clone() + Number::small_ratio(1, 3);
-        let mut temporary_g = hue.clone();
+        let mut temporary_r = hue + Number::small_ratio(1, 3);
+        let mut temporary_g = hue;
         let mut temporary_b = hue - Number::small_ratio(1, 3);
 
         macro_rules! clamp_temp {
@@ -429,17 +463,15 @@ impl Color {
 
         fn channel(temp: Number, temp1: &Number, temp2: &Number) -> Number {
             Number::from(255)
-                * if Number::from(6) * temp.clone() < Number::one() {
-                    temp2.clone() + (temp1.clone() - temp2.clone()) * Number::from(6) * temp
-                } else if Number::from(2) * temp.clone() < Number::one() {
-                    temp1.clone()
-                } else if Number::from(3) * temp.clone() < Number::from(2) {
-                    temp2.clone()
-                        + (temp1.clone() - temp2.clone())
-                            * (Number::small_ratio(2, 3) - temp)
-                            * Number::from(6)
+                * if Number::from(6) * temp < Number::one() {
+                    *temp2 + (*temp1 - *temp2) * Number::from(6) * temp
+                } else if Number::from(2) * temp < Number::one() {
+                    *temp1
+                } else if Number::from(3) * temp < Number::from(2) {
+                    *temp2
+                        + (*temp1 - *temp2) * (Number::small_ratio(2, 3) - temp) * Number::from(6)
                 } else {
-                    temp2.clone()
+                    *temp2
                 }
         }
 
@@ -474,6 +506,80 @@ impl Color {
     }
 }
 
+/// HWB color functions
+/// Algorithms adapted from <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+impl Color {
+    /// Calculate whiteness from RGBA values
+    pub fn whiteness(&self) -> Number {
+        let min = min(self.red(), min(self.green(), self.blue()));
+        min / Number::from(255) * Number::from(100)
+    }
+
+    /// Calculate blackness from RGBA values
+    pub fn blackness(&self) -> Number {
+        let max = max(self.red(), max(self.green(), self.blue()));
+        Number::from(100) - max / Number::from(255) * Number::from(100)
+    }
+
+    pub fn as_hwba(&self) -> (Number, Number, Number, Number) {
+        let (hue, ..) = self.as_hsla();
+        (
+            hue,
+            self.whiteness() / Number::from(100),
+            self.blackness() / Number::from(100),
+            self.alpha(),
+        )
+    }
+
+    /// Create RGBA representation from HWB values
+    pub fn from_hwb(hue: Number, whiteness: Number, blackness: Number, alpha: Number) -> Self {
+        let hue = Number(hue.0.rem_euclid(360.0)) / Number::from(360);
+        let mut whiteness = whiteness.clamp(0, 1);
+        let mut blackness = blackness.clamp(0, 1);
+
+        // if whiteness and blackness add up to more than 100%, they are
+        // normalized so that their sum is exactly 100%, which is a shade of gray
+        let sum = whiteness + blackness;
+        if sum > Number::one() {
+            whiteness /= sum;
+            blackness /= sum;
+        }
+
+        let factor = Number::one() - whiteness - blackness;
+
+        let channel = |hue: Number| {
+            (hue_to_rgb(Number::zero(), Number::one(), hue) * factor + whiteness)
+                * Number::from(255)
+        };
+
+        Color::from_rgba(
+            channel(hue + Number::small_ratio(1, 3)),
+            channel(hue),
+            channel(hue - Number::small_ratio(1, 3)),
+            alpha,
+        )
+    }
+}
+
+fn hue_to_rgb(m1: Number, m2: Number, mut hue: Number) -> Number {
+    if hue.is_negative() {
+        hue += Number::one();
+    }
+    if hue > Number::one() {
+        hue -= Number::one();
+    }
+
+    if hue < Number::small_ratio(1, 6) {
+        m1 + (m2 - m1) * hue * Number::from(6)
+    } else if hue < Number::small_ratio(1, 2) {
+        m2
+    } else if hue < Number::small_ratio(2, 3) {
+        m1 + (m2 - m1) * (Number::small_ratio(2, 3) - hue) * Number::from(6)
+    } else {
+        m1
+    }
+}
+
 /// Opacity color functions
 impl Color {
     pub fn alpha(&self) -> Number {
@@ -507,9 +613,46 @@ impl Color {
 
 /// Other color functions
 impl Color {
+    /// The relative luminance of this color, as defined by WCAG 2, from 0 for
+    /// black to 1 for white
+    ///
+    /// The alpha channel is ignored
+    ///
+    /// Algorithm adapted from <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+    pub fn relative_luminance(&self) -> Number {
+        fn channel(n: Number) -> f64 {
+            let n = n.0 / 255.0;
+            if n <= 0.039_28 {
+                n / 12.92
+            } else {
+                ((n + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        Number(
+            0.2126 * channel(self.red())
+                + 0.7152 * channel(self.green())
+                + 0.0722 * channel(self.blue()),
+        )
+    }
+
+    /// The WCAG 2 contrast ratio between this color and `other`, from 1 to 21
+    ///
+    /// Algorithm adapted from <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+    pub fn contrast_ratio(&self, other: &Color) -> Number {
+        let luminance1 = self.relative_luminance();
+        let luminance2 = other.relative_luminance();
+        let (lighter, darker) = if luminance1 > luminance2 {
+            (luminance1, luminance2)
+        } else {
+            (luminance2, luminance1)
+        };
+        (lighter + Number(0.05)) / (darker + Number(0.05))
+    }
+
     pub fn to_ie_hex_str(&self) -> String {
         format!(
-            "#{:X}{:X}{:X}{:X}",
+            "#{:02X}{:02X}{:02X}{:02X}",
             (self.alpha() * Number::from(255)).round().to_integer(),
             self.red().to_integer(),
             self.green().to_integer(),
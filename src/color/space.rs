@@ -0,0 +1,898 @@
+//! Colors in the color spaces introduced by CSS Color Level 4, such as
+//! `lab()`, `oklch()`, and `color(display-p3 ...)`.
+//!
+//! Colors in these spaces retain their channels in their original space, and
+//! are emitted in that space. They also carry an approximation of themselves
+//! in sRGB, so that the legacy color functions continue to work with them.
+//!
+//! Conversions between spaces go through the XYZ space with a D65 white point.
+//! Algorithms and matrices adapted from <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+
+use std::fmt::{self, Write};
+
+use num_traits::{One, Zero};
+
+use crate::value::Number;
+
+use super::{Color, Rgba};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ColorSpace {
+    Rgb,
+    Hsl,
+    Hwb,
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    XyzD50,
+    XyzD65,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+impl ColorSpace {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "rgb" => Self::Rgb,
+            "hsl" => Self::Hsl,
+            "hwb" => Self::Hwb,
+            "srgb" => Self::Srgb,
+            "srgb-linear" => Self::SrgbLinear,
+            "display-p3" => Self::DisplayP3,
+            "xyz-d50" => Self::XyzD50,
+            "xyz" | "xyz-d65" => Self::XyzD65,
+            "lab" => Self::Lab,
+            "lch" => Self::Lch,
+            "oklab" => Self::Oklab,
+            "oklch" => Self::Oklch,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rgb => "rgb",
+            Self::Hsl => "hsl",
+            Self::Hwb => "hwb",
+            Self::Srgb => "srgb",
+            Self::SrgbLinear => "srgb-linear",
+            Self::DisplayP3 => "display-p3",
+            Self::XyzD50 => "xyz-d50",
+            Self::XyzD65 => "xyz",
+            Self::Lab => "lab",
+            Self::Lch => "lch",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+        }
+    }
+
+    /// Whether colors in this space are represented with the legacy
+    /// `rgb()`, `hsl()`, and `hwb()` syntax
+    pub fn is_legacy(self) -> bool {
+        matches!(self, Self::Rgb | Self::Hsl | Self::Hwb)
+    }
+
+    /// Whether colors in this space are written with the `color()` function
+    pub fn is_predefined(self) -> bool {
+        matches!(
+            self,
+            Self::Srgb | Self::SrgbLinear | Self::DisplayP3 | Self::XyzD50 | Self::XyzD65
+        )
+    }
+
+    /// Whether this space has a gamut, outside of which colors can't be
+    /// displayed
+    pub fn is_bounded(self) -> bool {
+        matches!(
+            self,
+            Self::Rgb | Self::Hsl | Self::Hwb | Self::Srgb | Self::SrgbLinear | Self::DisplayP3
+        )
+    }
+
+    /// The index of the hue channel, if this is a polar space
+    pub fn hue_index(self) -> Option<usize> {
+        match self {
+            Self::Hsl | Self::Hwb => Some(0),
+            Self::Lch | Self::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    pub fn channel_names(self) -> [&'static str; 3] {
+        match self {
+            Self::Rgb | Self::Srgb | Self::SrgbLinear | Self::DisplayP3 => ["red", "green", "blue"],
+            Self::Hsl => ["hue", "saturation", "lightness"],
+            Self::Hwb => ["hue", "whiteness", "blackness"],
+            Self::XyzD50 | Self::XyzD65 => ["x", "y", "z"],
+            Self::Lab | Self::Oklab => ["lightness", "a", "b"],
+            Self::Lch | Self::Oklch => ["lightness", "chroma", "hue"],
+        }
+    }
+
+    /// The space whose gamut bounds colors in this space
+    fn gamut_space(self) -> Self {
+        if self.is_legacy() {
+            Self::Srgb
+        } else {
+            self
+        }
+    }
+}
+
+impl fmt::Display for ColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The channels of a color in a non-legacy color space
+///
+/// A channel of `None` is missing, and was written as `none`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct SpaceChannels {
+    space: ColorSpace,
+    channels: [Option<Number>; 3],
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum GamutMapMethod {
+    Clip,
+    LocalMinde,
+}
+
+impl GamutMapMethod {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clip" => Some(Self::Clip),
+            "local-minde" => Some(Self::LocalMinde),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum HueInterpolationMethod {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+impl HueInterpolationMethod {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "shorter" => Self::Shorter,
+            "longer" => Self::Longer,
+            "increasing" => Self::Increasing,
+            "decreasing" => Self::Decreasing,
+            _ => return None,
+        })
+    }
+}
+
+impl Color {
+    /// Create a color from its channels in `space`
+    ///
+    /// Colors in legacy spaces are converted to RGBA
+    pub fn from_space(space: ColorSpace, channels: [Option<Number>; 3], alpha: Number) -> Self {
+        let alpha = alpha.clamp(0, 1);
+
+        let [red, green, blue] = convert(space, unwrap_channels(channels), ColorSpace::Rgb);
+
+        if space.is_legacy() {
+            return Color::from_rgba(Number(red), Number(green), Number(blue), alpha);
+        }
+
+        let (red, green, blue) = (
+            Number(red).clamp(0, 255),
+            Number(green).clamp(0, 255),
+            Number(blue).clamp(0, 255),
+        );
+
+        let space = SpaceChannels { space, channels };
+
+        Color {
+            rgba: Rgba::new(red, green, blue, alpha),
+            hsla: None,
+            repr: space.repr(alpha),
+            space: Some(space),
+        }
+    }
+
+    /// The color space this color was defined in
+    pub fn space(&self) -> ColorSpace {
+        match &self.space {
+            Some(space) => space.space,
+            None if self.hsla.is_some() => ColorSpace::Hsl,
+            None => ColorSpace::Rgb,
+        }
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.space.is_none()
+    }
+
+    /// The channels of this color in `space`, with missing channels as zero
+    pub fn channels_in(&self, space: ColorSpace) -> [Number; 3] {
+        let [a, b, c] = self.raw_channels_in(space);
+        [Number(a), Number(b), Number(c)]
+    }
+
+    fn raw_channels_in(&self, space: ColorSpace) -> [f64; 3] {
+        match &self.space {
+            Some(own) => convert(own.space, unwrap_channels(own.channels), space),
+            // these are more exact than converting from RGB
+            None if space == ColorSpace::Hsl => {
+                let (hue, saturation, lightness, _) = self.as_hsla();
+                [hue.0, saturation.0 * 100.0, lightness.0 * 100.0]
+            }
+            None if space == ColorSpace::Hwb => {
+                let (hue, whiteness, blackness, _) = self.as_hwba();
+                [hue.0, whiteness.0 * 100.0, blackness.0 * 100.0]
+            }
+            None => convert(
+                ColorSpace::Rgb,
+                [self.rgba.red.0, self.rgba.green.0, self.rgba.blue.0],
+                space,
+            ),
+        }
+    }
+
+    /// The channels of this color in `space`, where channels that are
+    /// missing or powerless are `None`
+    fn channels_with_missing(&self, space: ColorSpace) -> [Option<Number>; 3] {
+        if let Some(own) = &self.space {
+            if own.space == space {
+                return own.channels;
+            }
+        }
+
+        let [a, b, c] = self.channels_in(space);
+        let mut channels = [Some(a), Some(b), Some(c)];
+
+        // the hue of a color without chroma is powerless, and so is missing
+        if let Some(hue) = space.hue_index() {
+            if !space.is_legacy() && b.is_zero() {
+                channels[hue] = None;
+            }
+        }
+
+        channels
+    }
+
+    /// Convert this color to `space`
+    pub fn to_space(&self, space: ColorSpace) -> Self {
+        if space == self.space() || (space.is_legacy() && self.is_legacy()) {
+            return self.clone();
+        }
+
+        Color::from_space(space, self.channels_with_missing(space), self.alpha())
+    }
+
+    /// Whether this color can be displayed in `space`
+    pub fn is_in_gamut(&self, space: ColorSpace) -> bool {
+        if !space.is_bounded() {
+            return true;
+        }
+
+        self.channels_in(space.gamut_space())
+            .iter()
+            .all(|&channel| channel >= Number::zero() && channel <= Number::one())
+    }
+
+    /// Map this color into the gamut of `space`, returning a color in this
+    /// color's space
+    pub fn to_gamut(&self, space: ColorSpace, method: GamutMapMethod) -> Self {
+        if self.is_in_gamut(space) {
+            return self.clone();
+        }
+
+        let gamut_space = space.gamut_space();
+
+        let mapped = match method {
+            GamutMapMethod::Clip => clip(self.raw_channels_in(gamut_space)),
+            GamutMapMethod::LocalMinde => {
+                local_minde(self.raw_channels_in(ColorSpace::Oklch), gamut_space)
+            }
+        };
+
+        let [a, b, c] = mapped;
+        Color::from_space(
+            gamut_space,
+            [Some(Number(a)), Some(Number(b)), Some(Number(c))],
+            self.alpha(),
+        )
+        .to_space(self.space())
+    }
+
+    /// Mix this color with `other` in `space`, where `weight` is the
+    /// proportion of this color in the result
+    ///
+    /// Algorithm adapted from <https://www.w3.org/TR/css-color-4/#interpolation>
+    pub fn interpolate(
+        &self,
+        other: &Color,
+        weight: Number,
+        space: ColorSpace,
+        hue_method: HueInterpolationMethod,
+    ) -> Self {
+        let weight = weight.0;
+
+        let alpha1 = self.alpha().0;
+        let alpha2 = other.alpha().0;
+        let alpha = alpha1 * weight + alpha2 * (1.0 - weight);
+
+        let channels1 = self.channels_with_missing(space);
+        let channels2 = other.channels_with_missing(space);
+
+        let mut channels = [None; 3];
+        for idx in 0..3 {
+            let (mut channel1, mut channel2) = match (channels1[idx], channels2[idx]) {
+                (None, None) => continue,
+                (Some(c), None) | (None, Some(c)) => (c.0, c.0),
+                (Some(c1), Some(c2)) => (c1.0, c2.0),
+            };
+
+            if space.hue_index() == Some(idx) {
+                fix_hues(&mut channel1, &mut channel2, hue_method);
+                let hue = channel1 * weight + channel2 * (1.0 - weight);
+                channels[idx] = Some(Number(hue.rem_euclid(360.0)));
+                continue;
+            }
+
+            // channels are interpolated in their premultiplied form
+            let premultiplied = channel1 * alpha1 * weight + channel2 * alpha2 * (1.0 - weight);
+            channels[idx] = Some(Number(if alpha == 0.0 {
+                premultiplied
+            } else {
+                premultiplied / alpha
+            }));
+        }
+
+        Color::from_space(space, channels, Number(alpha)).to_space(self.space())
+    }
+}
+
+impl SpaceChannels {
+    /// The CSS representation of a color in a non-legacy space
+    fn repr(&self, alpha: Number) -> String {
+        fn write_channel(buf: &mut String, channel: Option<Number>, unit: &str) {
+            match channel {
+                Some(n) => write!(buf, "{}{}", n, unit).unwrap(),
+                None => buf.push_str("none"),
+            }
+        }
+
+        let [c1, c2, c3] = self.channels;
+
+        let mut buf = if self.space.is_predefined() {
+            format!("color({} ", self.space)
+        } else {
+            format!("{}(", self.space)
+        };
+
+        match self.space {
+            ColorSpace::Lab | ColorSpace::Lch => write_channel(&mut buf, c1, "%"),
+            ColorSpace::Oklab | ColorSpace::Oklch => {
+                write_channel(&mut buf, c1.map(|n| n * Number::from(100)), "%")
+            }
+            _ => write_channel(&mut buf, c1, ""),
+        }
+        buf.push(' ');
+        write_channel(&mut buf, c2, "");
+        buf.push(' ');
+        match self.space {
+            ColorSpace::Lch | ColorSpace::Oklch => write_channel(&mut buf, c3, "deg"),
+            _ => write_channel(&mut buf, c3, ""),
+        }
+
+        if alpha < Number::one() {
+            write!(buf, " / {}", alpha).unwrap();
+        }
+
+        buf.push(')');
+        buf
+    }
+}
+
+fn unwrap_channels(channels: [Option<Number>; 3]) -> [f64; 3] {
+    let [a, b, c] = channels;
+    [
+        a.unwrap_or_else(Number::zero).0,
+        b.unwrap_or_else(Number::zero).0,
+        c.unwrap_or_else(Number::zero).0,
+    ]
+}
+
+/// Adjust two hues so that interpolating between them takes the path
+/// described by `method`
+fn fix_hues(hue1: &mut f64, hue2: &mut f64, method: HueInterpolationMethod) {
+    *hue1 = hue1.rem_euclid(360.0);
+    *hue2 = hue2.rem_euclid(360.0);
+    let difference = *hue2 - *hue1;
+
+    match method {
+        HueInterpolationMethod::Shorter => {
+            if difference > 180.0 {
+                *hue1 += 360.0;
+            } else if difference < -180.0 {
+                *hue2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Longer => {
+            if 0.0 < difference && difference < 180.0 {
+                *hue1 += 360.0;
+            } else if -180.0 < difference && difference <= 0.0 {
+                *hue2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Increasing => {
+            if difference < 0.0 {
+                *hue2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Decreasing => {
+            if difference > 0.0 {
+                *hue1 += 360.0;
+            }
+        }
+    }
+}
+
+/// Clamp the channels of a color in an RGB-like space into its gamut
+fn clip(channels: [f64; 3]) -> [f64; 3] {
+    let [a, b, c] = channels;
+    [
+        a.max(0.0).min(1.0),
+        b.max(0.0).min(1.0),
+        c.max(0.0).min(1.0),
+    ]
+}
+
+fn in_gamut(channels: [f64; 3]) -> bool {
+    channels
+        .iter()
+        .all(|&channel| Number(channel) >= Number::zero() && Number(channel) <= Number::one())
+}
+
+/// The distance between two colors given in OKLCH, in OKLab
+fn delta_eok(oklch1: [f64; 3], oklch2: [f64; 3]) -> f64 {
+    let [l1, a1, b1] = convert(ColorSpace::Oklch, oklch1, ColorSpace::Oklab);
+    let [l2, a2, b2] = convert(ColorSpace::Oklch, oklch2, ColorSpace::Oklab);
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+/// Map a color given in OKLCH into the gamut of `space` by reducing its
+/// chroma until clipping it produces a color that is visually close to it
+///
+/// Algorithm adapted from <https://www.w3.org/TR/css-color-4/#css-gamut-mapping>
+fn local_minde(origin: [f64; 3], space: ColorSpace) -> [f64; 3] {
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+
+    let [lightness, chroma, hue] = origin;
+
+    if lightness >= 1.0 {
+        return convert(ColorSpace::Oklab, [1.0, 0.0, 0.0], space);
+    }
+
+    if lightness <= 0.0 {
+        return convert(ColorSpace::Oklab, [0.0, 0.0, 0.0], space);
+    }
+
+    let mut current = origin;
+    let mut clipped = clip(convert(ColorSpace::Oklch, current, space));
+
+    if delta_eok(convert(space, clipped, ColorSpace::Oklch), current) < JND {
+        return clipped;
+    }
+
+    let mut min = 0.0;
+    let mut max = chroma;
+    let mut min_in_gamut = true;
+
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        current = [lightness, chroma, hue];
+
+        let converted = convert(ColorSpace::Oklch, current, space);
+
+        if min_in_gamut && in_gamut(converted) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(converted);
+        let delta = delta_eok(convert(space, clipped, ColorSpace::Oklch), current);
+
+        if delta < JND {
+            if JND - delta < EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+type Matrix = [[f64; 3]; 3];
+
+fn multiply(matrix: &Matrix, [a, b, c]: [f64; 3]) -> [f64; 3] {
+    [
+        matrix[0][0] * a + matrix[0][1] * b + matrix[0][2] * c,
+        matrix[1][0] * a + matrix[1][1] * b + matrix[1][2] * c,
+        matrix[2][0] * a + matrix[2][1] * b + matrix[2][2] * c,
+    ]
+}
+
+const LINEAR_SRGB_TO_XYZ_D65: Matrix = [
+    [
+        0.412_390_799_265_959_34,
+        0.357_584_339_383_878,
+        0.180_480_788_401_834_3,
+    ],
+    [
+        0.212_639_005_871_510_27,
+        0.715_168_678_767_756,
+        0.072_192_315_360_733_71,
+    ],
+    [
+        0.019_330_818_715_591_82,
+        0.119_194_779_794_625_98,
+        0.950_532_152_249_660_7,
+    ],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [
+        3.240_969_941_904_522_6,
+        -1.537_383_177_570_094,
+        -0.498_610_760_293_003_4,
+    ],
+    [
+        -0.969_243_636_280_879_6,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_59,
+    ],
+    [
+        0.055_630_079_696_993_66,
+        -0.203_976_958_888_976_52,
+        1.056_971_514_242_878_6,
+    ],
+];
+
+const LINEAR_DISPLAY_P3_TO_XYZ_D65: Matrix = [
+    [
+        0.486_570_948_648_216_2,
+        0.265_667_693_169_093_06,
+        0.198_217_285_234_362_5,
+    ],
+    [
+        0.228_974_564_069_748_8,
+        0.691_738_521_836_506_4,
+        0.079_286_914_093_745,
+    ],
+    [0.0, 0.045_113_381_858_902_64, 1.043_944_368_900_976],
+];
+
+const XYZ_D65_TO_LINEAR_DISPLAY_P3: Matrix = [
+    [
+        2.493_496_911_941_425,
+        -0.931_383_617_919_123_9,
+        -0.402_710_784_450_716_84,
+    ],
+    [
+        -0.829_488_969_561_574_7,
+        1.762_664_060_318_346_3,
+        0.023_624_685_841_943_577,
+    ],
+    [
+        0.035_845_830_243_784_47,
+        -0.076_172_389_268_041_82,
+        0.956_884_524_007_687_2,
+    ],
+];
+
+/// Bradford chromatic adaptation from D65 to D50
+const XYZ_D65_TO_XYZ_D50: Matrix = [
+    [
+        1.047_929_820_840_548_8,
+        0.022_946_793_341_019_088,
+        -0.050_192_229_543_135_57,
+    ],
+    [
+        0.029_627_815_688_159_344,
+        0.990_434_484_573_249,
+        -0.017_073_825_029_385_14,
+    ],
+    [
+        -0.009_243_058_152_591_178,
+        0.015_055_144_896_577_895,
+        0.751_874_289_958_000_8,
+    ],
+];
+
+const XYZ_D50_TO_XYZ_D65: Matrix = [
+    [
+        0.955_473_452_704_218_2,
+        -0.023_098_536_874_261_423,
+        0.063_259_308_661_021_7,
+    ],
+    [
+        -0.028_369_706_963_208_136,
+        1.009_995_458_010_662_9,
+        0.021_041_398_966_943_008,
+    ],
+    [
+        0.012_314_001_688_319_899,
+        -0.020_507_696_433_477_912,
+        1.330_365_936_608_075_3,
+    ],
+];
+
+const XYZ_D65_TO_LMS: Matrix = [
+    [
+        0.819_022_437_996_703,
+        0.361_906_260_052_890_4,
+        -0.128_873_781_520_987_9,
+    ],
+    [
+        0.032_983_653_932_388_5,
+        0.929_286_861_586_343_4,
+        0.036_144_666_350_642_4,
+    ],
+    [
+        0.048_177_189_359_624_2,
+        0.264_239_531_752_730_8,
+        0.633_547_828_469_430_9,
+    ],
+];
+
+const LMS_TO_XYZ_D65: Matrix = [
+    [
+        1.226_879_875_845_924_3,
+        -0.557_814_994_460_217_1,
+        0.281_391_045_665_964_7,
+    ],
+    [
+        -0.040_575_745_214_800_8,
+        1.112_286_803_280_317,
+        -0.071_711_058_065_516_4,
+    ],
+    [
+        -0.076_372_936_674_660_1,
+        -0.421_493_332_402_243_2,
+        1.586_924_019_836_781_6,
+    ],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+    [
+        0.210_454_268_309_314,
+        0.793_617_774_702_305_4,
+        -0.004_072_043_011_619_3,
+    ],
+    [
+        1.977_998_532_431_168_4,
+        -2.428_592_242_048_58,
+        0.450_593_709_617_411,
+    ],
+    [
+        0.025_904_042_465_547_8,
+        0.782_771_712_457_529_6,
+        -0.808_675_754_923_077_4,
+    ],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+    [1.0, 0.396_337_777_376_174_9, 0.215_803_757_309_913_6],
+    [1.0, -0.105_561_345_815_658_6, -0.063_854_172_825_813_3],
+    [1.0, -0.089_484_177_529_811_9, -1.291_485_548_019_409_2],
+];
+
+/// The D50 white point, used by Lab
+const D50: [f64; 3] = [0.345_7 / 0.358_5, 1.0, (1.0 - 0.345_7 - 0.358_5) / 0.358_5];
+
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+/// Convert the channels of a color from `from` to `to`
+///
+/// Channels are in the ranges used by CSS: RGB channels are 0-255,
+/// percentages in HSL and HWB are 0-100, and hues are in degrees
+pub(crate) fn convert(from: ColorSpace, channels: [f64; 3], to: ColorSpace) -> [f64; 3] {
+    if from == to {
+        return channels;
+    }
+
+    from_xyz_d65(to, to_xyz_d65(from, channels))
+}
+
+fn to_xyz_d65(space: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb => {
+            let [red, green, blue] = channels;
+            to_xyz_d65(ColorSpace::Srgb, [red / 255.0, green / 255.0, blue / 255.0])
+        }
+        ColorSpace::Hsl => to_xyz_d65(ColorSpace::Srgb, hsl_to_srgb(channels)),
+        ColorSpace::Hwb => to_xyz_d65(ColorSpace::Srgb, hwb_to_srgb(channels)),
+        ColorSpace::Srgb => multiply(&LINEAR_SRGB_TO_XYZ_D65, map(channels, srgb_to_linear)),
+        ColorSpace::SrgbLinear => multiply(&LINEAR_SRGB_TO_XYZ_D65, channels),
+        ColorSpace::DisplayP3 => {
+            multiply(&LINEAR_DISPLAY_P3_TO_XYZ_D65, map(channels, srgb_to_linear))
+        }
+        ColorSpace::XyzD50 => multiply(&XYZ_D50_TO_XYZ_D65, channels),
+        ColorSpace::XyzD65 => channels,
+        ColorSpace::Lab => multiply(&XYZ_D50_TO_XYZ_D65, lab_to_xyz_d50(channels)),
+        ColorSpace::Lch => to_xyz_d65(ColorSpace::Lab, polar_to_rectangular(channels)),
+        ColorSpace::Oklab => multiply(
+            &LMS_TO_XYZ_D65,
+            map(multiply(&OKLAB_TO_LMS, channels), |n| n.powi(3)),
+        ),
+        ColorSpace::Oklch => to_xyz_d65(ColorSpace::Oklab, polar_to_rectangular(channels)),
+    }
+}
+
+fn from_xyz_d65(space: ColorSpace, xyz: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb => map(from_xyz_d65(ColorSpace::Srgb, xyz), |n| n * 255.0),
+        ColorSpace::Hsl => srgb_to_hsl(from_xyz_d65(ColorSpace::Srgb, xyz)),
+        ColorSpace::Hwb => srgb_to_hwb(from_xyz_d65(ColorSpace::Srgb, xyz)),
+        ColorSpace::Srgb => map(multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz), linear_to_srgb),
+        ColorSpace::SrgbLinear => multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz),
+        ColorSpace::DisplayP3 => map(multiply(&XYZ_D65_TO_LINEAR_DISPLAY_P3, xyz), linear_to_srgb),
+        ColorSpace::XyzD50 => multiply(&XYZ_D65_TO_XYZ_D50, xyz),
+        ColorSpace::XyzD65 => xyz,
+        ColorSpace::Lab => xyz_d50_to_lab(multiply(&XYZ_D65_TO_XYZ_D50, xyz)),
+        ColorSpace::Lch => rectangular_to_polar(from_xyz_d65(ColorSpace::Lab, xyz)),
+        ColorSpace::Oklab => multiply(
+            &LMS_TO_OKLAB,
+            map(multiply(&XYZ_D65_TO_LMS, xyz), f64::cbrt),
+        ),
+        ColorSpace::Oklch => rectangular_to_polar(from_xyz_d65(ColorSpace::Oklab, xyz)),
+    }
+}
+
+fn map(channels: [f64; 3], f: impl Fn(f64) -> f64) -> [f64; 3] {
+    let [a, b, c] = channels;
+    [f(a), f(b), f(c)]
+}
+
+/// The sRGB transfer function, which is shared by display-p3
+fn srgb_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+    if abs <= 0.040_45 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f64) -> f64 {
+    let abs = channel.abs();
+    if abs > 0.003_130_8 {
+        channel.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        channel * 12.92
+    }
+}
+
+fn lab_to_xyz_d50([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let f1 = (lightness + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = if f0.powi(3) > LAB_EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / LAB_KAPPA
+    };
+    let y = if lightness > LAB_KAPPA * LAB_EPSILON {
+        f1.powi(3)
+    } else {
+        lightness / LAB_KAPPA
+    };
+    let z = if f2.powi(3) > LAB_EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / LAB_KAPPA
+    };
+
+    [x * D50[0], y * D50[1], z * D50[2]]
+}
+
+fn xyz_d50_to_lab([x, y, z]: [f64; 3]) -> [f64; 3] {
+    let f = |n: f64| {
+        if n > LAB_EPSILON {
+            n.cbrt()
+        } else {
+            (LAB_KAPPA * n + 16.0) / 116.0
+        }
+    };
+
+    let f0 = f(x / D50[0]);
+    let f1 = f(y / D50[1]);
+    let f2 = f(z / D50[2]);
+
+    [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+fn polar_to_rectangular([lightness, chroma, hue]: [f64; 3]) -> [f64; 3] {
+    let hue = hue.to_radians();
+    [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
+
+fn rectangular_to_polar([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let hue = b.atan2(a).to_degrees().rem_euclid(360.0);
+    [lightness, a.hypot(b), hue]
+}
+
+fn hsl_to_srgb([hue, saturation, lightness]: [f64; 3]) -> [f64; 3] {
+    let saturation = saturation / 100.0;
+    let lightness = lightness / 100.0;
+
+    let f = |n: f64| {
+        let k = (n + hue / 30.0).rem_euclid(12.0);
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).min(1.0).max(-1.0)
+    };
+
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+fn srgb_to_hsl([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let lightness = (min + max) / 2.0;
+    let d = max - min;
+
+    let mut hue = 0.0;
+    let mut saturation = 0.0;
+
+    if d != 0.0 {
+        saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            (max - lightness) / lightness.min(1.0 - lightness)
+        };
+
+        hue = if max == red {
+            (green - blue) / d + if green < blue { 6.0 } else { 0.0 }
+        } else if max == green {
+            (blue - red) / d + 2.0
+        } else {
+            (red - green) / d + 4.0
+        } * 60.0;
+    }
+
+    [hue, saturation * 100.0, lightness * 100.0]
+}
+
+fn hwb_to_srgb([hue, whiteness, blackness]: [f64; 3]) -> [f64; 3] {
+    let whiteness = whiteness / 100.0;
+    let blackness = blackness / 100.0;
+
+    // a shade of gray, normalized so that whiteness and blackness add up to 100%
+    if whiteness + blackness >= 1.0 {
+        let gray = whiteness / (whiteness + blackness);
+        return [gray, gray, gray];
+    }
+
+    map(hsl_to_srgb([hue, 100.0, 50.0]), |n| {
+        n * (1.0 - whiteness - blackness) + whiteness
+    })
+}
+
+fn srgb_to_hwb(channels: [f64; 3]) -> [f64; 3] {
+    let [red, green, blue] = channels;
+    let [hue, ..] = srgb_to_hsl(channels);
+    let whiteness = red.min(green).min(blue);
+    let blackness = 1.0 - red.max(green).max(blue);
+    [hue, whiteness * 100.0, blackness * 100.0]
+}
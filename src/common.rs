@@ -1,4 +1,7 @@
-use std::fmt::{self, Display, Write};
+use std::{
+    fmt::{self, Display, Write},
+    rc::Rc,
+};
 
 use crate::interner::InternedString;
 
@@ -50,15 +53,16 @@ impl Op {
     /// If precedence is equal, the leftmost operation is evaluated first
     pub fn precedence(self) -> usize {
         match self {
-            Self::And | Self::Or | Self::Not => 0,
+            Self::Or | Self::Not => 0,
+            Self::And => 1,
             Self::Equal
             | Self::NotEqual
             | Self::GreaterThan
             | Self::GreaterThanEqual
             | Self::LessThan
-            | Self::LessThanEqual => 1,
-            Self::Plus | Self::Minus => 2,
-            Self::Mul | Self::Div | Self::Rem => 3,
+            | Self::LessThanEqual => 2,
+            Self::Plus | Self::Minus => 3,
+            Self::Mul | Self::Div | Self::Rem => 4,
         }
     }
 }
@@ -89,6 +93,7 @@ pub(crate) enum Brackets {
 pub(crate) enum ListSeparator {
     Space,
     Comma,
+    Slash,
 }
 
 impl ListSeparator {
@@ -96,6 +101,7 @@ impl ListSeparator {
         match self {
             Self::Space => " ",
             Self::Comma => ", ",
+            Self::Slash => " / ",
         }
     }
 
@@ -103,6 +109,7 @@ impl ListSeparator {
         match self {
             Self::Space => "space",
             Self::Comma => "comma",
+            Self::Slash => "slash",
         }
     }
 }
@@ -145,6 +152,12 @@ impl From<&str> for Identifier {
     }
 }
 
+impl From<Rc<str>> for Identifier {
+    fn from(s: Rc<str>) -> Identifier {
+        Identifier::from(&*s)
+    }
+}
+
 impl Display for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -0,0 +1,124 @@
+use std::{collections::HashMap, fmt};
+
+/// A deprecated Sass feature, which emits a warning whenever it's used
+///
+/// Each deprecation can be silenced with `Options::silence_deprecation`
+/// (`--silence-deprecation` on the command line) or turned into an error
+/// with `Options::fatal_deprecation` (`--fatal-deprecation`), using the
+/// `dart-sass` identifiers returned by `Deprecation::id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Deprecation {
+    /// Using `/` for division outside of `calc()`, which is only reported
+    /// when `Options::warn_slash_div` is set
+    SlashDiv,
+    /// Calling a global built-in function which is also available from
+    /// a built-in module, e.g. `map-get()` rather than `map.get()`
+    GlobalBuiltin,
+    /// Loading a stylesheet with `@import`
+    Import,
+    /// Using `@elseif` rather than `@else if`
+    Elseif,
+}
+
+impl Deprecation {
+    /// Every deprecation
+    pub const ALL: &'static [Self] = &[
+        Self::SlashDiv,
+        Self::GlobalBuiltin,
+        Self::Import,
+        Self::Elseif,
+    ];
+
+    /// The identifier used for this deprecation by `dart-sass`,
+    /// e.g. `slash-div`
+    #[must_use]
+    #[inline]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::SlashDiv => "slash-div",
+            Self::GlobalBuiltin => "global-builtin",
+            Self::Import => "import",
+            Self::Elseif => "elseif",
+        }
+    }
+
+    /// A description of the deprecated feature
+    #[must_use]
+    #[inline]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::SlashDiv => "/ operator for division.",
+            Self::GlobalBuiltin => "Global built-in functions that are available in sass: modules.",
+            Self::Import => "@import rules.",
+            Self::Elseif => "@elseif.",
+        }
+    }
+
+    /// The version of `dart-sass` in which this feature was deprecated
+    #[must_use]
+    #[inline]
+    pub const fn deprecated_in(self) -> &'static str {
+        match self {
+            Self::SlashDiv => "1.33.0",
+            Self::GlobalBuiltin => "1.80.0",
+            Self::Import => "1.80.0",
+            Self::Elseif => "1.3.2",
+        }
+    }
+
+    /// Look up a deprecation by its identifier, e.g. `slash-div`
+    #[must_use]
+    #[inline]
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|deprecation| deprecation.id() == id)
+    }
+}
+
+impl fmt::Display for Deprecation {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+/// The number of times each deprecation has been reported so far
+///
+/// Unless `Options::verbose` is set, only the first few uses of each
+/// deprecation are reported, so that e.g. a stylesheet which divides with `/`
+/// thousands of times doesn't bury every other warning.
+#[derive(Debug, Default)]
+pub(crate) struct DeprecationCounts {
+    counts: HashMap<Deprecation, usize>,
+    omitted: usize,
+}
+
+impl DeprecationCounts {
+    /// The number of times each deprecation is reported when not verbose
+    pub const MAX_REPETITIONS: usize = 5;
+
+    /// Records a use of `deprecation`, returning whether it should be reported
+    pub fn record(&mut self, deprecation: Deprecation, verbose: bool) -> bool {
+        let count = self.counts.entry(deprecation).or_insert(0);
+        *count += 1;
+        if verbose || *count <= Self::MAX_REPETITIONS {
+            true
+        } else {
+            self.omitted += 1;
+            false
+        }
+    }
+
+    /// Warns about the number of deprecation warnings which weren't reported
+    pub fn report_omitted(&self) {
+        if self.omitted > 0 {
+            eprintln!(
+                "Warning: {} repetitive deprecation warnings omitted.\nRun in verbose mode to see all warnings.",
+                self.omitted
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@ use std::{
     string::FromUtf8Error,
 };
 
-use codemap::{Span, SpanLoc};
+use codemap::{CodeMap, Span, SpanLoc};
 
 pub type SassResult<T> = Result<T, Box<SassError>>;
 
@@ -31,22 +31,93 @@ pub type SassResult<T> = Result<T, Box<SassError>>;
 /// directly to the error by simply clicking the file name.
 ///
 /// Note that this is a deviation from the Sass specification.
+///
+/// When `Options::continue_on_error` is set, a single `SassError` may
+/// hold several errors, which are displayed one after another and
+/// listed by `SassError::errors`.
+///
+/// Errors raised inside of a mixin, function, `@content` block,
+/// or imported file are followed by a Sass stack trace, e.g.
+///```text
+///   input.scss 2:3   button()
+///   input.scss 5:13  root stylesheet
+///```
 #[derive(Debug, Clone)]
 pub struct SassError {
     kind: SassErrorKind,
 }
 
 impl SassError {
-    pub(crate) fn raw(self) -> (String, Span) {
+    /// The Sass stack trace of this error, starting with the frame
+    /// in which it was raised and ending with the root stylesheet
+    ///
+    /// This is empty for errors without a location in a stylesheet,
+    /// such as an `io::Error`
+    #[must_use]
+    #[inline]
+    pub fn trace(&self) -> &[StackFrame] {
+        match &self.kind {
+            SassErrorKind::ParseError { trace, .. } => trace,
+            SassErrorKind::Multiple(errors) => errors[0].trace(),
+            _ => &[],
+        }
+    }
+
+    /// Every error this contains, in the order they were raised
+    ///
+    /// This is only ever more than just this error itself when
+    /// `Options::continue_on_error` is set
+    #[must_use]
+    #[inline]
+    pub fn errors(&self) -> &[SassError] {
+        match &self.kind {
+            SassErrorKind::Multiple(errors) => errors,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    /// Returns the message, span, and trace of a raw error, or the error
+    /// itself if it has no span (e.g. an `io::Error`)
+    pub(crate) fn raw(self) -> Result<(String, Span, Option<Vec<(Span, String)>>), Self> {
         match self.kind {
-            SassErrorKind::Raw(string, span) => (string, span),
-            e => todo!("unable to get raw of {:?}", e),
+            SassErrorKind::Raw(string, span, trace) => Ok((string, span, trace)),
+            _ => Err(self),
+        }
+    }
+
+    /// The span of a raw error which has not yet been given a trace
+    pub(crate) fn untraced_span(&self) -> Option<Span> {
+        match self.kind {
+            SassErrorKind::Raw(_, span, None) => Some(span),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_trace(&mut self, trace: Vec<(Span, String)>) {
+        if let SassErrorKind::Raw(_, _, t) = &mut self.kind {
+            *t = Some(trace);
+        }
+    }
+
+    pub(crate) const fn output_too_large(max: usize) -> Self {
+        SassError {
+            kind: SassErrorKind::OutputTooLarge(max),
+        }
+    }
+
+    pub(crate) const fn multiple(errors: Vec<SassError>) -> Self {
+        SassError {
+            kind: SassErrorKind::Multiple(errors),
         }
     }
 
-    pub(crate) const fn from_loc(message: String, loc: SpanLoc) -> Self {
+    pub(crate) const fn from_loc(message: String, loc: SpanLoc, trace: Vec<StackFrame>) -> Self {
         SassError {
-            kind: SassErrorKind::ParseError { message, loc },
+            kind: SassErrorKind::ParseError {
+                message,
+                loc,
+                trace,
+            },
         }
     }
 }
@@ -54,17 +125,23 @@ impl SassError {
 #[derive(Debug, Clone)]
 enum SassErrorKind {
     /// A raw error with no additional metadata
-    /// It contains only a `String` message and
-    /// a span
-    Raw(String, Span),
+    /// It contains only a `String` message, a span,
+    /// and, once it has left a mixin, function, `@content`
+    /// block, or import, the Sass stack trace at the point it was raised
+    Raw(String, Span, Option<Vec<(Span, String)>>),
     ParseError {
         message: String,
         loc: SpanLoc,
+        trace: Vec<StackFrame>,
     },
     // we put IoErrors in an `Rc` to allow it to be
     // cloneable
     IoError(Rc<io::Error>),
     FromUtf8Error(String),
+    /// The emitted CSS was larger than `Options::max_output_size`
+    OutputTooLarge(usize),
+    /// Several errors recovered from when `Options::continue_on_error` is set
+    Multiple(Vec<SassError>),
 }
 
 impl Display for SassError {
@@ -73,11 +150,31 @@ impl Display for SassError {
     // TODO: integrate with codemap-diagnostics
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (message, loc) = match &self.kind {
-            SassErrorKind::ParseError { message, loc } => (message, loc),
+        let (message, loc, trace) = match &self.kind {
+            SassErrorKind::ParseError {
+                message,
+                loc,
+                trace,
+            } => (message, loc, trace),
             SassErrorKind::FromUtf8Error(s) => return writeln!(f, "Error: {}", s),
             SassErrorKind::IoError(s) => return writeln!(f, "Error: {}", s),
-            SassErrorKind::Raw(..) => todo!(),
+            SassErrorKind::Raw(message, ..) => return writeln!(f, "Error: {}", message),
+            SassErrorKind::OutputTooLarge(max) => {
+                return writeln!(
+                    f,
+                    "Error: Output exceeds the maximum size of {} bytes.",
+                    max
+                )
+            }
+            SassErrorKind::Multiple(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                return Ok(());
+            }
         };
         let line = loc.begin.line + 1;
         let col = loc.begin.column + 1;
@@ -98,10 +195,90 @@ impl Display for SassError {
         )?;
         writeln!(f, "{}|", padding)?;
         writeln!(f, "./{}:{}:{}", loc.file.name(), line, col)?;
+        if trace.len() > 1 {
+            write!(f, "{}", format_trace(trace, 2))?;
+        }
         Ok(())
     }
 }
 
+/// A single frame of a Sass stack trace: a location in a stylesheet,
+/// and the member (e.g. a mixin or function) being evaluated there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    file: String,
+    line: usize,
+    column: usize,
+    member: String,
+}
+
+impl StackFrame {
+    pub(crate) fn new(map: &CodeMap, span: Span, member: String) -> Self {
+        let loc = map.look_up_span(span);
+        StackFrame {
+            file: loc.file.name().to_owned(),
+            line: loc.begin.line + 1,
+            column: loc.begin.column + 1,
+            member,
+        }
+    }
+
+    /// The name of the file containing this frame
+    #[must_use]
+    #[inline]
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The 1-indexed line of this frame
+    #[must_use]
+    #[inline]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-indexed column of this frame
+    #[must_use]
+    #[inline]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The member being evaluated, e.g. `button()` for a mixin or function
+    /// named `button`, `@content`, `@import`, or `root stylesheet`
+    #[must_use]
+    #[inline]
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+}
+
+/// Formats a Sass stack trace as `dart-sass` does, with one frame per line
+/// and the member names aligned, e.g.
+///```text
+///   input.scss 2:3   button()
+///   input.scss 5:13  root stylesheet
+///```
+pub(crate) fn format_trace(trace: &[StackFrame], indent: usize) -> String {
+    let locations: Vec<String> = trace
+        .iter()
+        .map(|frame| format!("{} {}:{}", frame.file, frame.line, frame.column))
+        .collect();
+    let width = locations.iter().map(String::len).max().unwrap_or(0);
+
+    let mut buf = String::new();
+    for (location, frame) in locations.iter().zip(trace) {
+        buf.push_str(&format!(
+            "{}{:width$}  {}\n",
+            " ".repeat(indent),
+            location,
+            frame.member,
+            width = width
+        ));
+    }
+    buf
+}
+
 impl From<io::Error> for Box<SassError> {
     #[inline]
     fn from(error: io::Error) -> Box<SassError> {
@@ -127,7 +304,7 @@ impl From<(&str, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (&str, Span)) -> Box<SassError> {
         Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0.to_owned(), error.1),
+            kind: SassErrorKind::Raw(error.0.to_owned(), error.1, None),
         })
     }
 }
@@ -136,7 +313,7 @@ impl From<(String, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (String, Span)) -> Box<SassError> {
         Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0, error.1),
+            kind: SassErrorKind::Raw(error.0, error.1, None),
         })
     }
 }
@@ -80,7 +80,11 @@ grass input.scss
 )]
 #![cfg_attr(feature = "nightly", feature(track_caller))]
 #![cfg_attr(feature = "profiling", inline(never))]
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
@@ -91,17 +95,20 @@ use codemap::CodeMap;
 
 use peekmore::PeekMore;
 
-pub use crate::error::{SassError as Error, SassResult as Result};
+pub use crate::deprecation::Deprecation;
+pub use crate::error::{SassError as Error, SassResult as Result, StackFrame};
 pub(crate) use crate::token::Token;
 use crate::{
+    deprecation::DeprecationCounts,
     lexer::Lexer,
     output::Css,
     parse::{
         common::{ContextFlags, NeverEmptyVec},
-        Parser,
+        Budget, Parser,
     },
     scope::{Scope, Scopes},
     selector::{Extender, Selector},
+    value::{PrecisionGuard, DEFAULT_PRECISION},
 };
 
 mod args;
@@ -109,6 +116,7 @@ mod atrule;
 mod builtin;
 mod color;
 mod common;
+mod deprecation;
 mod error;
 mod interner;
 mod lexer;
@@ -122,9 +130,388 @@ mod unit;
 mod utils;
 mod value;
 
+/// Configuration for Sass compilation
+///
+/// The simplest usage is `grass::Options::default()`;
+/// however, a builder pattern is also exposed to offer
+/// more control.
+#[derive(Debug, Clone)]
+pub struct Options {
+    warn_slash_div: bool,
+    max_depth: usize,
+    max_call_depth: usize,
+    max_steps: Option<usize>,
+    timeout: Option<Duration>,
+    max_output_size: Option<usize>,
+    import_roots: Vec<PathBuf>,
+    deterministic: bool,
+    silenced_deprecations: Vec<Deprecation>,
+    fatal_deprecations: Vec<Deprecation>,
+    verbose: bool,
+    continue_on_error: bool,
+    load_paths: Vec<PathBuf>,
+    quiet: bool,
+    quiet_deps: bool,
+    precision: usize,
+}
+
+impl Default for Options {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            warn_slash_div: false,
+            max_depth: 100,
+            max_call_depth: 10_000,
+            max_steps: None,
+            timeout: None,
+            max_output_size: None,
+            import_roots: Vec::new(),
+            deterministic: false,
+            silenced_deprecations: Vec::new(),
+            fatal_deprecations: Vec::new(),
+            verbose: false,
+            continue_on_error: false,
+            load_paths: Vec::new(),
+            quiet: false,
+            quiet_deps: false,
+            precision: DEFAULT_PRECISION,
+        }
+    }
+}
+
+impl Options {
+    /// Emit a deprecation warning whenever `/` is used to divide two numbers
+    /// outside of `calc()`
+    ///
+    /// This is off by default, and is intended to help migrate stylesheets
+    /// to `math.div()` before `/` stops meaning division.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().warn_slash_div(true);
+    ///     let sass = grass::from_string_with_options(
+    ///         "a { b: (4px / 2); }".to_string(),
+    ///         &options,
+    ///     )?;
+    ///     assert_eq!(sass, "a {\n  b: 2px;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn warn_slash_div(mut self, warn_slash_div: bool) -> Self {
+        self.warn_slash_div = warn_slash_div;
+        self
+    }
+
+    /// The maximum depth to which blocks, expressions, and selectors may be
+    /// nested
+    ///
+    /// Exceeding this limit is an error, so that deeply nested input can't
+    /// exhaust memory. Calls to `@function`s and `@mixin`s are limited
+    /// separately by `Options::max_call_depth`. This defaults to 100.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().max_depth(2);
+    ///     assert!(grass::from_string_with_options(
+    ///         "a { b { c { d: e; } } }".to_string(),
+    ///         &options,
+    ///     ).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The maximum number of calls to `@function`s and `@mixin`s which may be
+    /// in progress at once
+    ///
+    /// Exceeding this limit errors with "Stack overflow.", which prevents
+    /// infinitely recursive `@function`s and `@mixin`s from running until
+    /// memory runs out. This defaults to 10,000.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().max_call_depth(10);
+    ///     assert!(grass::from_string_with_options(
+    ///         "@function foo($a) { @return foo($a); }\na { b: foo(1); }".to_string(),
+    ///         &options,
+    ///     ).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// The maximum number of steps of evaluation, where a step is a block,
+    /// a call to a `@function` or `@mixin`, an iteration of a loop, or
+    /// building 1024 bytes of a string (or elements of a list or map)
+    ///
+    /// There is no limit by default. Along with `Options::timeout`, this
+    /// guarantees that untrusted stylesheets (e.g. containing an infinite
+    /// `@while` loop) eventually stop compiling.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().max_steps(1000);
+    ///     let sass = "$i: 0; @while true { $i: $i + 1; }";
+    ///     assert!(grass::from_string_with_options(sass.to_string(), &options).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// The maximum amount of time compilation may take
+    ///
+    /// There is no limit by default. The time is checked at each step of
+    /// evaluation (see `Options::max_steps`) and after evaluating each
+    /// expression, so a single step which takes a long time may overrun it.
+    ///
+    /// This is not supported when compiling to `wasm32-unknown-unknown`,
+    /// which has no clock.
+    #[must_use]
+    #[inline]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The maximum size in bytes of the emitted CSS
+    ///
+    /// There is no limit by default. No single string (or list or map) may
+    /// be larger than this either, even if it is never emitted.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().max_output_size(10);
+    ///     let sass = "@for $i from 1 through 10 { a { b: $i; } }";
+    ///     assert!(grass::from_string_with_options(sass.to_string(), &options).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn max_output_size(mut self, max_output_size: usize) -> Self {
+        self.max_output_size = Some(max_output_size);
+        self
+    }
+
+    /// Only allow `@import`s of files within the directory `root`
+    ///
+    /// By default, a stylesheet may import any file on disk. Once this has
+    /// been called, imports resolving to files outside of every allowed
+    /// directory fail as though the file did not exist. This may be called
+    /// multiple times to allow several directories.
+    ///
+    /// Paths are compared after resolving symlinks, so a symlink within `root`
+    /// which points outside of it may not be imported.
+    #[must_use]
+    #[inline]
+    pub fn allow_imports_from<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.import_roots.push(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Disable the builtin functions whose results are not determined by the
+    /// input stylesheet, `random()` and `unique-id()`
+    ///
+    /// This is off by default. When on, calling either function is an error.
+    #[must_use]
+    #[inline]
+    pub const fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Don't emit warnings for uses of the deprecated feature `deprecation`
+    ///
+    /// This may be called multiple times to silence several deprecations.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default()
+    ///         .silence_deprecation(grass::Deprecation::GlobalBuiltin);
+    ///     let sass = grass::from_string_with_options(
+    ///         "a { b: map-get((c: d), c); }".to_string(),
+    ///         &options,
+    ///     )?;
+    ///     assert_eq!(sass, "a {\n  b: d;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn silence_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.silenced_deprecations.push(deprecation);
+        self
+    }
+
+    /// Treat uses of the deprecated feature `deprecation` as errors rather
+    /// than warnings
+    ///
+    /// This may be called multiple times to make several deprecations fatal.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default()
+    ///         .fatal_deprecation(grass::Deprecation::GlobalBuiltin);
+    ///     let sass = "a { b: map-get((c: d), c); }";
+    ///     assert!(grass::from_string_with_options(sass.to_string(), &options).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn fatal_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.fatal_deprecations.push(deprecation);
+        self
+    }
+
+    /// Emit every deprecation warning
+    ///
+    /// By default, only the first 5 uses of each deprecated feature are
+    /// reported, followed by a count of the warnings which were omitted.
+    #[must_use]
+    #[inline]
+    pub const fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Rather than stopping at the first error, skip the statement in which
+    /// it was raised and continue compiling in order to report every error
+    /// in the stylesheet at once
+    ///
+    /// This is off by default. No CSS is emitted if any error occurred, and
+    /// the errors are returned together, with `Error::errors` listing each
+    /// of them. Errors inside of a `@function` are recovered from by skipping
+    /// the statement which called it.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().continue_on_error(true);
+    ///     let sass = "a { b: $c; }\nd { e: $f; }";
+    ///     let err = grass::from_string_with_options(sass.to_string(), &options).unwrap_err();
+    ///     assert_eq!(err.errors().len(), 2);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Look for `@import`ed files in the directory `path` when they can't be
+    /// found relative to the file importing them
+    ///
+    /// This may be called multiple times, in which case the directories are
+    /// searched in the order they were added. Files loaded this way, along
+    /// with any files they import, are considered dependencies for the
+    /// purposes of `Options::quiet_deps`.
+    #[must_use]
+    #[inline]
+    pub fn load_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.load_paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Don't print warnings or the output of `@debug`
+    ///
+    /// This is off by default. Deprecations made fatal with
+    /// `Options::fatal_deprecation` are still errors.
+    #[must_use]
+    #[inline]
+    pub const fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Don't print warnings raised within dependencies, which are the files
+    /// loaded through `Options::load_path` and the files they import
+    ///
+    /// This is off by default. Warnings raised within the stylesheet being
+    /// compiled, or files it imports by relative path, are still printed.
+    #[must_use]
+    #[inline]
+    pub const fn quiet_deps(mut self, quiet_deps: bool) -> Self {
+        self.quiet_deps = quiet_deps;
+        self
+    }
+
+    /// The maximum number of digits after the decimal point in emitted
+    /// numbers, including in colors and the result of `inspect()`
+    ///
+    /// This defaults to 10, as in `dart-sass`. `libsass` used 5. Numbers are
+    /// still compared to 10 decimal places regardless of this setting.
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default().precision(5);
+    ///     let sass = grass::from_string_with_options(
+    ///         "a { b: 1.23456789; }".to_string(),
+    ///         &options,
+    ///     )?;
+    ///     assert_eq!(sass, "a {\n  b: 1.23457;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+}
+
 fn raw_to_parse_error(map: &CodeMap, err: Error) -> Box<Error> {
-    let (message, span) = err.raw();
-    Box::new(Error::from_loc(message, map.look_up_span(span)))
+    match err.raw() {
+        Ok((message, span, trace)) => {
+            let trace = trace
+                .unwrap_or_else(|| vec![(span, "root stylesheet".to_owned())])
+                .into_iter()
+                .map(|(span, member)| StackFrame::new(map, span, member))
+                .collect();
+            Box::new(Error::from_loc(message, map.look_up_span(span), trace))
+        }
+        Err(err) => Box::new(err),
+    }
+}
+
+/// Combines the errors recovered from while parsing, if any, with the result
+/// of parsing
+fn collect_errors<T>(map: &CodeMap, result: Result<T>, mut errors: Vec<Error>) -> Result<T> {
+    match result {
+        Ok(value) if errors.is_empty() => return Ok(value),
+        Ok(..) => {}
+        Err(e) => errors.push(*e),
+    }
+
+    let mut errors: Vec<Error> = errors
+        .into_iter()
+        .map(|e| *raw_to_parse_error(map, e))
+        .collect();
+
+    if errors.len() == 1 {
+        Err(Box::new(errors.remove(0)))
+    } else {
+        Err(Box::new(Error::multiple(errors)))
+    }
 }
 
 /// Compile CSS from a path
@@ -140,9 +527,29 @@ fn raw_to_parse_error(map: &CodeMap, err: Error) -> Box<Error> {
 #[cfg_attr(not(feature = "profiling"), inline)]
 #[cfg(not(feature = "wasm"))]
 pub fn from_path(p: &str) -> Result<String> {
+    from_path_with_options(p, &Options::default())
+}
+
+/// Compile CSS from a path, configured by `options`
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = grass::Options::default().quiet(true);
+///     let sass = grass::from_path_with_options("input.scss", &options)?;
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn from_path_with_options(p: &str, options: &Options) -> Result<String> {
+    let _precision = PrecisionGuard::new(options.precision);
     let mut map = CodeMap::new();
     let file = map.add_file(p.into(), String::from_utf8(fs::read(p)?)?);
     let empty_span = file.span.subspan(0, 0);
+    let mut deprecations = DeprecationCounts::default();
+    let mut errors = Vec::new();
+    let mut budget = Budget::new(options);
 
     let stmts = Parser {
         toks: &mut Lexer::new(&file)
@@ -160,14 +567,25 @@ pub fn from_path(p: &str) -> Result<String> {
         at_root: true,
         at_root_has_selector: false,
         extender: &mut Extender::new(empty_span),
+        options,
         content_scopes: &mut Scopes::new(),
+        depth: 0,
+        import_stack: &mut vec![(fs::canonicalize(p)?, empty_span)],
+        budget: &mut budget,
+        call_stack: &mut Vec::new(),
+        deprecations: &mut deprecations,
+        errors: &mut errors,
+        dependencies: &mut Vec::new(),
     }
-    .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e))?;
+    .parse();
+
+    deprecations.report_omitted();
+
+    let stmts = collect_errors(&map, stmts, errors)?;
 
     Css::from_stmts(stmts, false)
         .map_err(|e| raw_to_parse_error(&map, *e))?
-        .pretty_print(&map)
+        .pretty_print(&map, options, &mut budget)
         .map_err(|e| raw_to_parse_error(&map, *e))
 }
 
@@ -184,9 +602,30 @@ pub fn from_path(p: &str) -> Result<String> {
 #[cfg_attr(not(feature = "profiling"), inline)]
 #[cfg(not(feature = "wasm"))]
 pub fn from_string(p: String) -> Result<String> {
+    from_string_with_options(p, &Options::default())
+}
+
+/// Compile CSS from a string, configured by `options`
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = grass::Options::default().precision(3);
+///     let sass = grass::from_string_with_options("a { b: (1/3); }".to_string(), &options)?;
+///     assert_eq!(sass, "a {\n  b: 0.333;\n}\n");
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn from_string_with_options(p: String, options: &Options) -> Result<String> {
+    let _precision = PrecisionGuard::new(options.precision);
     let mut map = CodeMap::new();
     let file = map.add_file("stdin".into(), p);
     let empty_span = file.span.subspan(0, 0);
+    let mut deprecations = DeprecationCounts::default();
+    let mut errors = Vec::new();
+    let mut budget = Budget::new(options);
     let stmts = Parser {
         toks: &mut Lexer::new(&file)
             .collect::<Vec<Token>>()
@@ -203,23 +642,39 @@ pub fn from_string(p: String) -> Result<String> {
         at_root: true,
         at_root_has_selector: false,
         extender: &mut Extender::new(empty_span),
+        options,
         content_scopes: &mut Scopes::new(),
+        depth: 0,
+        import_stack: &mut Vec::new(),
+        budget: &mut budget,
+        call_stack: &mut Vec::new(),
+        deprecations: &mut deprecations,
+        errors: &mut errors,
+        dependencies: &mut Vec::new(),
     }
-    .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e))?;
+    .parse();
+
+    deprecations.report_omitted();
+
+    let stmts = collect_errors(&map, stmts, errors)?;
 
     Css::from_stmts(stmts, false)
         .map_err(|e| raw_to_parse_error(&map, *e))?
-        .pretty_print(&map)
+        .pretty_print(&map, options, &mut budget)
         .map_err(|e| raw_to_parse_error(&map, *e))
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn from_string(p: String) -> std::result::Result<String, JsValue> {
+    let options = &Options::default();
+    let _precision = PrecisionGuard::new(options.precision);
     let mut map = CodeMap::new();
     let file = map.add_file("stdin".into(), p);
     let empty_span = file.span.subspan(0, 0);
+    let mut deprecations = DeprecationCounts::default();
+    let mut errors = Vec::new();
+    let mut budget = Budget::new(options);
 
     let stmts = Parser {
         toks: &mut Lexer::new(&file)
@@ -237,13 +692,25 @@ pub fn from_string(p: String) -> std::result::Result<String, JsValue> {
         at_root: true,
         at_root_has_selector: false,
         extender: &mut Extender::new(empty_span),
+        options,
         content_scopes: &mut Scopes::new(),
+        depth: 0,
+        import_stack: &mut Vec::new(),
+        budget: &mut budget,
+        call_stack: &mut Vec::new(),
+        deprecations: &mut deprecations,
+        errors: &mut errors,
+        dependencies: &mut Vec::new(),
     }
-    .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e).to_string())?;
+    .parse();
+
+    deprecations.report_omitted();
+
+    let stmts = collect_errors(&map, stmts, errors).map_err(|e| e.to_string())?;
 
     Ok(Css::from_stmts(stmts, false)
         .map_err(|e| raw_to_parse_error(&map, *e).to_string())?
-        .pretty_print(&map)
-        .map_err(|e| raw_to_parse_error(&map, *e).to_string())?)
+        .pretty_print(&map, options, &mut budget)
+        .map_err(|e| raw_to_parse_error(&map, *e))
+        .map_err(|e| e.to_string())?)
 }
@@ -6,7 +6,7 @@ use std::{
 use clap::{arg_enum, App, AppSettings, Arg};
 
 #[cfg(not(feature = "wasm"))]
-use grass::from_path;
+use grass::{from_path_with_options, Deprecation, Options};
 
 arg_enum! {
     #[derive(PartialEq, Debug)]
@@ -30,6 +30,8 @@ fn main() {}
 #[cfg(not(feature = "wasm"))]
 #[cfg_attr(feature = "profiling", inline(never))]
 fn main() -> std::io::Result<()> {
+    let deprecations: Vec<&str> = Deprecation::ALL.iter().map(|d| d.id()).collect();
+
     let matches = App::new("grass")
         .setting(AppSettings::ColoredHelp)
         .version(env!("CARGO_PKG_VERSION"))
@@ -51,7 +53,6 @@ fn main() -> std::io::Result<()> {
             Arg::with_name("LOAD_PATH")
                 .short("I")
                 .long("load-path")
-                .hidden(true)
                 .help("A path to use when resolving imports. May be passed multiple times.")
                 .multiple(true)
                 .takes_value(true)
@@ -161,9 +162,46 @@ fn main() -> std::io::Result<()> {
             Arg::with_name("QUIET")
                 .short("q")
                 .long("quiet")
-                .hidden(true)
                 .help("Don't print warnings."),
         )
+        .arg(
+            Arg::with_name("QUIET_DEPS")
+                .long("quiet-deps")
+                .help("Don't print warnings from dependencies loaded through load paths."),
+        )
+        .arg(
+            Arg::with_name("WARN_SLASH_DIV")
+                .long("warn-slash-div")
+                .help("Emit a deprecation warning when / is used for division."),
+        )
+        .arg(
+            Arg::with_name("SILENCE_DEPRECATION")
+                .long("silence-deprecation")
+                .help("Deprecations to ignore. May be passed multiple times.")
+                .multiple(true)
+                .takes_value(true)
+                .number_of_values(1)
+                .possible_values(&deprecations),
+        )
+        .arg(
+            Arg::with_name("FATAL_DEPRECATION")
+                .long("fatal-deprecation")
+                .help("Deprecations to treat as errors. May be passed multiple times.")
+                .multiple(true)
+                .takes_value(true)
+                .number_of_values(1)
+                .possible_values(&deprecations),
+        )
+        .arg(
+            Arg::with_name("CONTINUE_ON_ERROR")
+                .long("continue-on-error")
+                .help("Report every error in the stylesheet rather than stopping at the first."),
+        )
+        .arg(
+            Arg::with_name("VERBOSE")
+                .long("verbose")
+                .help("Print all deprecation warnings even when they're repetitive."),
+        )
         .arg(
             Arg::with_name("INPUT")
                 .required(true)
@@ -174,20 +212,58 @@ fn main() -> std::io::Result<()> {
                 .help("Output SCSS file")
         )
 
-        // Hidden, legacy arguments
+        // Legacy arguments
         .arg(
             Arg::with_name("PRECISION")
                 .long("precision")
-                .hidden(true)
+                .help("How many digits of precision to use when outputting decimal numbers.")
                 .takes_value(true)
+                .validator(|precision| {
+                    precision
+                        .parse::<usize>()
+                        .map(|_| ())
+                        .map_err(|_| "must be a non-negative integer".to_owned())
+                })
         )
         .get_matches();
 
+    let mut options = Options::default()
+        .warn_slash_div(matches.is_present("WARN_SLASH_DIV"))
+        .verbose(matches.is_present("VERBOSE"))
+        .continue_on_error(matches.is_present("CONTINUE_ON_ERROR"))
+        .quiet(matches.is_present("QUIET"))
+        .quiet_deps(matches.is_present("QUIET_DEPS"));
+
+    if let Some(precision) = matches.value_of("PRECISION") {
+        // this has already been validated by clap
+        options = options.precision(precision.parse().unwrap());
+    }
+
+    for path in matches.values_of("LOAD_PATH").into_iter().flatten() {
+        options = options.load_path(path);
+    }
+
+    for id in matches
+        .values_of("SILENCE_DEPRECATION")
+        .into_iter()
+        .flatten()
+    {
+        if let Some(deprecation) = Deprecation::from_id(id) {
+            options = options.silence_deprecation(deprecation);
+        }
+    }
+
+    for id in matches.values_of("FATAL_DEPRECATION").into_iter().flatten() {
+        if let Some(deprecation) = Deprecation::from_id(id) {
+            options = options.fatal_deprecation(deprecation);
+        }
+    }
+
     if let Some(name) = matches.value_of("INPUT") {
         if let Some(path) = matches.value_of("OUTPUT") {
             let mut buf = BufWriter::new(File::open(path).unwrap_or(File::create(path)?));
             buf.write_all(
-                from_path(name)
+                from_path_with_options(name, &options)
                     .unwrap_or_else(|e| {
                         eprintln!("{}", e);
                         std::process::exit(1)
@@ -197,7 +273,7 @@ fn main() -> std::io::Result<()> {
         } else {
             let mut stdout = BufWriter::new(stdout());
             stdout.write_all(
-                from_path(name)
+                from_path_with_options(name, &options)
                     .unwrap_or_else(|e| {
                         eprintln!("{}", e);
                         std::process::exit(1)
@@ -9,10 +9,11 @@ use crate::{
         media::MediaRule,
         SupportsRule, UnknownAtRule,
     },
-    error::SassResult,
-    parse::Stmt,
+    error::{SassError, SassResult},
+    parse::{Budget, Stmt},
     selector::Selector,
     style::Style,
+    Options,
 };
 
 #[derive(Debug, Clone)]
@@ -54,40 +55,19 @@ impl BlockEntry {
     }
 }
 
-impl Toplevel {
-    const fn new_rule(selector: Selector) -> Self {
-        Toplevel::RuleSet(selector, Vec::new())
-    }
-
-    fn new_keyframes_rule(selector: Vec<KeyframesSelector>) -> Self {
-        Toplevel::KeyframesRuleSet(selector, Vec::new())
+impl BlockEntry {
+    fn check_timeout(&self, options: &Options, budget: &mut Budget) -> SassResult<()> {
+        match self {
+            BlockEntry::Style(s) => budget.check_timeout(options, s.value.span),
+            BlockEntry::MultilineComment(..) | BlockEntry::Import(..) => Ok(()),
+        }
     }
 
-    fn push_style(&mut self, s: Style) {
+    fn push_style(entries: &mut Vec<BlockEntry>, s: Style) {
         if s.value.is_null() {
             return;
         }
-        if let Toplevel::RuleSet(_, entries) | Toplevel::KeyframesRuleSet(_, entries) = self {
-            entries.push(BlockEntry::Style(s));
-        } else {
-            panic!()
-        }
-    }
-
-    fn push_comment(&mut self, s: String) {
-        if let Toplevel::RuleSet(_, entries) | Toplevel::KeyframesRuleSet(_, entries) = self {
-            entries.push(BlockEntry::MultilineComment(s));
-        } else {
-            panic!()
-        }
-    }
-
-    fn push_import(&mut self, s: String) {
-        if let Toplevel::RuleSet(_, entries) | Toplevel::KeyframesRuleSet(_, entries) = self {
-            entries.push(BlockEntry::Import(s));
-        } else {
-            panic!()
-        }
+        entries.push(BlockEntry::Style(s));
     }
 }
 
@@ -119,12 +99,13 @@ impl Css {
                 if selector.is_empty() {
                     return Ok(Vec::new());
                 }
-                let mut vals = vec![Toplevel::new_rule(selector)];
+                let mut entries = Vec::new();
+                let mut vals = Vec::new();
                 for rule in body {
                     match rule {
                         Stmt::RuleSet { .. } => vals.extend(self.parse_stmt(rule)?),
-                        Stmt::Style(s) => vals.get_mut(0).unwrap().push_style(s),
-                        Stmt::Comment(s) => vals.get_mut(0).unwrap().push_comment(s),
+                        Stmt::Style(s) => BlockEntry::push_style(&mut entries, s),
+                        Stmt::Comment(s) => entries.push(BlockEntry::MultilineComment(s)),
                         Stmt::Media(m) => {
                             let MediaRule { query, body, .. } = *m;
                             vals.push(Toplevel::Media { query, body })
@@ -154,12 +135,11 @@ impl Css {
                             let Keyframes { name, body } = *k;
                             vals.push(Toplevel::Keyframes(Box::new(Keyframes { name, body })))
                         }
-                        k @ Stmt::KeyframesRuleSet(..) => {
-                            unreachable!("@keyframes ruleset {:?}", k)
-                        }
-                        Stmt::Import(s) => vals.get_mut(0).unwrap().push_import(s),
+                        Stmt::KeyframesRuleSet(..) => vals.extend(self.parse_stmt(rule)?),
+                        Stmt::Import(s) => entries.push(BlockEntry::Import(s)),
                     };
                 }
+                vals.insert(0, Toplevel::RuleSet(selector, entries));
                 vals
             }
             Stmt::Comment(s) => vec![Toplevel::MultilineComment(s)],
@@ -184,21 +164,30 @@ impl Css {
                 }))]
             }
             Stmt::Return(..) => unreachable!("@return: {:?}", stmt),
-            Stmt::AtRoot { .. } => unreachable!("@at-root: {:?}", stmt),
+            Stmt::AtRoot { body } => {
+                let mut vals = Vec::new();
+                for stmt in body {
+                    vals.extend(self.parse_stmt(stmt)?);
+                }
+                vals
+            }
             Stmt::Keyframes(k) => vec![Toplevel::Keyframes(k)],
             Stmt::KeyframesRuleSet(k) => {
                 let KeyframesRuleSet { body, selector } = *k;
                 if body.is_empty() {
                     return Ok(Vec::new());
                 }
-                let mut vals = vec![Toplevel::new_keyframes_rule(selector)];
+                let mut entries = Vec::new();
+                let mut vals = Vec::new();
                 for rule in body {
                     match rule {
-                        Stmt::Style(s) => vals.get_mut(0).unwrap().push_style(s),
-                        Stmt::KeyframesRuleSet(..) => vals.extend(self.parse_stmt(rule)?),
-                        _ => todo!(),
+                        Stmt::Style(s) => BlockEntry::push_style(&mut entries, s),
+                        Stmt::Comment(s) => entries.push(BlockEntry::MultilineComment(s)),
+                        // nested keyframes blocks are emitted after their parent
+                        _ => vals.extend(self.parse_stmt(rule)?),
                     }
                 }
+                vals.insert(0, Toplevel::KeyframesRuleSet(selector, entries));
                 vals
             }
         })
@@ -223,15 +212,25 @@ impl Css {
         Ok(self)
     }
 
-    pub fn pretty_print(self, map: &CodeMap) -> SassResult<String> {
+    /// Write the CSS, which errors as soon as it exceeds
+    /// `Options::max_output_size` or `Options::timeout` is exceeded
+    pub fn pretty_print(
+        self,
+        map: &CodeMap,
+        options: &Options,
+        budget: &mut Budget,
+    ) -> SassResult<String> {
         let mut string = Vec::new();
-        self._inner_pretty_print(&mut string, map, 0)?;
-        if string.iter().any(|s| !s.is_ascii()) {
-            return Ok(format!("@charset \"UTF-8\";\n{}", unsafe {
+        self._inner_pretty_print(&mut string, map, 0, options, budget)?;
+        let css = if string.iter().any(|s| !s.is_ascii()) {
+            format!("@charset \"UTF-8\";\n{}", unsafe {
                 String::from_utf8_unchecked(string)
-            }));
-        }
-        Ok(unsafe { String::from_utf8_unchecked(string) })
+            })
+        } else {
+            unsafe { String::from_utf8_unchecked(string) }
+        };
+        check_output_size(css.as_bytes(), options)?;
+        Ok(css)
     }
 
     fn _inner_pretty_print(
@@ -239,6 +238,8 @@ impl Css {
         buf: &mut Vec<u8>,
         map: &CodeMap,
         nesting: usize,
+        options: &Options,
+        budget: &mut Budget,
     ) -> SassResult<()> {
         let mut has_written = false;
         let padding = vec![' '; nesting * 2].iter().collect::<String>();
@@ -256,7 +257,9 @@ impl Css {
                     }
                     writeln!(buf, "{}{} {{", padding, selector)?;
                     for style in styles {
+                        style.check_timeout(options, budget)?;
                         writeln!(buf, "{}  {}", padding, style.to_string()?)?;
+                        check_output_size(buf, options)?;
                     }
                     writeln!(buf, "{}}}", padding)?;
                 }
@@ -277,7 +280,9 @@ impl Css {
                             .join(", ")
                     )?;
                     for style in body {
+                        style.check_timeout(options, budget)?;
                         writeln!(buf, "{}  {}", padding, style.to_string()?)?;
+                        check_output_size(buf, options)?;
                     }
                     writeln!(buf, "{}}}", padding)?;
                 }
@@ -309,7 +314,13 @@ impl Css {
                         writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true)?._inner_pretty_print(buf, map, nesting + 1)?;
+                    Css::from_stmts(body, true)?._inner_pretty_print(
+                        buf,
+                        map,
+                        nesting + 1,
+                        options,
+                        budget,
+                    )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::Keyframes(k) => {
@@ -332,7 +343,13 @@ impl Css {
                         writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true)?._inner_pretty_print(buf, map, nesting + 1)?;
+                    Css::from_stmts(body, true)?._inner_pretty_print(
+                        buf,
+                        map,
+                        nesting + 1,
+                        options,
+                        budget,
+                    )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::Supports { params, body } => {
@@ -354,7 +371,13 @@ impl Css {
                         writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true)?._inner_pretty_print(buf, map, nesting + 1)?;
+                    Css::from_stmts(body, true)?._inner_pretty_print(
+                        buf,
+                        map,
+                        nesting + 1,
+                        options,
+                        budget,
+                    )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::Media { query, body } => {
@@ -363,10 +386,17 @@ impl Css {
                     }
 
                     writeln!(buf, "{}@media {} {{", padding, query)?;
-                    Css::from_stmts(body, true)?._inner_pretty_print(buf, map, nesting + 1)?;
+                    Css::from_stmts(body, true)?._inner_pretty_print(
+                        buf,
+                        map,
+                        nesting + 1,
+                        options,
+                        budget,
+                    )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::Style(s) => {
+                    budget.check_timeout(options, s.value.span)?;
                     writeln!(buf, "{}{}", padding, s.to_string()?)?;
                 }
                 Toplevel::Newline => {
@@ -376,7 +406,16 @@ impl Css {
                     continue;
                 }
             }
+            check_output_size(buf, options)?;
         }
         Ok(())
     }
 }
+
+/// Errors once the CSS written so far exceeds `Options::max_output_size`
+fn check_output_size(css: &[u8], options: &Options) -> SassResult<()> {
+    match options.max_output_size {
+        Some(max) if css.len() > max => Err(Box::new(SassError::output_too_large(max))),
+        _ => Ok(()),
+    }
+}
@@ -1,13 +1,16 @@
-use std::{collections::HashMap, mem};
+use std::mem;
 
 use codemap::{Span, Spanned};
 
+use indexmap::IndexMap;
+
 use crate::{
-    args::{CallArg, CallArgs, FuncArg, FuncArgs},
+    args::{CallArg, CallArgs, FuncArg, FuncArgs, UnevaluatedCallArg, UnevaluatedCallArgs},
+    common::Identifier,
     error::SassResult,
     scope::Scope,
     utils::{read_until_closing_paren, read_until_closing_quote, read_until_closing_square_brace},
-    value::Value,
+    value::{SassArgList, Value},
     Token,
 };
 
@@ -125,7 +128,12 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_call_args(&mut self) -> SassResult<CallArgs> {
-        let mut args = HashMap::new();
+        let args = self.parse_unevaluated_call_args()?;
+        self.eval_call_args(args)
+    }
+
+    pub(super) fn parse_unevaluated_call_args(&mut self) -> SassResult<UnevaluatedCallArgs> {
+        let mut args = Vec::new();
         self.whitespace_or_comment();
         let mut name = String::new();
         let mut val: Vec<Token> = Vec::new();
@@ -161,7 +169,7 @@ impl<'a> Parser<'a> {
                 }
                 Some(Token { kind: ')', .. }) => {
                     self.toks.next();
-                    return Ok(CallArgs(args, span));
+                    return Ok(UnevaluatedCallArgs(args, span));
                 }
                 Some(..) | None => name.clear(),
             }
@@ -172,16 +180,13 @@ impl<'a> Parser<'a> {
             while let Some(tok) = self.toks.next() {
                 match tok.kind {
                     ')' => {
-                        args.insert(
-                            if name.is_empty() {
-                                CallArg::Positional(args.len())
-                            } else {
-                                CallArg::Named(mem::take(&mut name).into())
-                            },
-                            self.parse_value_from_vec(val, true),
-                        );
+                        args.push(UnevaluatedCallArg {
+                            name: unevaluated_arg_name(&mut name),
+                            toks: val,
+                            is_splat: false,
+                        });
                         span = span.merge(tok.pos());
-                        return Ok(CallArgs(args, span));
+                        return Ok(UnevaluatedCallArgs(args, span));
                     }
                     ',' => break,
                     '[' => {
@@ -217,84 +222,137 @@ impl<'a> Parser<'a> {
                 }
             }
 
-            if is_splat {
-                let val = self.parse_value_from_vec(mem::take(&mut val), true)?;
-                match val.node {
-                    Value::ArgList(v) => {
-                        for arg in v {
-                            args.insert(CallArg::Positional(args.len()), Ok(arg));
-                        }
-                    }
-                    Value::List(v, ..) => {
-                        for arg in v {
-                            args.insert(CallArg::Positional(args.len()), Ok(arg.span(val.span)));
-                        }
+            args.push(UnevaluatedCallArg {
+                name: if is_splat {
+                    None
+                } else {
+                    unevaluated_arg_name(&mut name)
+                },
+                toks: mem::take(&mut val),
+                is_splat,
+            });
+
+            self.whitespace();
+
+            if self.toks.peek().is_none() {
+                return Err(("expected \")\".", span).into());
+            }
+        }
+    }
+
+    /// Evaluate the arguments to a function call, in the order they were written
+    pub(crate) fn eval_call_args(
+        &mut self,
+        unevaluated: UnevaluatedCallArgs,
+    ) -> SassResult<CallArgs> {
+        let UnevaluatedCallArgs(unevaluated, span) = unevaluated;
+        let mut args = IndexMap::new();
+
+        for arg in unevaluated {
+            if !arg.is_splat {
+                args.insert(
+                    match arg.name {
+                        Some(name) => CallArg::Named(name),
+                        None => CallArg::Positional(args.len()),
+                    },
+                    self.parse_value_from_vec(arg.toks, true),
+                );
+                continue;
+            }
+
+            let val = self.parse_value_from_vec(arg.toks, true)?;
+            match val.node {
+                Value::ArgList(v) => {
+                    // passing on the keywords counts as accessing them
+                    let keywords = v.keywords();
+                    for arg in v.elems {
+                        args.insert(CallArg::Positional(args.len()), Ok(arg));
                     }
-                    Value::Map(v) => {
-                        // NOTE: we clone the map here because it is used
-                        // later for error reporting. perhaps there is
-                        // some way around this?
-                        for (name, arg) in v.clone().entries() {
-                            let name = match name {
-                                Value::String(s, ..) => s,
-                                _ => {
-                                    return Err((
-                                        format!(
-                                            "{} is not a string in {}.",
-                                            name.inspect(val.span)?,
-                                            Value::Map(v).inspect(val.span)?
-                                        ),
-                                        val.span,
-                                    )
-                                        .into())
-                                }
-                            };
+                    for (name, arg) in keywords {
+                        if let Value::String(name, ..) = name {
                             args.insert(CallArg::Named(name.into()), Ok(arg.span(val.span)));
                         }
                     }
-                    _ => {
-                        args.insert(CallArg::Positional(args.len()), Ok(val));
+                }
+                Value::List(v, ..) => {
+                    for arg in v.iter().cloned() {
+                        args.insert(CallArg::Positional(args.len()), Ok(arg.span(val.span)));
                     }
                 }
-            } else {
-                args.insert(
-                    if name.is_empty() {
-                        CallArg::Positional(args.len())
-                    } else {
-                        CallArg::Named(mem::take(&mut name).into())
-                    },
-                    self.parse_value_from_vec(mem::take(&mut val), true),
-                );
+                Value::Map(v) => {
+                    // NOTE: we clone the map here because it is used
+                    // later for error reporting. perhaps there is
+                    // some way around this?
+                    for (name, arg) in v.clone().entries() {
+                        let name = match name {
+                            Value::String(s, ..) => s,
+                            _ => {
+                                return Err((
+                                    format!(
+                                        "{} is not a string in {}.",
+                                        name.inspect(val.span)?,
+                                        Value::Map(v).inspect(val.span)?
+                                    ),
+                                    val.span,
+                                )
+                                    .into())
+                            }
+                        };
+                        args.insert(CallArg::Named(name.into()), Ok(arg.span(val.span)));
+                    }
+                }
+                _ => {
+                    args.insert(CallArg::Positional(args.len()), Ok(val));
+                }
             }
+        }
 
-            self.whitespace();
+        Ok(CallArgs::from_args(args, span))
+    }
+}
 
-            if self.toks.peek().is_none() {
-                return Err(("expected \")\".", span).into());
-            }
-        }
+fn unevaluated_arg_name(name: &mut String) -> Option<Identifier> {
+    if name.is_empty() {
+        None
+    } else {
+        Some(mem::take(name).into())
     }
 }
 
 impl<'a> Parser<'a> {
-    pub(super) fn eval_args(&mut self, fn_args: FuncArgs, mut args: CallArgs) -> SassResult<Scope> {
+    /// Bind the arguments of a call to the parameters of a function or mixin,
+    /// returning the value of the variadic parameter if there is one
+    ///
+    /// Once the body has been evaluated, `SassArgList::check_keywords_accessed`
+    /// should be called on the variadic parameter
+    pub(super) fn eval_args(
+        &mut self,
+        fn_args: FuncArgs,
+        mut args: CallArgs,
+    ) -> SassResult<(Scope, Option<SassArgList>)> {
         let mut scope = Scope::new();
         if fn_args.0.is_empty() {
             args.max_args(0)?;
-            return Ok(scope);
+            return Ok((scope, None));
+        }
+        if !fn_args.0.iter().any(|arg| arg.is_variadic) {
+            let params: Vec<Identifier> = fn_args.0.iter().map(|arg| arg.name).collect();
+            args.check_named(&params)?;
         }
+        let mut arg_list = None;
         self.scopes.enter_new_scope();
         for (idx, mut arg) in fn_args.0.into_iter().enumerate() {
             if arg.is_variadic {
                 let span = args.span();
-                let arg_list = Value::ArgList(args.get_variadic()?);
+                let list = args.get_arg_list()?;
                 scope.insert_var(
                     arg.name,
                     Spanned {
-                        node: arg_list,
+                        node: Value::ArgList(list.clone()),
                         span,
                     },
                 );
+                arg_list = Some(list);
                 break;
             }
             let val = match args.get(idx, arg.name) {
@@ -312,6 +370,6 @@ impl<'a> Parser<'a> {
             scope.insert_var(arg.name, val);
         }
         self.scopes.exit_scope();
-        Ok(scope)
+        Ok((scope, arg_list))
     }
 }
@@ -0,0 +1,207 @@
+//! Limits on the amount of work done to compile an untrusted stylesheet
+
+use std::time::Instant;
+
+use codemap::Span;
+
+use crate::{error::SassResult, value::Value, Options};
+
+use super::Parser;
+
+/// The number of bytes of a string, or elements of a list or map, which
+/// count as a single step of evaluation when the value is built
+const SIZE_PER_STEP: usize = 1024;
+
+/// The work done so far, shared by every `Parser` involved in a compilation
+#[derive(Debug)]
+pub(crate) struct Budget {
+    steps: usize,
+    /// When compilation started, if `Options::timeout` is set
+    ///
+    /// This is only read when a timeout is configured, as `Instant::now`
+    /// is not available on every platform (e.g. `wasm32-unknown-unknown`)
+    start: Option<Instant>,
+    /// The size of the values of the styles emitted so far
+    output_size: usize,
+    exhausted: bool,
+}
+
+impl Budget {
+    pub fn new(options: &Options) -> Self {
+        Budget {
+            steps: 0,
+            start: options.timeout.map(|_| Instant::now()),
+            output_size: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Whether `Options::max_steps` or `Options::timeout` has been exceeded,
+    /// in which case compilation must stop even if errors are being
+    /// recovered from
+    pub const fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Errors once `Options::timeout` is exceeded
+    pub fn check_timeout(&mut self, options: &Options, span: Span) -> SassResult<()> {
+        if let (Some(timeout), Some(start)) = (options.timeout, self.start) {
+            if start.elapsed() > timeout {
+                self.exhausted = true;
+                return Err(("Maximum compilation time exceeded.", span).into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Records a single step of evaluation: a block, a call to a `@function`
+    /// or `@mixin`, or an iteration of a loop
+    ///
+    /// This errors once `Options::max_steps` or `Options::timeout` is
+    /// exceeded, so that e.g. an infinite `@while` loop terminates
+    pub fn step(&mut self, span: Span) -> SassResult<()> {
+        self.budget.steps += 1;
+        self.check_budget(span)
+    }
+
+    /// Records the evaluation of an expression or call to `value`
+    ///
+    /// Repeatedly combining a value with itself (e.g. `$s: $s + $s` or
+    /// `$l: $l $l` in a loop) doubles its size at every step, so larger values
+    /// are charged more steps, and a value may not be larger than
+    /// `Options::max_output_size`
+    pub fn charge(&mut self, value: &Value, span: Span) -> SassResult<()> {
+        if self.options.max_steps.is_none()
+            && self.options.timeout.is_none()
+            && self.options.max_output_size.is_none()
+        {
+            return Ok(());
+        }
+
+        let size = self.measure(value, span)?;
+
+        if let Some(max_output_size) = self.options.max_output_size {
+            if size > max_output_size {
+                self.budget.exhausted = true;
+                return Err(("Maximum value size exceeded.", span).into());
+            }
+        }
+
+        self.budget.steps += size / SIZE_PER_STEP;
+        self.check_budget(span)
+    }
+
+    /// Records the emission of a style with the value `value`
+    ///
+    /// The CSS is only written once the whole stylesheet has been evaluated,
+    /// so the size of emitted values is counted towards
+    /// `Options::max_output_size` as they're evaluated, rather than building
+    /// up enormous output only to reject it
+    pub fn emit(&mut self, value: &Value, span: Span) -> SassResult<()> {
+        let max_output_size = match self.options.max_output_size {
+            Some(max_output_size) => max_output_size,
+            None => return Ok(()),
+        };
+
+        let size = self.measure(value, span)?;
+        self.budget.output_size = self.budget.output_size.saturating_add(size);
+
+        if self.budget.output_size > max_output_size {
+            self.budget.exhausted = true;
+            return Err((
+                format!(
+                    "Output exceeds the maximum size of {} bytes.",
+                    max_output_size
+                ),
+                span,
+            )
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// The size of `value`, counting the bytes of strings and the elements of
+    /// lists and maps, including those nested inside of it
+    ///
+    /// Nested values may be shared, so a value can be exponentially larger
+    /// than the memory it uses. Measuring stops as soon as the size is known
+    /// to exceed the remaining budget, and the timeout is checked along the
+    /// way, so this takes time proportional to the steps that are charged
+    fn measure(&mut self, value: &Value, span: Span) -> SassResult<usize> {
+        /// The number of values measured between checks of the timeout
+        const VALUES_PER_TIMEOUT_CHECK: usize = 4096;
+
+        let remaining_steps = self
+            .options
+            .max_steps
+            .map(|max_steps| max_steps.saturating_sub(self.budget.steps));
+        let limit = match (self.options.max_output_size, remaining_steps) {
+            (Some(max_output_size), Some(steps)) => {
+                max_output_size.min(steps.saturating_mul(SIZE_PER_STEP))
+            }
+            (Some(max_output_size), None) => max_output_size,
+            (None, Some(steps)) => steps.saturating_mul(SIZE_PER_STEP),
+            (None, None) => usize::MAX,
+        };
+
+        let mut size = 0_usize;
+        let mut measured = 0_usize;
+        let mut pending = vec![value];
+        while let Some(value) = pending.pop() {
+            match value {
+                Value::String(s, ..) => size = size.saturating_add(s.len()),
+                Value::List(elems, ..) => pending.extend(elems.iter()),
+                Value::Map(map) => {
+                    for (key, value) in map.iter() {
+                        pending.push(key);
+                        pending.push(value);
+                    }
+                }
+                Value::ArgList(args) => pending.extend(args.elems.iter().map(|arg| &arg.node)),
+                _ => size = size.saturating_add(1),
+            }
+
+            if size > limit {
+                // the value is too large; the budget is checked by the caller
+                return Ok(size);
+            }
+
+            measured += 1;
+            if measured % VALUES_PER_TIMEOUT_CHECK == 0 {
+                self.budget.check_timeout(self.options, span)?;
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Errors once `Options::max_steps` or `Options::timeout` is exceeded
+    fn check_budget(&mut self, span: Span) -> SassResult<()> {
+        if let Some(max_steps) = self.options.max_steps {
+            if self.budget.steps > max_steps {
+                self.budget.exhausted = true;
+                return Err(("Maximum number of evaluation steps exceeded.", span).into());
+            }
+        }
+
+        self.budget.check_timeout(self.options, span)
+    }
+
+    /// Whether `@import`s may load the file at the canonicalized `path`
+    ///
+    /// If `Options::allow_imports_from` was never called, every file may be
+    /// imported; otherwise the file must be within one of the given directories
+    pub fn may_import(&self, path: &std::path::Path) -> bool {
+        self.options.import_roots.is_empty()
+            || self
+                .options
+                .import_roots
+                .iter()
+                .filter_map(|root| root.canonicalize().ok())
+                .any(|root| path.starts_with(root))
+    }
+}
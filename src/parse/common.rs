@@ -33,6 +33,15 @@ impl<T> NeverEmptyVec<T> {
     pub fn is_empty(&self) -> bool {
         self.rest.is_empty()
     }
+
+    pub fn len(&self) -> usize {
+        self.rest.len() + 1
+    }
+
+    /// Removes every element after the first `len`, though never the first
+    pub fn truncate(&mut self, len: usize) {
+        self.rest.truncate(len.saturating_sub(1));
+    }
 }
 
 /// A toplevel element beginning with something other than
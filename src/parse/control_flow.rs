@@ -4,6 +4,7 @@ use peekmore::PeekMore;
 
 use crate::{
     common::Identifier,
+    deprecation::Deprecation,
     error::SassResult,
     parse::{ContextFlags, Parser, Stmt},
     unit::Unit,
@@ -51,7 +52,15 @@ impl<'a> Parser<'a> {
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
+                options: self.options,
                 content_scopes: self.content_scopes,
+                depth: self.depth,
+                import_stack: self.import_stack,
+                budget: self.budget,
+                call_stack: self.call_stack,
+                deprecations: self.deprecations,
+                errors: self.errors,
+                dependencies: self.dependencies,
             }
             .parse_stmt()?;
         } else {
@@ -59,28 +68,44 @@ impl<'a> Parser<'a> {
         }
 
         loop {
+            let is_elseif;
             self.whitespace_or_comment();
             if let Some(Token { kind: '@', pos }) = self.toks.peek().cloned() {
                 self.toks.peek_forward(1);
                 let ident = peek_ident_no_interpolation(self.toks, false, pos)?;
-                if ident.as_str() != "else" {
-                    self.toks.reset_cursor();
-                    break;
-                }
+                is_elseif = match ident.as_str() {
+                    "else" => false,
+                    "elseif" => true,
+                    _ => {
+                        self.toks.reset_cursor();
+                        break;
+                    }
+                };
                 self.toks.truncate_iterator_to_cursor();
+                if is_elseif {
+                    self.warn_deprecation(
+                        Deprecation::Elseif,
+                        "@elseif is deprecated and will not be supported in future Sass versions.\n\nRecommendation: @else if",
+                        ident.span,
+                    )?;
+                }
             } else {
                 break;
             }
             self.whitespace_or_comment();
             if let Some(tok) = self.toks.peek().cloned() {
                 match tok.kind {
-                    'i' if matches!(
-                        self.toks.peek_forward(1),
-                        Some(Token { kind: 'f', .. }) | Some(Token { kind: 'F', .. })
-                    ) =>
+                    _ if is_elseif
+                        || (tok.kind == 'i'
+                            && matches!(
+                                self.toks.peek_forward(1),
+                                Some(Token { kind: 'f', .. }) | Some(Token { kind: 'F', .. })
+                            )) =>
                     {
-                        self.toks.next();
-                        self.toks.next();
+                        if !is_elseif {
+                            self.toks.next();
+                            self.toks.next();
+                        }
                         let cond = if found_true {
                             self.throw_away_until_open_curly_brace()?;
                             false
@@ -109,7 +134,15 @@ impl<'a> Parser<'a> {
                                 at_root: self.at_root,
                                 at_root_has_selector: self.at_root_has_selector,
                                 extender: self.extender,
+                                options: self.options,
                                 content_scopes: self.content_scopes,
+                                depth: self.depth,
+                                import_stack: self.import_stack,
+                                budget: self.budget,
+                                call_stack: self.call_stack,
+                                deprecations: self.deprecations,
+                                errors: self.errors,
+                                dependencies: self.dependencies,
                             }
                             .parse_stmt()?;
                         } else {
@@ -136,7 +169,15 @@ impl<'a> Parser<'a> {
                                 at_root: self.at_root,
                                 at_root_has_selector: self.at_root_has_selector,
                                 extender: self.extender,
+                                options: self.options,
                                 content_scopes: self.content_scopes,
+                                depth: self.depth,
+                                import_stack: self.import_stack,
+                                budget: self.budget,
+                                call_stack: self.call_stack,
+                                deprecations: self.deprecations,
+                                errors: self.errors,
+                                dependencies: self.dependencies,
                             }
                             .parse_stmt();
                         }
@@ -294,6 +335,7 @@ impl<'a> Parser<'a> {
         self.scopes.enter_new_scope();
 
         for i in iter {
+            self.step(var.span)?;
             self.scopes.insert_var_last(
                 var.node,
                 Spanned {
@@ -315,7 +357,15 @@ impl<'a> Parser<'a> {
                     at_root: self.at_root,
                     at_root_has_selector: self.at_root_has_selector,
                     extender: self.extender,
+                    options: self.options,
                     content_scopes: self.content_scopes,
+                    depth: self.depth,
+                    import_stack: self.import_stack,
+                    budget: self.budget,
+                    call_stack: self.call_stack,
+                    deprecations: self.deprecations,
+                    errors: self.errors,
+                    dependencies: self.dependencies,
                 }
                 .parse()?;
                 if !these_stmts.is_empty() {
@@ -336,7 +386,15 @@ impl<'a> Parser<'a> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        options: self.options,
                         content_scopes: self.content_scopes,
+                        depth: self.depth,
+                        import_stack: self.import_stack,
+                        budget: self.budget,
+                        call_stack: self.call_stack,
+                        deprecations: self.deprecations,
+                        errors: self.errors,
+                        dependencies: self.dependencies,
                     }
                     .parse()?,
                 );
@@ -371,6 +429,7 @@ impl<'a> Parser<'a> {
         let mut val = self.parse_value_from_vec(cond.clone(), true)?;
         self.scopes.enter_new_scope();
         while val.node.is_true() {
+            self.step(val.span)?;
             if self.flags.in_function() {
                 let these_stmts = Parser {
                     toks: &mut body.clone().into_iter().peekmore(),
@@ -385,7 +444,15 @@ impl<'a> Parser<'a> {
                     at_root: self.at_root,
                     at_root_has_selector: self.at_root_has_selector,
                     extender: self.extender,
+                    options: self.options,
                     content_scopes: self.content_scopes,
+                    depth: self.depth,
+                    import_stack: self.import_stack,
+                    budget: self.budget,
+                    call_stack: self.call_stack,
+                    deprecations: self.deprecations,
+                    errors: self.errors,
+                    dependencies: self.dependencies,
                 }
                 .parse()?;
                 if !these_stmts.is_empty() {
@@ -406,7 +473,15 @@ impl<'a> Parser<'a> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        options: self.options,
                         content_scopes: self.content_scopes,
+                        depth: self.depth,
+                        import_stack: self.import_stack,
+                        budget: self.budget,
+                        call_stack: self.call_stack,
+                        deprecations: self.deprecations,
+                        errors: self.errors,
+                        dependencies: self.dependencies,
                     }
                     .parse()?,
                 );
@@ -470,6 +545,7 @@ impl<'a> Parser<'a> {
         self.scopes.enter_new_scope();
 
         for row in iter {
+            self.step(vars[0].span)?;
             if vars.len() == 1 {
                 self.scopes.insert_var_last(
                     vars[0].node,
@@ -508,7 +584,15 @@ impl<'a> Parser<'a> {
                     at_root: self.at_root,
                     at_root_has_selector: self.at_root_has_selector,
                     extender: self.extender,
+                    options: self.options,
                     content_scopes: self.content_scopes,
+                    depth: self.depth,
+                    import_stack: self.import_stack,
+                    budget: self.budget,
+                    call_stack: self.call_stack,
+                    deprecations: self.deprecations,
+                    errors: self.errors,
+                    dependencies: self.dependencies,
                 }
                 .parse()?;
                 if !these_stmts.is_empty() {
@@ -529,7 +613,15 @@ impl<'a> Parser<'a> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        options: self.options,
                         content_scopes: self.content_scopes,
+                        depth: self.depth,
+                        import_stack: self.import_stack,
+                        budget: self.budget,
+                        call_stack: self.call_stack,
+                        deprecations: self.deprecations,
+                        errors: self.errors,
+                        dependencies: self.dependencies,
                     }
                     .parse()?,
                 );
@@ -4,9 +4,9 @@ use peekmore::PeekMore;
 use crate::{
     args::CallArgs,
     atrule::Function,
-    common::unvendor,
+    common::{unvendor, Identifier},
     error::SassResult,
-    scope::Scopes,
+    scope::{CallKey, Scopes},
     utils::{read_until_closing_curly_brace, read_until_semicolon_or_closing_curly_brace},
     value::Value,
     Token,
@@ -70,7 +70,13 @@ impl<'a> Parser<'a> {
         Ok(Box::new(v.node))
     }
 
-    pub fn eval_function(&mut self, function: Function, args: CallArgs) -> SassResult<Value> {
+    pub fn eval_function(
+        &mut self,
+        function: Function,
+        name: Identifier,
+        args: CallArgs,
+    ) -> SassResult<Value> {
+        let pos = function.pos();
         let Function {
             body,
             args: fn_args,
@@ -78,7 +84,27 @@ impl<'a> Parser<'a> {
             ..
         } = function;
 
-        let scope = self.eval_args(fn_args, args)?;
+        let generation = self.global_scope.function_cache().generation();
+
+        let span = args.span();
+        let (scope, arg_list) = self.eval_args(fn_args, args)?;
+
+        // functions declared at the root only depend on their arguments and
+        // the global scope, so their results can be memoized
+        let key = if declared_at_root {
+            let key = CallKey::new(pos, &scope);
+            if let Some(value) = self.global_scope.function_cache().get(&key) {
+                return Ok(value);
+            }
+            Some(key)
+        } else {
+            // functions declared elsewhere can see local variables, so neither
+            // they nor any function calling them through `call()` are memoized
+            self.global_scope.function_cache().taint();
+            None
+        };
+
+        self.enter_frame(format!("{}()", name), span)?;
 
         let mut new_scope = Scopes::new();
         let mut entered_scope = false;
@@ -89,7 +115,7 @@ impl<'a> Parser<'a> {
             self.scopes.enter_scope(scope);
         };
 
-        let mut return_value = Parser {
+        let return_value = Parser {
             toks: &mut body.into_iter().peekmore(),
             map: self.map,
             path: self.path,
@@ -106,21 +132,46 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: 0,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
-        .parse()?;
+        .parse();
+
+        let mut return_value = self.exit_frame(return_value)?;
 
         if entered_scope {
             self.scopes.exit_scope();
         }
 
-        debug_assert!(return_value.len() <= 1);
-        match return_value
-            .pop()
-            .ok_or(("Function finished without @return.", self.span_before))?
-        {
-            Stmt::Return(v) => Ok(*v),
-            _ => todo!("should be unreachable"),
+        let value = match return_value.pop() {
+            Some(Stmt::Return(v)) if return_value.is_empty() => *v,
+            Some(..) => {
+                return Err((
+                    "Functions can only contain variable declarations and control directives.",
+                    self.span_before,
+                )
+                    .into())
+            }
+            None => return Err(("Function finished without @return.", self.span_before).into()),
+        };
+
+        if let Some(arg_list) = arg_list {
+            arg_list.check_keywords_accessed(span)?;
         }
+
+        if let Some(key) = key {
+            self.global_scope
+                .function_cache()
+                .insert(key, value.clone(), generation);
+        }
+
+        Ok(value)
     }
 }
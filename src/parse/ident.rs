@@ -5,7 +5,7 @@ use codemap::Spanned;
 use crate::{
     common::QuoteKind,
     error::SassResult,
-    utils::{as_hex, hex_char_for, is_name, is_name_start},
+    utils::{hex_char_for, is_name, is_name_start},
     value::Value,
     Token,
 };
@@ -91,12 +91,14 @@ impl<'a> Parser<'a> {
                     Some(t) => t,
                     None => break,
                 };
-                if !next.kind.is_ascii_hexdigit() {
-                    break;
-                }
+                let digit = match next.kind.to_digit(16) {
+                    Some(digit) => digit,
+                    None => break,
+                };
                 value *= 16;
                 span = span.merge(next.pos());
-                value += as_hex(self.toks.next().unwrap().kind)
+                value += digit;
+                self.toks.next();
             }
             if self.toks.peek().is_some() && self.toks.peek().unwrap().kind.is_whitespace() {
                 self.toks.next();
@@ -246,13 +248,13 @@ impl<'a> Parser<'a> {
             match tok.kind {
                 '"' if q == '"' => {
                     return Ok(Spanned {
-                        node: Value::String(s, QuoteKind::Quoted),
+                        node: Value::String(s.into(), QuoteKind::Quoted),
                         span,
                     })
                 }
                 '\'' if q == '\'' => {
                     return Ok(Spanned {
-                        node: Value::String(s, QuoteKind::Quoted),
+                        node: Value::String(s.into(), QuoteKind::Quoted),
                         span,
                     })
                 }
@@ -293,10 +295,12 @@ impl<'a> Parser<'a> {
                                 Some(c) => c,
                                 None => break,
                             };
-                            if !next.kind.is_ascii_hexdigit() {
-                                break;
-                            }
-                            value = (value << 4) + as_hex(self.toks.next().unwrap().kind);
+                            let digit = match next.kind.to_digit(16) {
+                                Some(digit) => digit,
+                                None => break,
+                            };
+                            value = (value << 4) + digit;
+                            self.toks.next();
                         }
 
                         if self.toks.peek().is_some()
@@ -1,9 +1,16 @@
-use std::{ffi::OsStr, fs, path::Path};
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
 
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 use peekmore::PeekMore;
 
-use crate::{common::QuoteKind, error::SassResult, lexer::Lexer, value::Value, Token};
+use crate::{
+    common::QuoteKind, deprecation::Deprecation, error::SassResult, lexer::Lexer, value::Value,
+    Token,
+};
 
 use super::{Parser, Stmt};
 
@@ -33,7 +40,7 @@ impl<'a> Parser<'a> {
             }
             Value::String(s, QuoteKind::None) => {
                 if s.starts_with("url(") {
-                    return Ok(vec![Stmt::Import(s)]);
+                    return Ok(vec![Stmt::Import(s.to_string())]);
                 } else {
                     s
                 }
@@ -41,9 +48,15 @@ impl<'a> Parser<'a> {
             _ => return Err(("Expected string.", span).into()),
         };
 
+        self.warn_deprecation(
+            Deprecation::Import,
+            "Sass @import rules are deprecated and will be removed in Dart Sass 3.0.0.\n\nMore info and automated migrator: https://sass-lang.com/d/import",
+            span,
+        )?;
+
         self.whitespace();
 
-        let path: &Path = file_name.as_ref();
+        let path = Path::new(&*file_name);
 
         let path_buf = if path.is_absolute() {
             // todo: test for absolute path imports
@@ -55,26 +68,51 @@ impl<'a> Parser<'a> {
                 .join(path)
         };
 
-        let name = path_buf.file_name().unwrap_or_else(|| OsStr::new(".."));
+        // files are looked for relative to the importing file, then in each
+        // of the load paths
+        let mut bases = vec![(path_buf, false)];
+        if !path.is_absolute() {
+            bases.extend(
+                self.options
+                    .load_paths
+                    .iter()
+                    .map(|load_path| (load_path.join(path), true)),
+            );
+        }
+
+        for (path_buf, from_load_path) in bases {
+            for name in &import_candidates(&path_buf) {
+                if !name.is_file() {
+                    continue;
+                }
+
+                let canonical = fs::canonicalize(name)?;
 
-        let paths = [
-            path_buf.with_file_name(name).with_extension("scss"),
-            path_buf
-                .with_file_name(format!("_{}", name.to_str().unwrap()))
-                .with_extension("scss"),
-            path_buf.clone(),
-            path_buf.join("index.scss"),
-            path_buf.join("_index.scss"),
-        ];
+                if !self.may_import(&canonical) {
+                    continue;
+                }
+
+                if let Some(idx) = self
+                    .import_stack
+                    .iter()
+                    .position(|(path, ..)| *path == canonical)
+                {
+                    return Err((self.import_cycle_message(idx, span), span).into());
+                }
 
-        for name in &paths {
-            if name.is_file() {
                 let file = self.map.add_file(
                     name.to_string_lossy().into(),
                     String::from_utf8(fs::read(name)?)?,
                 );
 
-                return Parser {
+                if from_load_path || self.is_dependency(span) {
+                    self.dependencies.push(file.span);
+                }
+
+                self.import_stack.push((canonical, span));
+                self.call_stack.push(("@import".to_owned(), span));
+
+                let stmts = Parser {
                     toks: &mut Lexer::new(&file)
                         .collect::<Vec<Token>>()
                         .into_iter()
@@ -90,12 +128,77 @@ impl<'a> Parser<'a> {
                     at_root: self.at_root,
                     at_root_has_selector: self.at_root_has_selector,
                     extender: self.extender,
+                    options: self.options,
                     content_scopes: self.content_scopes,
+                    depth: self.depth,
+                    import_stack: self.import_stack,
+                    budget: self.budget,
+                    call_stack: self.call_stack,
+                    deprecations: self.deprecations,
+                    errors: self.errors,
+                    dependencies: self.dependencies,
                 }
                 .parse();
+
+                self.import_stack.pop();
+
+                return self.exit_frame(stmts);
             }
         }
 
         Err(("Can't find stylesheet to import.", span).into())
     }
+
+    /// Describes the chain of imports leading back to the file at
+    /// `self.import_stack[idx]`, which `span` is attempting to import again
+    ///
+    /// Each line is the location of an `@import` in the chain, followed by
+    /// the file it loads, e.g.
+    ///
+    /// ```text
+    /// This file is already being loaded.
+    ///   a.scss 1:9  imports b.scss
+    ///   b.scss 1:9  imports a.scss
+    /// ```
+    fn import_cycle_message(&self, idx: usize, span: Span) -> String {
+        let importers: Vec<(String, usize, usize)> = self.import_stack[idx + 1..]
+            .iter()
+            .map(|(_, span)| *span)
+            .chain(std::iter::once(span))
+            .map(|span| {
+                let loc = self.map.look_up_span(span);
+                (
+                    loc.file.name().to_owned(),
+                    loc.begin.line + 1,
+                    loc.begin.column + 1,
+                )
+            })
+            .collect();
+
+        let mut message = String::from("This file is already being loaded.");
+
+        for (i, (file, line, col)) in importers.iter().enumerate() {
+            let imported = &importers[(i + 1) % importers.len()].0;
+            message.push_str(&format!(
+                "\n  {} {}:{}  imports {}",
+                file, line, col, imported
+            ));
+        }
+
+        message
+    }
+}
+
+/// The files which an `@import` of `path` may refer to, in order of precedence
+fn import_candidates(path: &Path) -> [PathBuf; 5] {
+    let name = path.file_name().unwrap_or_else(|| OsStr::new(".."));
+
+    [
+        path.with_file_name(name).with_extension("scss"),
+        path.with_file_name(format!("_{}", name.to_str().unwrap()))
+            .with_extension("scss"),
+        path.to_path_buf(),
+        path.join("index.scss"),
+        path.join("_index.scss"),
+    ]
 }
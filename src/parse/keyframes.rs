@@ -36,22 +36,13 @@ impl<'a, 'b> KeyframesSelectorParser<'a, 'b> {
         self.parser.whitespace_or_comment();
         while let Some(tok) = self.parser.toks.peek().cloned() {
             match tok.kind {
-                't' | 'T' => {
+                't' | 'T' | 'f' | 'F' | '\\' => {
                     let mut ident = self.parser.parse_identifier()?;
                     ident.node.make_ascii_lowercase();
-                    if ident.node == "to" {
-                        selectors.push(KeyframesSelector::To)
-                    } else {
-                        return Err(("Expected \"to\" or \"from\".", tok.pos).into());
-                    }
-                }
-                'f' | 'F' => {
-                    let mut ident = self.parser.parse_identifier()?;
-                    ident.node.make_ascii_lowercase();
-                    if ident.node == "from" {
-                        selectors.push(KeyframesSelector::From)
-                    } else {
-                        return Err(("Expected \"to\" or \"from\".", tok.pos).into());
+                    match ident.node.as_str() {
+                        "to" => selectors.push(KeyframesSelector::To),
+                        "from" => selectors.push(KeyframesSelector::From),
+                        _ => return Err(("Expected \"to\" or \"from\".", tok.pos).into()),
                     }
                 }
                 '0'..='9' => {
@@ -62,7 +53,6 @@ impl<'a, 'b> KeyframesSelectorParser<'a, 'b> {
                     selectors.push(KeyframesSelector::Percent(num.into_boxed_str()));
                 }
                 '{' => break,
-                '\\' => todo!("escaped chars in @keyframes selector"),
                 _ => return Err(("Expected \"to\" or \"from\".", tok.pos).into()),
             }
             self.parser.whitespace_or_comment();
@@ -164,7 +154,15 @@ impl<'a> Parser<'a> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        options: self.options,
                         content_scopes: self.content_scopes,
+                        depth: self.depth,
+                        import_stack: self.import_stack,
+                        budget: self.budget,
+                        call_stack: self.call_stack,
+                        deprecations: self.deprecations,
+                        errors: self.errors,
+                        dependencies: self.dependencies,
                     })
                     .parse_keyframes_selector()?;
 
@@ -194,7 +192,15 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: self.depth,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
         .parse_stmt()?;
 
@@ -14,7 +14,10 @@ impl<'a> Parser<'a> {
         let peeked_identifier =
             match peek_ident_no_interpolation(self.toks, false, self.span_before) {
                 Ok(v) => v.node,
-                Err(..) => return Ok(false),
+                Err(..) => {
+                    self.toks.reset_cursor();
+                    return Ok(false);
+                }
             };
         if peeked_identifier == ident {
             self.toks.truncate_iterator_to_cursor();
@@ -45,36 +48,86 @@ impl<'a> Parser<'a> {
         false
     }
 
+    /// Whether the next tokens could begin an identifier, possibly
+    /// one that starts with interpolation
+    fn looking_at_interpolated_identifier(&mut self) -> bool {
+        let first = match self.toks.peek() {
+            Some(Token { kind, .. }) => *kind,
+            None => return false,
+        };
+
+        let second = self.toks.peek_forward(1).map(|t| t.kind);
+        self.toks.reset_cursor();
+
+        match first {
+            '\\' => true,
+            '#' => second == Some('{'),
+            '-' => match second {
+                Some('#') | Some('\\') | Some('-') => true,
+                Some(c) => is_name_start(c),
+                None => false,
+            },
+            c => is_name_start(c),
+        }
+    }
+
+    /// Consume a SassScript expression, stopping before a `<`, `>`, single `=`,
+    /// `:`, or unmatched `)`, and return it evaluated as a string
     pub fn expression_until_comparison(&mut self) -> SassResult<Cow<'static, str>> {
         let mut toks = Vec::new();
+        let mut nesting = 0;
         while let Some(tok) = self.toks.peek().cloned() {
             match tok.kind {
                 '=' => {
-                    self.toks.advance_cursor();
-                    if matches!(self.toks.peek(), Some(Token { kind: '=', .. })) {
+                    if nesting == 0
+                        && !matches!(self.toks.peek_forward(1), Some(Token { kind: '=', .. }))
+                    {
                         self.toks.reset_cursor();
                         break;
                     }
                     self.toks.reset_cursor();
                     toks.push(tok);
-                    toks.push(tok);
                     self.toks.next();
+                    if let Some(tok @ Token { kind: '=', .. }) = self.toks.peek().cloned() {
+                        toks.push(tok);
+                        self.toks.next();
+                    }
+                }
+                '>' | '<' | ':' if nesting == 0 => break,
+                ')' if nesting == 0 => break,
+                '(' | '{' | '[' => {
+                    nesting += 1;
+                    toks.push(tok);
                     self.toks.next();
                 }
-                '>' | '<' | ':' | ')' => {
-                    break;
+                ')' | '}' | ']' => {
+                    nesting -= 1;
+                    toks.push(tok);
+                    self.toks.next();
                 }
                 '\'' | '"' => {
                     toks.push(tok);
                     self.toks.next();
                     toks.append(&mut read_until_closing_quote(self.toks, tok.kind)?);
                 }
+                '\\' => {
+                    toks.push(tok);
+                    self.toks.next();
+                    if let Some(next) = self.toks.next() {
+                        toks.push(next);
+                    }
+                }
                 _ => {
                     toks.push(tok);
                     self.toks.next();
                 }
             }
         }
+
+        if toks.iter().all(|t| t.kind.is_whitespace()) {
+            return Err(("Expected expression.", self.span_before).into());
+        }
+
         self.parse_value_as_string_from_vec(toks, false)
     }
 
@@ -82,7 +135,8 @@ impl<'a> Parser<'a> {
         let mut buf = String::new();
         loop {
             self.whitespace();
-            buf.push_str(&self.parse_single_media_query()?);
+            self.parse_single_media_query(&mut buf)?;
+            self.whitespace();
             if !self.scan_char(',') {
                 break;
             }
@@ -92,46 +146,104 @@ impl<'a> Parser<'a> {
         Ok(buf)
     }
 
-    fn parse_media_feature(&mut self) -> SassResult<String> {
-        if let Some(Token { kind: '#', .. }) = self.toks.peek() {
-            if let Some(Token { kind: '{', .. }) = self.toks.peek_forward(1) {
-                self.toks.next();
-                self.toks.next();
-                return Ok(self.parse_interpolation_as_string()?.into_owned());
+    /// Parse either a parenthesized media condition or a single interpolation
+    /// that stands in for one
+    fn parse_media_or_interpolation(&mut self, buf: &mut String) -> SassResult<()> {
+        if let Some(Token { kind: '#', pos }) = self.toks.peek().cloned() {
+            self.toks.next();
+            self.span_before = pos;
+            self.expect_char('{')?;
+            buf.push_str(&self.parse_interpolation_as_string()?);
+            return Ok(());
+        }
+
+        self.parse_media_in_parens(buf)
+    }
+
+    /// Parse a sequence of media conditions joined by a single logical
+    /// operator, e.g. `(a) and (b) and (c)`
+    ///
+    /// Mixing `and` and `or` without parentheses is not allowed
+    fn parse_media_logic_sequence(&mut self, buf: &mut String, operator: &str) -> SassResult<()> {
+        loop {
+            self.parse_media_or_interpolation(buf)?;
+            self.whitespace();
+
+            if !self.scan_identifier(operator)? {
+                return Ok(());
             }
-            todo!()
+            self.whitespace();
+
+            buf.push(' ');
+            buf.push_str(operator);
+            buf.push(' ');
         }
-        let mut buf = String::with_capacity(2);
+    }
+
+    /// Parse the remainder of a condition that began with a parenthesized
+    /// expression, which may be followed by `and` or `or`
+    fn parse_media_condition_tail(&mut self, buf: &mut String) -> SassResult<()> {
+        self.whitespace();
+        if self.scan_identifier("and")? {
+            buf.push_str(" and ");
+            self.whitespace();
+            self.parse_media_logic_sequence(buf, "and")?;
+        } else if self.scan_identifier("or")? {
+            buf.push_str(" or ");
+            self.whitespace();
+            self.parse_media_logic_sequence(buf, "or")?;
+        }
+        Ok(())
+    }
+
+    fn parse_media_in_parens(&mut self, buf: &mut String) -> SassResult<()> {
         self.expect_char('(')?;
         buf.push('(');
         self.whitespace();
 
-        buf.push_str(&self.expression_until_comparison()?);
-
-        if let Some(Token { kind: ':', .. }) = self.toks.peek() {
-            self.toks.next();
+        if let Some(Token { kind: '(', .. }) = self.toks.peek() {
+            self.parse_media_in_parens(buf)?;
+            self.parse_media_condition_tail(buf)?;
+        } else if self.scan_identifier("not")? {
+            buf.push_str("not ");
             self.whitespace();
+            self.parse_media_or_interpolation(buf)?;
+        } else {
+            buf.push_str(&self.expression_until_comparison()?);
 
-            buf.push(':');
-            buf.push(' ');
-            let mut toks = read_until_closing_paren(self.toks)?;
-            if let Some(tok) = toks.pop() {
-                if tok.kind != ')' {
-                    todo!()
+            if self.scan_char(':') {
+                self.whitespace();
+
+                buf.push(':');
+                buf.push(' ');
+
+                let mut toks = read_until_closing_paren(self.toks)?;
+                match toks.pop() {
+                    Some(Token { kind: ')', .. }) => {}
+                    Some(Token { pos, .. }) => return Err(("expected \")\".", pos).into()),
+                    None => return Err(("expected \")\".", self.span_before).into()),
                 }
+
+                if toks.iter().all(|t| t.kind.is_whitespace()) {
+                    return Err(("Expected expression.", self.span_before).into());
+                }
+
+                buf.push_str(&self.parse_value_as_string_from_vec(toks, true)?);
+
+                self.whitespace();
+                buf.push(')');
+                return Ok(());
             }
-            buf.push_str(&self.parse_value_as_string_from_vec(toks, true)?);
 
-            self.whitespace();
-            buf.push(')');
-            return Ok(buf);
-        } else {
-            let next_tok = self.toks.peek().cloned();
-            let is_angle = next_tok.map_or(false, |t| t.kind == '<' || t.kind == '>');
-            if is_angle || matches!(next_tok, Some(Token { kind: '=', .. })) {
+            if let Some(Token { kind: next @ '<', .. })
+            | Some(Token { kind: next @ '>', .. })
+            | Some(Token { kind: next @ '=', .. }) = self.toks.peek().cloned()
+            {
+                self.toks.next();
+                let is_angle = next == '<' || next == '>';
+
                 buf.push(' ');
-                // todo: remove this unwrap
-                buf.push(self.toks.next().unwrap().kind);
+                buf.push(next);
                 if is_angle && self.scan_char('=') {
                     buf.push('=');
                 }
@@ -140,57 +252,86 @@ impl<'a> Parser<'a> {
                 self.whitespace();
 
                 buf.push_str(&self.expression_until_comparison()?);
+
+                // a range such as `(400px <= width < 700px)` may contain a second
+                // comparison, but only one pointing in the same direction
+                if is_angle && self.scan_char(next) {
+                    buf.push(' ');
+                    buf.push(next);
+                    if self.scan_char('=') {
+                        buf.push('=');
+                    }
+                    buf.push(' ');
+
+                    self.whitespace();
+
+                    buf.push_str(&self.expression_until_comparison()?);
+                }
             }
         }
 
         self.expect_char(')')?;
         self.whitespace();
         buf.push(')');
-        Ok(buf)
+        Ok(())
     }
 
-    fn parse_single_media_query(&mut self) -> SassResult<String> {
-        let mut buf = String::new();
+    fn parse_single_media_query(&mut self, buf: &mut String) -> SassResult<()> {
+        if let Some(Token { kind: '(', .. }) = self.toks.peek() {
+            self.parse_media_in_parens(buf)?;
+            return self.parse_media_condition_tail(buf);
+        }
 
-        if !matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
-            buf.push_str(&self.parse_identifier()?);
+        let ident1 = self.parse_identifier()?;
 
+        if ident1.node.eq_ignore_ascii_case("not") {
+            // e.g. `@media not (...) {`
             self.whitespace();
-
-            if let Some(tok) = self.toks.peek() {
-                if !is_name_start(tok.kind) {
-                    return Ok(buf);
-                }
+            if !self.looking_at_interpolated_identifier() {
+                buf.push_str("not ");
+                return self.parse_media_or_interpolation(buf);
             }
+        }
 
-            buf.push(' ');
-            let ident = self.parse_identifier()?;
+        self.whitespace();
+        buf.push_str(&ident1.node);
 
-            self.whitespace();
+        if !self.looking_at_interpolated_identifier() {
+            // e.g. `@media screen {`
+            return Ok(());
+        }
 
-            if ident.to_ascii_lowercase() == "and" {
-                buf.push_str("and ");
-            } else {
-                buf.push_str(&ident);
+        buf.push(' ');
 
-                if self.scan_identifier("and")? {
-                    self.whitespace();
-                    buf.push_str(" and ");
-                } else {
-                    return Ok(buf);
-                }
+        let ident2 = self.parse_identifier()?;
+
+        self.whitespace();
+
+        if ident2.node.eq_ignore_ascii_case("and") {
+            // e.g. `@media screen and ...`
+            buf.push_str("and ");
+        } else {
+            buf.push_str(&ident2.node);
+
+            if self.scan_identifier("and")? {
+                // e.g. `@media only screen and ...`
+                self.whitespace();
+                buf.push_str(" and ");
+            } else {
+                // e.g. `@media only screen {`
+                return Ok(());
             }
         }
 
-        loop {
-            self.whitespace();
-            buf.push_str(&self.parse_media_feature()?);
+        // we've consumed either `IDENTIFIER "and"` or `IDENTIFIER IDENTIFIER "and"`
+
+        if self.scan_identifier("not")? {
+            // e.g. `@media screen and not (...) {`
             self.whitespace();
-            if !self.scan_identifier("and")? {
-                break;
-            }
-            buf.push_str(" and ");
+            buf.push_str("not ");
+            return self.parse_media_or_interpolation(buf);
         }
-        Ok(buf)
+
+        self.parse_media_logic_sequence(buf, "and")
     }
 }
@@ -7,8 +7,10 @@ use peekmore::PeekMore;
 use crate::{
     args::{CallArgs, FuncArgs},
     atrule::{Content, Mixin},
+    common::Identifier,
     error::SassResult,
     utils::read_until_closing_curly_brace,
+    value::Value,
     Token,
 };
 
@@ -51,10 +53,12 @@ impl<'a> Parser<'a> {
         // if `@content` is *not* present and `@include` attempts to give a body, we throw an error
         // `Error: Mixin doesn't accept a content block.`
         //
-        // this is blocked on figuring out just how to check for this. presumably we could have a check
-        // not when parsing initially, but rather when `@include`ing to see if an `@content` was found.
+        // whether `@content` is present is recorded on the mixin for `accepts-content()`, but
+        // `@include` does not yet check it
 
-        let mixin = Mixin::new(args, body, false, self.at_root);
+        let accepts_content_block = contains_content_rule(&body);
+
+        let mixin = Mixin::new(args, body, accepts_content_block, self.at_root, span);
 
         if self.at_root {
             self.global_scope.insert_mixin(name, mixin);
@@ -66,11 +70,26 @@ impl<'a> Parser<'a> {
 
     pub(super) fn parse_include(&mut self) -> SassResult<Vec<Stmt>> {
         self.whitespace_or_comment();
-        let name = self.parse_identifier()?.map_node(Into::into);
+        let name: Spanned<Identifier> = self.parse_identifier()?.map_node(Into::into);
+
+        let is_apply = if let Some(Token { kind: '.', .. }) = self.toks.peek() {
+            self.toks.next();
+            let member: Spanned<Identifier> = self
+                .parse_identifier_no_interpolation(false)?
+                .map_node(Into::into);
+            let module = self.global_scope.get_module(name)?;
+            if !module.mixin_exists(member.node) {
+                return Err(("Undefined mixin.", name.span.merge(member.span)).into());
+            }
+            true
+        } else {
+            !self.scopes.mixin_exists(name.node, self.global_scope)
+                && self.global_scope.global_module_mixin_exists(name.node)
+        };
 
         self.whitespace_or_comment();
 
-        let args = if let Some(Token { kind: '(', .. }) = self.toks.peek() {
+        let mut args = if let Some(Token { kind: '(', .. }) = self.toks.peek() {
             self.toks.next();
             self.parse_call_args()?
         } else {
@@ -120,14 +139,38 @@ impl<'a> Parser<'a> {
             self.toks.next();
         }
 
+        let (mixin, member, args) = if is_apply {
+            let mixin = match args.get_err(0, "mixin")? {
+                Value::MixinRef(mixin) => mixin,
+                v => {
+                    return Err((
+                        format!(
+                            "$mixin: {} is not a mixin reference.",
+                            v.inspect(args.span())?
+                        ),
+                        args.span(),
+                    )
+                        .into())
+                }
+            };
+            let member = format!("{}()", mixin.name());
+            (mixin.into_mixin(), member, args.decrement())
+        } else {
+            let mixin = self.scopes.get_mixin(name, self.global_scope)?;
+            (mixin, format!("{}()", name.node), args)
+        };
+
         let Mixin {
             body,
             args: fn_args,
             declared_at_root,
             ..
-        } = self.scopes.get_mixin(name, self.global_scope)?;
+        } = mixin;
+
+        let span = args.span();
+        let (scope, arg_list) = self.eval_args(fn_args, args)?;
 
-        let scope = self.eval_args(fn_args, args)?;
+        self.enter_frame(member, name.span)?;
 
         if declared_at_root {
             mem::swap(self.scopes, self.content_scopes);
@@ -153,9 +196,19 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: 0,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
-        .parse()?;
+        .parse();
+
+        let body = self.exit_frame(body);
 
         self.content.pop();
         self.scopes.exit_scope();
@@ -164,6 +217,12 @@ impl<'a> Parser<'a> {
             mem::swap(self.scopes, self.content_scopes);
         }
 
+        let body = body?;
+
+        if let Some(arg_list) = arg_list {
+            arg_list.check_keywords_accessed(span)?;
+        }
+
         Ok(body)
     }
 
@@ -176,14 +235,20 @@ impl<'a> Parser<'a> {
                 .into());
         }
 
+        let mut arg_list = None;
+        let content_span = self.span_before;
+        let mut span = self.span_before;
+
         if let Some(Token { kind: '(', .. }) = self.toks.peek() {
             self.toks.next();
             let args = self.parse_call_args()?;
+            span = args.span();
             if let Some(Some(content_args)) = self.content.last().map(|v| v.content_args.clone()) {
                 args.max_args(content_args.len())?;
 
-                let scope = self.eval_args(content_args, args)?;
+                let (scope, list) = self.eval_args(content_args, args)?;
                 self.content_scopes.merge(scope);
+                arg_list = list;
             } else {
                 args.max_args(0)?;
             }
@@ -191,7 +256,8 @@ impl<'a> Parser<'a> {
 
         Ok(if let Some(content) = &self.content.pop() {
             let stmts = if let Some(body) = content.content.clone() {
-                Parser {
+                self.call_stack.push(("@content".to_owned(), content_span));
+                let stmts = Parser {
                     toks: &mut body.into_iter().peekmore(),
                     map: self.map,
                     path: self.path,
@@ -204,17 +270,48 @@ impl<'a> Parser<'a> {
                     at_root: self.at_root,
                     at_root_has_selector: self.at_root_has_selector,
                     extender: self.extender,
+                    options: self.options,
                     content_scopes: self.scopes,
+                    depth: self.depth,
+                    import_stack: self.import_stack,
+                    budget: self.budget,
+                    call_stack: self.call_stack,
+                    deprecations: self.deprecations,
+                    errors: self.errors,
+                    dependencies: self.dependencies,
                 }
-                .parse()?
+                .parse();
+                self.exit_frame(stmts)
             } else {
-                Vec::new()
+                Ok(Vec::new())
             };
             self.content.push(content.clone());
             self.scopes.exit_scope();
+
+            let stmts = stmts?;
+
+            if let Some(arg_list) = arg_list {
+                arg_list.check_keywords_accessed(span)?;
+            }
+
             stmts
         } else {
             Vec::new()
         })
     }
 }
+
+/// Whether the body of a mixin contains an `@content` rule
+///
+/// This is a purely lexical check, so `@content` inside of a string or comment
+/// is counted as well
+fn contains_content_rule(body: &[Token]) -> bool {
+    body.windows(9).any(|window| {
+        window[0].kind == '@'
+            && window[1..8]
+                .iter()
+                .map(|tok| tok.kind)
+                .eq("content".chars())
+            && !matches!(window[8].kind, '-' | '_' | 'a'..='z' | 'A'..='Z' | '0'..='9')
+    })
+}
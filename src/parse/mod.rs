@@ -1,4 +1,8 @@
-use std::{convert::TryFrom, path::Path, vec::IntoIter};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    vec::IntoIter,
+};
 
 use codemap::{CodeMap, Span, Spanned};
 use peekmore::{PeekMore, PeekMoreIterator};
@@ -9,22 +13,28 @@ use crate::{
         media::MediaRule,
         AtRuleKind, Content, SupportsRule, UnknownAtRule,
     },
-    error::SassResult,
+    deprecation::{Deprecation, DeprecationCounts},
+    error::{format_trace, SassError, SassResult, StackFrame},
     scope::{Scope, Scopes},
     selector::{
         ComplexSelectorComponent, ExtendRule, ExtendedSelector, Extender, Selector, SelectorParser,
     },
     style::Style,
-    utils::{read_until_closing_curly_brace, read_until_semicolon_or_closing_curly_brace},
+    utils::{
+        ensure_stack, read_until_closing_curly_brace, read_until_semicolon_or_closing_curly_brace,
+    },
     value::Value,
-    {Cow, Token},
+    {Cow, Options, Token},
 };
 
+pub(crate) use budget::Budget;
 use common::{Comment, ContextFlags, NeverEmptyVec, SelectorOrStyle};
+use recovery::Checkpoint;
 
-pub(crate) use value::{HigherIntermediateValue, ValueVisitor};
+pub(crate) use value::ValueVisitor;
 
 mod args;
+mod budget;
 pub mod common;
 mod control_flow;
 mod function;
@@ -33,6 +43,8 @@ mod import;
 mod keyframes;
 mod media;
 mod mixin;
+mod module;
+mod recovery;
 mod style;
 mod throw_away;
 mod value;
@@ -82,6 +94,24 @@ pub(crate) struct Parser<'a> {
     /// not the `@at-rule` block has a super selector
     pub at_root_has_selector: bool,
     pub extender: &'a mut Extender,
+    pub options: &'a Options,
+    /// How deeply the current block, expression, or selector is nested,
+    /// including through calls to functions and mixins
+    pub depth: usize,
+    /// The canonicalized paths of the files currently being imported, along
+    /// with the span of the `@import` which loaded each of them
+    pub import_stack: &'a mut Vec<(PathBuf, Span)>,
+    pub budget: &'a mut Budget,
+    /// The mixins, functions, `@content` blocks, and imports currently being
+    /// evaluated, along with the span of the call which entered each of them
+    pub call_stack: &'a mut Vec<(String, Span)>,
+    pub deprecations: &'a mut DeprecationCounts,
+    /// The errors which were recovered from, if `Options::continue_on_error`
+    /// is set
+    pub errors: &'a mut Vec<SassError>,
+    /// The spans of the files loaded through `Options::load_path`, or
+    /// imported by such a file, whose warnings `Options::quiet_deps` silences
+    pub dependencies: &'a mut Vec<Span>,
 }
 
 impl<'a> Parser<'a> {
@@ -97,13 +127,95 @@ impl<'a> Parser<'a> {
         Ok(stmts)
     }
 
+    /// Returns the depth of a block, expression, or selector nested inside the
+    /// current one
+    ///
+    /// This errors once `Options::max_depth` is exceeded, so that deeply nested
+    /// input is rejected rather than exhausting memory
+    pub fn nested_depth(&self, span: Span) -> SassResult<usize> {
+        if self.depth < self.options.max_depth {
+            Ok(self.depth + 1)
+        } else {
+            Err(("Maximum nesting depth exceeded.", span).into())
+        }
+    }
+
+    /// Pushes the frame of a call to a `@function` or `@mixin` onto the call
+    /// stack, which must later be popped by `Parser::exit_frame`
+    ///
+    /// This errors once `Options::max_call_depth` is exceeded, so that
+    /// infinitely recursive `@function`s and `@mixin`s are reported rather
+    /// than recursing until memory runs out
+    pub fn enter_frame(&mut self, member: String, span: Span) -> SassResult<()> {
+        if self.call_stack.len() >= self.options.max_call_depth {
+            return Err(("Stack overflow.", span).into());
+        }
+        self.call_stack.push((member, span));
+        Ok(())
+    }
+
+    /// The Sass stack trace at `span`, starting with the innermost frame
+    pub fn trace(&self, span: Span) -> Vec<(Span, String)> {
+        let mut trace = Vec::with_capacity(self.call_stack.len() + 1);
+        let mut span = span;
+        for (member, call_span) in self.call_stack.iter().rev() {
+            trace.push((span, member.clone()));
+            span = *call_span;
+        }
+        trace.push((span, "root stylesheet".to_owned()));
+        trace
+    }
+
+    /// Pops the innermost frame of the call stack once it has been evaluated
+    ///
+    /// An error raised inside of the frame is given the stack trace at the
+    /// point it was raised, before the frame is popped
+    pub fn exit_frame<T>(&mut self, result: SassResult<T>) -> SassResult<T> {
+        let result = result.map_err(|mut e| {
+            if let Some(span) = e.untraced_span() {
+                e.set_trace(self.trace(span));
+            }
+            e
+        });
+        self.call_stack.pop();
+        result
+    }
+
     fn parse_stmt(&mut self) -> SassResult<Vec<Stmt>> {
+        let depth = self.depth;
+        self.depth = self.nested_depth(self.span_before)?;
+        self.step(self.span_before)?;
+        let stmts = ensure_stack(|| self.parse_block());
+        self.depth = depth;
+        stmts
+    }
+
+    fn parse_block(&mut self) -> SassResult<Vec<Stmt>> {
         let mut stmts = Vec::new();
-        while let Some(Token { kind, pos }) = self.toks.peek() {
+        let mut checkpoint = None;
+        while let Err(e) = self.parse_statements(&mut stmts, &mut checkpoint) {
+            self.recover(e, checkpoint.take())?;
+        }
+        Ok(stmts)
+    }
+
+    /// Parses statements into `stmts` until the end of the current block
+    ///
+    /// Before each statement which may fail, `checkpoint` is set to the point
+    /// from which to continue parsing should it fail
+    fn parse_statements(
+        &mut self,
+        stmts: &mut Vec<Stmt>,
+        checkpoint: &mut Option<Checkpoint>,
+    ) -> SassResult<()> {
+        while let Some(Token { kind, pos }) = self.toks.peek().copied() {
             if self.flags.in_function() && !stmts.is_empty() {
-                return Ok(stmts);
+                return Ok(());
+            }
+            self.span_before = pos;
+            if !matches!(kind, '\t' | '\n' | ' ' | ';' | '}') {
+                *checkpoint = self.checkpoint();
             }
-            self.span_before = *pos;
             match kind {
                 '@' => {
                     self.toks.next();
@@ -117,7 +229,8 @@ impl<'a> Parser<'a> {
                         AtRuleKind::Function => self.parse_function()?,
                         AtRuleKind::Return => {
                             if self.flags.in_function() {
-                                return Ok(vec![Stmt::Return(self.parse_return()?)]);
+                                *stmts = vec![Stmt::Return(self.parse_return()?)];
+                                return Ok(());
                             } else {
                                 return Err((
                                     "This at-rule is not allowed here.",
@@ -192,8 +305,10 @@ impl<'a> Parser<'a> {
                         AtRuleKind::Unknown(_) => {
                             stmts.push(self.parse_unknown_at_rule(kind_string.node)?)
                         }
-                        AtRuleKind::Use => todo!("@use not yet implemented"),
-                        AtRuleKind::Forward => todo!("@forward not yet implemented"),
+                        AtRuleKind::Use => self.parse_module_use(kind_string.span)?,
+                        AtRuleKind::Forward => {
+                            return Err(("@forward is not yet supported.", kind_string.span).into())
+                        }
                         AtRuleKind::Extend => self.parse_extend()?,
                         AtRuleKind::Supports => stmts.push(self.parse_supports()?),
                         AtRuleKind::Keyframes => stmts.push(self.parse_keyframes()?),
@@ -214,14 +329,14 @@ impl<'a> Parser<'a> {
                     }
                 }
                 '\u{0}'..='\u{8}' | '\u{b}'..='\u{1f}' => {
-                    return Err(("expected selector.", *pos).into())
+                    return Err(("expected selector.", pos).into())
                 }
                 '}' => {
                     self.toks.next();
                     break;
                 }
                 // dart-sass seems to special-case the error message here?
-                '!' | '{' => return Err(("expected \"}\".", *pos).into()),
+                '!' | '{' => return Err(("expected \"}\".", pos).into()),
                 _ => {
                     if self.flags.in_keyframes() {
                         match self.is_selector_or_style()? {
@@ -238,10 +353,26 @@ impl<'a> Parser<'a> {
                             }
                             SelectorOrStyle::Selector(init) => {
                                 let selector = self.parse_keyframes_selector(init)?;
+                                let span = self.span_before;
                                 self.scopes.enter_new_scope();
 
                                 let body = self.parse_stmt()?;
                                 self.scopes.exit_scope();
+
+                                if body.iter().any(|stmt| {
+                                    !matches!(
+                                        stmt,
+                                        Stmt::Style(..)
+                                            | Stmt::Comment(..)
+                                            | Stmt::KeyframesRuleSet(..)
+                                    )
+                                }) {
+                                    return Err((
+                                        "Only declarations are allowed in @keyframes blocks.",
+                                        span,
+                                    )
+                                        .into());
+                                }
                                 stmts.push(Stmt::KeyframesRuleSet(Box::new(KeyframesRuleSet {
                                     selector,
                                     body,
@@ -290,7 +421,7 @@ impl<'a> Parser<'a> {
                 }
             }
         }
-        Ok(stmts)
+        Ok(())
     }
 
     pub fn parse_selector(
@@ -358,7 +489,15 @@ impl<'a> Parser<'a> {
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
+                options: self.options,
                 content_scopes: self.content_scopes,
+                depth: self.depth,
+                import_stack: self.import_stack,
+                budget: self.budget,
+                call_stack: self.call_stack,
+                deprecations: self.deprecations,
+                errors: self.errors,
+                dependencies: self.dependencies,
             },
             allows_parent,
             true,
@@ -416,7 +555,11 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse_interpolation(&mut self) -> SassResult<Spanned<Value>> {
-        let val = self.parse_value(true)?;
+        let depth = self.depth;
+        self.depth = self.nested_depth(self.span_before)?;
+        let val = ensure_stack(|| self.parse_value(true));
+        self.depth = depth;
+        let val = val?;
         match self.toks.next() {
             Some(Token { kind: '}', .. }) => {}
             Some(..) | None => return Err(("expected \"}\".", val.span).into()),
@@ -427,7 +570,7 @@ impl<'a> Parser<'a> {
     pub fn parse_interpolation_as_string(&mut self) -> SassResult<Cow<'static, str>> {
         let interpolation = self.parse_interpolation()?;
         Ok(match interpolation.node {
-            Value::String(v, ..) => Cow::owned(v),
+            Value::String(v, ..) => Cow::owned(v.to_string()),
             v => v.to_css_string(interpolation.span)?,
         })
     }
@@ -596,7 +739,15 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: self.depth,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
         .parse_stmt()?;
 
@@ -663,7 +814,15 @@ impl<'a> Parser<'a> {
             at_root: true,
             at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: self.depth,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
         .parse()?
         .into_iter()
@@ -703,7 +862,15 @@ impl<'a> Parser<'a> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: self.depth,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
         .parse_selector(false, true, String::new())?;
 
@@ -729,15 +896,15 @@ impl<'a> Parser<'a> {
         let super_selector = self.super_selectors.last();
 
         for complex in value.0.components {
-            if complex.components.len() != 1 || !complex.components.first().unwrap().is_compound() {
+            let compound = match complex.components.first() {
+                Some(ComplexSelectorComponent::Compound(c)) if complex.components.len() == 1 => {
+                    c.clone()
+                }
                 // If the selector was a compound selector but not a simple
                 // selector, emit a more explicit error.
-                return Err(("complex selectors may not be extended.", self.span_before).into());
-            }
-
-            let compound = match complex.components.first() {
-                Some(ComplexSelectorComponent::Compound(c)) => c.clone(),
-                Some(..) | None => todo!(),
+                _ => {
+                    return Err(("complex selectors may not be extended.", self.span_before).into())
+                }
             };
             if compound.components.len() != 1 {
                 return Err((
@@ -780,7 +947,15 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            options: self.options,
             content_scopes: self.content_scopes,
+            depth: self.depth,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
         }
         .parse_stmt()?;
 
@@ -841,7 +1016,11 @@ impl<'a> Parser<'a> {
 }
 
 impl<'a> Parser<'a> {
-    fn debug(&self, message: &Spanned<Cow<'a, str>>) {
+    fn debug(&mut self, message: &Spanned<Cow<'a, str>>) {
+        self.global_scope.function_cache().taint();
+        if self.options.quiet {
+            return;
+        }
         let loc = self.map.look_up_span(message.span);
         eprintln!(
             "{}:{} Debug: {}",
@@ -851,14 +1030,76 @@ impl<'a> Parser<'a> {
         );
     }
 
-    fn warn(&self, message: &Spanned<Cow<'a, str>>) {
-        let loc = self.map.look_up_span(message.span);
-        eprintln!(
-            "Warning: {}\n    {} {}:{}  root stylesheet",
+    /// Whether `span` is within a file loaded through `Options::load_path`,
+    /// or imported by such a file
+    fn is_dependency(&self, span: Span) -> bool {
+        self.dependencies
+            .iter()
+            .any(|file| file.low() <= span.low() && span.high() <= file.high())
+    }
+
+    /// Whether warnings raised at `span` are silenced, either by
+    /// `Options::quiet` or, within a dependency, `Options::quiet_deps`
+    fn is_quiet(&self, span: Span) -> bool {
+        self.options.quiet || (self.options.quiet_deps && self.is_dependency(span))
+    }
+
+    fn warn(&mut self, message: &Spanned<Cow<'a, str>>) {
+        self.global_scope.function_cache().taint();
+        if self.is_quiet(message.span) {
+            return;
+        }
+        eprint!(
+            "Warning: {}\n{}",
             message.node,
-            loc.file.name(),
-            loc.begin.line + 1,
-            loc.begin.column + 1
+            self.format_trace(message.span)
         );
     }
+
+    /// Reports a use of a deprecated feature, unless it has been silenced
+    ///
+    /// This errors instead if the deprecation was made fatal
+    fn warn_deprecation(
+        &mut self,
+        deprecation: Deprecation,
+        message: &str,
+        span: Span,
+    ) -> SassResult<()> {
+        if self.options.fatal_deprecations.contains(&deprecation) {
+            return Err((
+                format!(
+                    "{}\n\nThis is only an error because you've set the {} deprecation to be fatal.\nRemove this setting if you need to keep using this feature.",
+                    message, deprecation
+                ),
+                span,
+            )
+                .into());
+        }
+
+        if self.options.silenced_deprecations.contains(&deprecation) || self.is_quiet(span) {
+            return Ok(());
+        }
+
+        self.global_scope.function_cache().taint();
+
+        if self.deprecations.record(deprecation, self.options.verbose) {
+            eprint!(
+                "Deprecation Warning [{}]: {}\n{}",
+                deprecation,
+                message,
+                self.format_trace(span)
+            );
+        }
+
+        Ok(())
+    }
+
+    fn format_trace(&self, span: Span) -> String {
+        let trace: Vec<StackFrame> = self
+            .trace(span)
+            .into_iter()
+            .map(|(span, member)| StackFrame::new(self.map, span, member))
+            .collect();
+        format_trace(&trace, 4)
+    }
 }
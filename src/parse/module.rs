@@ -0,0 +1,101 @@
+use codemap::{Span, Spanned};
+
+use crate::{builtin::Module, common::Identifier, error::SassResult, value::Value, Token};
+
+use super::Parser;
+
+impl<'a> Parser<'a> {
+    /// Parse `@use`, after the at-rule name
+    ///
+    /// Only the built-in `sass:` modules are currently supported
+    pub(super) fn parse_module_use(&mut self, rule_span: Span) -> SassResult<()> {
+        if !self.at_root {
+            return Err(("This at-rule is not allowed here.", rule_span).into());
+        }
+
+        self.whitespace_or_comment();
+
+        let quote = match self.toks.next() {
+            Some(Token { kind: q @ '"', .. }) | Some(Token { kind: q @ '\'', .. }) => q,
+            Some(Token { pos, .. }) => return Err(("Expected string.", pos).into()),
+            None => return Err(("Expected string.", self.span_before).into()),
+        };
+
+        let Spanned { node: url, span } = self.parse_quoted_string(quote)?;
+
+        let url = match url {
+            Value::String(s, ..) => s,
+            _ => return Err(("Expected string.", span).into()),
+        };
+
+        let module = match Module::from_url(&url) {
+            Some(module) => module,
+            None if url.starts_with("sass:") => {
+                return Err(("Can't find stylesheet to import.", span).into())
+            }
+            None => return Err(("@use of stylesheets is not yet supported.", span).into()),
+        };
+
+        self.whitespace_or_comment();
+
+        let mut namespace = Some(Identifier::from(module.default_namespace()));
+
+        match self.toks.peek() {
+            Some(Token { kind: ';', .. }) | Some(Token { kind: '}', .. }) | None => {}
+            Some(..) => {
+                let Spanned {
+                    node: keyword,
+                    span: keyword_span,
+                } = self.parse_identifier()?;
+
+                match keyword.as_str() {
+                    "as" => {
+                        self.whitespace_or_comment();
+                        if let Some(Token { kind: '*', .. }) = self.toks.peek() {
+                            self.toks.next();
+                            namespace = None;
+                        } else {
+                            namespace =
+                                Some(self.parse_identifier_no_interpolation(false)?.node.into());
+                        }
+                    }
+                    "with" => {
+                        return Err(("Built-in modules can't be configured.", keyword_span).into())
+                    }
+                    _ => return Err(("expected \";\".", keyword_span).into()),
+                }
+
+                self.whitespace_or_comment();
+            }
+        }
+
+        match self.toks.peek() {
+            Some(Token { kind: ';', .. }) => {
+                self.toks.next();
+            }
+            Some(Token { kind: '}', .. }) | None => {}
+            Some(Token { pos, .. }) => return Err(("expected \";\".", *pos).into()),
+        }
+
+        match namespace {
+            Some(namespace) => {
+                if self.global_scope.insert_module(namespace, module).is_some() {
+                    return Err((
+                        format!("There's already a module with namespace \"{}\".", namespace),
+                        span,
+                    )
+                        .into());
+                }
+            }
+            None => {
+                for (name, value) in module.variables() {
+                    self.global_scope
+                        .insert_var(name.into(), Spanned { node: value, span });
+                }
+                self.global_scope.insert_global_module(module);
+            }
+        }
+
+        Ok(())
+    }
+}
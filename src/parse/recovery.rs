@@ -0,0 +1,178 @@
+//! Recovering from errors to report every problem in a stylesheet at once
+
+use codemap::Span;
+
+use crate::{error::SassError, Token};
+
+use super::Parser;
+
+/// The point from which to continue parsing if a statement fails, along
+/// with the state to restore
+#[derive(Debug)]
+pub(crate) struct Checkpoint {
+    /// The span of the last token of the statement
+    end: Span,
+    scopes: usize,
+    content_scopes: usize,
+    super_selectors: usize,
+    content: usize,
+    at_root: bool,
+}
+
+impl<'a> Parser<'a> {
+    /// Records the state before the statement beginning at the next token,
+    /// if an error in it may be recovered from
+    ///
+    /// Errors are only recovered from when `Options::continue_on_error` is
+    /// set, and never inside of a `@function`, where skipping a statement
+    /// would only lead to a confusing error about a missing `@return`
+    pub fn checkpoint(&mut self) -> Option<Checkpoint> {
+        if !self.options.continue_on_error || self.flags.in_function() {
+            return None;
+        }
+
+        Some(Checkpoint {
+            end: self.statement_end()?,
+            scopes: self.scopes.len(),
+            content_scopes: self.content_scopes.len(),
+            super_selectors: self.super_selectors.len(),
+            content: self.content.len(),
+            at_root: self.at_root,
+        })
+    }
+
+    /// Records `err` and skips the rest of the statement in which it was
+    /// raised, or returns it if it can't be recovered from
+    pub fn recover(
+        &mut self,
+        mut err: Box<SassError>,
+        checkpoint: Option<Checkpoint>,
+    ) -> Result<(), Box<SassError>> {
+        let checkpoint = match checkpoint {
+            Some(checkpoint) if !self.budget.is_exhausted() => checkpoint,
+            _ => return Err(err),
+        };
+
+        if let Some(span) = err.untraced_span() {
+            err.set_trace(self.trace(span));
+        }
+        self.errors.push(*err);
+
+        self.toks.reset_cursor();
+        while let Some(tok) = self.toks.peek() {
+            if tok.pos.low() > checkpoint.end.low() {
+                break;
+            }
+            self.toks.next();
+        }
+
+        self.scopes.truncate(checkpoint.scopes);
+        self.content_scopes.truncate(checkpoint.content_scopes);
+        self.super_selectors.truncate(checkpoint.super_selectors);
+        self.content.truncate(checkpoint.content);
+        self.at_root = checkpoint.at_root;
+
+        Ok(())
+    }
+
+    /// Finds the span of the last token of the statement beginning at the
+    /// next token, without consuming any
+    ///
+    /// A statement ends with a `;` or with the `}` closing its block (and
+    /// any `@else` blocks following it), whichever comes first outside of
+    /// brackets, strings, and comments. A `}` closing the enclosing block
+    /// is not part of the statement.
+    fn statement_end(&mut self) -> Option<Span> {
+        // the unclosed brackets, with `#` standing in for the `{` of interpolation
+        let mut brackets = Vec::new();
+        let mut quote = None;
+        let mut end = None;
+        let mut prev = None;
+        let mut i = 0;
+
+        while let Some(Token { kind, pos }) = self.toks.peek_nth(i).copied() {
+            i += 1;
+
+            if let Some(q) = quote {
+                match kind {
+                    '\\' => i += 1,
+                    _ if kind == q => quote = None,
+                    _ => {}
+                }
+                end = Some(pos);
+                continue;
+            }
+
+            match kind {
+                '"' | '\'' => quote = Some(kind),
+                '\\' => i += 1,
+                '/' if matches!(self.toks.peek_nth(i), Some(Token { kind: '/', .. })) => {
+                    while let Some(Token { kind, .. }) = self.toks.peek_nth(i) {
+                        if *kind == '\n' {
+                            break;
+                        }
+                        i += 1;
+                    }
+                    continue;
+                }
+                '/' if matches!(self.toks.peek_nth(i), Some(Token { kind: '*', .. })) => {
+                    i += 1;
+                    while let Some(Token { kind, pos }) = self.toks.peek_nth(i).copied() {
+                        i += 1;
+                        end = Some(pos);
+                        if kind == '*'
+                            && matches!(self.toks.peek_nth(i), Some(Token { kind: '/', .. }))
+                        {
+                            end = self.toks.peek_nth(i).map(Token::pos);
+                            i += 1;
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                '{' if prev == Some('#') => brackets.push('#'),
+                '(' | '[' | '{' => brackets.push(kind),
+                ')' | ']' => {
+                    brackets.pop();
+                }
+                '}' => match brackets.pop() {
+                    None => break,
+                    Some('{') if brackets.is_empty() => {
+                        end = Some(pos);
+                        if !self.else_follows(i) {
+                            return end;
+                        }
+                        prev = Some(kind);
+                        continue;
+                    }
+                    Some(..) => {}
+                },
+                ';' if brackets.is_empty() => return Some(pos),
+                _ => {}
+            }
+
+            end = Some(pos);
+            prev = Some(kind);
+        }
+
+        end
+    }
+
+    /// Whether the tokens beginning at the `i`th are whitespace followed by
+    /// `@else` (or `@elseif`)
+    fn else_follows(&mut self, mut i: usize) -> bool {
+        while matches!(
+            self.toks.peek_nth(i),
+            Some(Token { kind: ' ', .. })
+                | Some(Token { kind: '\t', .. })
+                | Some(Token { kind: '\n', .. })
+        ) {
+            i += 1;
+        }
+
+        "@else"
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| matches!(self.toks.peek_nth(i + offset), Some(tok) if tok.kind == c))
+    }
+}
@@ -126,6 +126,7 @@ impl<'a> Parser<'a> {
                         if let Some(toks) = self.parse_style_value_when_no_space_after_semicolon() {
                             let len = toks.len();
                             if let Ok(val) = self.parse_value_from_vec(toks, false) {
+                                self.emit(&val.node, val.span)?;
                                 self.toks.take(len).for_each(drop);
                                 return Ok(SelectorOrStyle::Style(
                                     InternedString::get_or_intern(property),
@@ -173,7 +174,9 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_style_value(&mut self) -> SassResult<Spanned<Value>> {
-        self.parse_value(false)
+        let value = self.parse_value(false)?;
+        self.emit(&value.node, value.span)?;
+        Ok(value)
     }
 
     pub(super) fn parse_style_group(
@@ -0,0 +1,432 @@
+use std::vec::IntoIter;
+
+use codemap::Span;
+
+use peekmore::{PeekMore, PeekMoreIterator};
+
+use crate::{
+    common::{Op, QuoteKind},
+    error::SassResult,
+    utils::{is_name, is_name_start, read_until_closing_curly_brace, read_until_closing_paren},
+    value::{CalculationArg, CalculationName, SassCalculation, Value},
+    Token,
+};
+
+use super::super::Parser;
+
+/// The structure of a calculation, before any of its operands have been
+/// evaluated
+#[derive(Debug)]
+enum CalculationNode {
+    /// A number, variable, or function call, evaluated as SassScript
+    Expression(Vec<Token>),
+    /// The contents of `#{...}`
+    Interpolation(Vec<Token>),
+    Operation(Box<CalculationNode>, Op, Box<CalculationNode>),
+}
+
+impl<'a> Parser<'a> {
+    /// Parse the arguments to `calc()` or `clamp()` as a calculation, falling
+    /// back to treating the function as plain CSS if the arguments aren't valid
+    /// calculation syntax
+    ///
+    /// This function assumes that the opening parenthesis has already been consumed
+    pub(super) fn parse_calculation_or_css_function(
+        &mut self,
+        name: CalculationName,
+    ) -> SassResult<Value> {
+        let toks = read_until_closing_paren(self.toks)?;
+
+        if let Some(value) = self.parse_calculation(name, toks.clone())? {
+            return Ok(value);
+        }
+
+        let mut parser = Parser {
+            toks: &mut toks.into_iter().peekmore(),
+            map: self.map,
+            path: self.path,
+            scopes: self.scopes,
+            global_scope: self.global_scope,
+            super_selectors: self.super_selectors,
+            span_before: self.span_before,
+            content: self.content,
+            flags: self.flags,
+            at_root: self.at_root,
+            at_root_has_selector: self.at_root_has_selector,
+            extender: self.extender,
+            options: self.options,
+            content_scopes: self.content_scopes,
+            depth: self.depth,
+            import_stack: self.import_stack,
+            budget: self.budget,
+            call_stack: self.call_stack,
+            deprecations: self.deprecations,
+            errors: self.errors,
+            dependencies: self.dependencies,
+        };
+
+        let mut buf = name.as_str().to_owned();
+        match name {
+            CalculationName::Calc => parser.parse_calc_args(&mut buf)?,
+            _ => buf.push_str(&parser.parse_call_args()?.to_css_string()?),
+        }
+
+        Ok(Value::String(buf.into(), QuoteKind::None))
+    }
+
+    /// Evaluate `toks`, the arguments to a calculation function including the
+    /// closing parenthesis, simplifying the result as much as possible
+    ///
+    /// Returns `None` if `toks` is not valid calculation syntax
+    pub(super) fn parse_calculation(
+        &mut self,
+        name: CalculationName,
+        mut toks: Vec<Token>,
+    ) -> SassResult<Option<Value>> {
+        let span = match toks.pop() {
+            Some(Token { kind: ')', pos }) => self.span_before.merge(pos),
+            Some(Token { pos, .. }) => return Err(("expected \")\".", pos).into()),
+            None => return Err(("expected \")\".", self.span_before).into()),
+        };
+
+        let nodes = match parse_calculation_args(&mut toks.into_iter().peekmore()) {
+            Some(nodes) => nodes,
+            None => return Ok(None),
+        };
+
+        match (name, nodes.len()) {
+            (CalculationName::Calc, 1)
+            | (CalculationName::Clamp, 3)
+            | (CalculationName::Min, _)
+            | (CalculationName::Max, _) => {}
+            _ => return Ok(None),
+        }
+
+        let mut args = nodes
+            .into_iter()
+            .map(|node| self.eval_calculation_node(node, span))
+            .collect::<SassResult<Vec<CalculationArg>>>()?;
+
+        Ok(Some(match name {
+            CalculationName::Calc => SassCalculation::calc(args.pop().unwrap()),
+            CalculationName::Min | CalculationName::Max => {
+                SassCalculation::min_max(name, args, span)?
+            }
+            CalculationName::Clamp => {
+                let max = args.pop().unwrap();
+                let value = args.pop().unwrap();
+                let min = args.pop().unwrap();
+                SassCalculation::clamp(min, value, max, span)?
+            }
+        }))
+    }
+
+    fn eval_calculation_node(
+        &mut self,
+        node: CalculationNode,
+        span: Span,
+    ) -> SassResult<CalculationArg> {
+        Ok(match node {
+            CalculationNode::Expression(toks) => {
+                let value = self.parse_value_from_vec(toks, true)?;
+                CalculationArg::from_value(value.node, value.span)?
+            }
+            CalculationNode::Interpolation(toks) => {
+                let value = self.parse_value_from_vec(toks, false)?;
+                CalculationArg::Interpolation(
+                    value.node.unquote().to_css_string(value.span)?.into_owned(),
+                )
+            }
+            CalculationNode::Operation(lhs, op, rhs) => {
+                let lhs = self.eval_calculation_node(*lhs, span)?;
+                let rhs = self.eval_calculation_node(*rhs, span)?;
+                SassCalculation::operate(op, lhs, rhs, span)?
+            }
+        })
+    }
+}
+
+type Toks = PeekMoreIterator<IntoIter<Token>>;
+
+fn parse_calculation_args(toks: &mut Toks) -> Option<Vec<CalculationNode>> {
+    let mut args = Vec::new();
+    loop {
+        whitespace(toks);
+        args.push(parse_sum(toks)?);
+        whitespace(toks);
+        match toks.next() {
+            Some(Token { kind: ',', .. }) => {}
+            Some(..) => return None,
+            None => return Some(args),
+        }
+    }
+}
+
+fn parse_sum(toks: &mut Toks) -> Option<CalculationNode> {
+    let mut lhs = parse_product(toks)?;
+    loop {
+        let (next, found_whitespace) = peek_past_whitespace(toks);
+        let op = match next {
+            Some('+') => Op::Plus,
+            Some('-') => Op::Minus,
+            _ => {
+                toks.reset_cursor();
+                return Some(lhs);
+            }
+        };
+
+        // `+` and `-` must be surrounded by whitespace in calculations
+        if !found_whitespace {
+            toks.reset_cursor();
+            return None;
+        }
+
+        toks.reset_cursor();
+        whitespace(toks);
+        toks.next();
+
+        if !whitespace(toks) {
+            return None;
+        }
+
+        let rhs = parse_product(toks)?;
+        lhs = CalculationNode::Operation(Box::new(lhs), op, Box::new(rhs));
+    }
+}
+
+fn parse_product(toks: &mut Toks) -> Option<CalculationNode> {
+    let mut lhs = parse_calculation_value(toks)?;
+    loop {
+        let op = match peek_past_whitespace(toks).0 {
+            Some('*') => Op::Mul,
+            Some('/') => Op::Div,
+            _ => {
+                toks.reset_cursor();
+                return Some(lhs);
+            }
+        };
+
+        toks.reset_cursor();
+        whitespace(toks);
+        toks.next();
+        whitespace(toks);
+
+        let rhs = parse_calculation_value(toks)?;
+        lhs = CalculationNode::Operation(Box::new(lhs), op, Box::new(rhs));
+    }
+}
+
+fn parse_calculation_value(toks: &mut Toks) -> Option<CalculationNode> {
+    let first = *toks.peek()?;
+    let second = toks.peek_forward(1).map(|t| t.kind);
+    let third = toks.peek_forward(1).map(|t| t.kind);
+    toks.reset_cursor();
+
+    match first.kind {
+        '(' => {
+            toks.next();
+            whitespace(toks);
+            let inner = parse_sum(toks)?;
+            whitespace(toks);
+            match toks.next() {
+                Some(Token { kind: ')', .. }) => Some(inner),
+                _ => None,
+            }
+        }
+        '$' => {
+            let mut buf = vec![toks.next()?];
+            read_name(toks, &mut buf);
+            if buf.len() == 1 {
+                return None;
+            }
+            Some(CalculationNode::Expression(buf))
+        }
+        '#' if second == Some('{') => {
+            toks.next();
+            toks.next();
+            let inner = read_until_closing_curly_brace(toks).ok()?;
+            let close = toks.next()?;
+            if close.kind != '}' {
+                return None;
+            }
+
+            // interpolation followed by more of an identifier, e.g. `#{$a}px`
+            if matches!(toks.peek(), Some(t) if is_name(t.kind) || t.kind == '#') {
+                let mut buf = vec![first, Token::new(first.pos, '{')];
+                buf.extend(inner);
+                buf.push(close);
+                read_name(toks, &mut buf);
+                return parse_function_call(toks, buf);
+            }
+
+            Some(CalculationNode::Interpolation(inner))
+        }
+        '0'..='9' | '.' => parse_number(toks, Vec::new()),
+        '+' | '-'
+            if matches!(second, Some('0'..='9'))
+                || (second == Some('.') && matches!(third, Some('0'..='9'))) =>
+        {
+            let sign = toks.next()?;
+            parse_number(toks, vec![sign])
+        }
+        c if c == '\\' || c == '-' || (is_name_start(c) && !c.is_ascii_digit()) => {
+            let mut buf = Vec::new();
+            read_name(toks, &mut buf);
+            if buf.is_empty() {
+                return None;
+            }
+            parse_function_call(toks, buf)
+        }
+        _ => None,
+    }
+}
+
+/// Read the arguments to a function whose name has already been consumed into
+/// `buf`. Bare identifiers are not allowed in calculations
+fn parse_function_call(toks: &mut Toks, mut buf: Vec<Token>) -> Option<CalculationNode> {
+    match toks.peek() {
+        Some(Token { kind: '(', .. }) => buf.push(toks.next()?),
+        _ => return None,
+    }
+
+    let args = read_until_closing_paren(toks).ok()?;
+    if !matches!(args.last(), Some(Token { kind: ')', .. })) {
+        return None;
+    }
+    buf.extend(args);
+
+    Some(CalculationNode::Expression(buf))
+}
+
+fn parse_number(toks: &mut Toks, mut buf: Vec<Token>) -> Option<CalculationNode> {
+    read_digits(toks, &mut buf);
+
+    if let Some(Token { kind: '.', .. }) = toks.peek() {
+        if !matches!(
+            toks.peek_forward(1),
+            Some(Token {
+                kind: '0'..='9',
+                ..
+            })
+        ) {
+            toks.reset_cursor();
+            return None;
+        }
+        toks.reset_cursor();
+        buf.push(toks.next()?);
+        read_digits(toks, &mut buf);
+    }
+
+    if let Some(Token { kind: 'e', .. }) | Some(Token { kind: 'E', .. }) = toks.peek() {
+        let next = toks.peek_forward(1).map(|t| t.kind);
+        let after = toks.peek_forward(1).map(|t| t.kind);
+        toks.reset_cursor();
+        match (next, after) {
+            (Some('0'..='9'), _) => {
+                buf.push(toks.next()?);
+            }
+            (Some('+'), Some('0'..='9')) | (Some('-'), Some('0'..='9')) => {
+                buf.push(toks.next()?);
+                buf.push(toks.next()?);
+            }
+            _ => {}
+        }
+        read_digits(toks, &mut buf);
+    }
+
+    match toks.peek() {
+        Some(tok @ Token { kind: '%', .. }) => {
+            buf.push(*tok);
+            toks.next();
+        }
+        Some(Token { kind, .. }) if is_name_start(*kind) || *kind == '\\' => {
+            read_name(toks, &mut buf);
+        }
+        Some(Token { kind: '-', .. }) => {
+            let next = toks.peek_forward(1).map(|t| t.kind);
+            toks.reset_cursor();
+            if matches!(next, Some(c) if is_name_start(c) && !c.is_ascii_digit()) {
+                read_name(toks, &mut buf);
+            }
+        }
+        _ => {}
+    }
+
+    Some(CalculationNode::Expression(buf))
+}
+
+fn read_digits(toks: &mut Toks, buf: &mut Vec<Token>) {
+    while let Some(
+        tok @ Token {
+            kind: '0'..='9', ..
+        },
+    ) = toks.peek().copied()
+    {
+        buf.push(tok);
+        toks.next();
+    }
+}
+
+/// Read the characters of an identifier, including escapes and interpolation
+fn read_name(toks: &mut Toks, buf: &mut Vec<Token>) {
+    while let Some(tok) = toks.peek().copied() {
+        match tok.kind {
+            '\\' => {
+                buf.push(tok);
+                toks.next();
+                if let Some(next) = toks.next() {
+                    buf.push(next);
+                }
+            }
+            '#' => {
+                if !matches!(toks.peek_forward(1), Some(Token { kind: '{', .. })) {
+                    toks.reset_cursor();
+                    return;
+                }
+                toks.reset_cursor();
+                buf.push(tok);
+                buf.push(toks.next().unwrap());
+                toks.next();
+                let inner = match read_until_closing_curly_brace(toks) {
+                    Ok(v) => v,
+                    Err(..) => return,
+                };
+                buf.extend(inner);
+                if let Some(close) = toks.next() {
+                    buf.push(close);
+                }
+            }
+            c if is_name(c) => {
+                buf.push(tok);
+                toks.next();
+            }
+            _ => return,
+        }
+    }
+}
+
+fn whitespace(toks: &mut Toks) -> bool {
+    let mut found_whitespace = false;
+    while let Some(tok) = toks.peek() {
+        if !tok.kind.is_whitespace() {
+            break;
+        }
+        found_whitespace = true;
+        toks.next();
+    }
+    found_whitespace
+}
+
+/// Peek the next non-whitespace character without consuming anything,
+/// returning it along with whether or not any whitespace was skipped
+fn peek_past_whitespace(toks: &mut Toks) -> (Option<char>, bool) {
+    let mut found_whitespace = false;
+    while let Some(tok) = toks.peek() {
+        if !tok.kind.is_whitespace() {
+            return (Some(tok.kind), found_whitespace);
+        }
+        found_whitespace = true;
+        toks.advance_cursor();
+    }
+    (None, found_whitespace)
+}
@@ -5,7 +5,7 @@ use codemap::Spanned;
 use crate::{
     error::SassResult,
     utils::{
-        as_hex, hex_char_for, is_name, peek_ident_no_interpolation, peek_until_closing_curly_brace,
+        hex_char_for, is_name, peek_ident_no_interpolation, peek_until_closing_curly_brace,
         peek_whitespace,
     },
     value::Value,
@@ -265,7 +265,6 @@ impl<'a> Parser<'a> {
         Ok(Some(buf))
     }
 
-    #[allow(dead_code, unused_mut, unused_variables, unused_assignments)]
     fn try_parse_min_max_function(&mut self, fn_name: &'static str) -> SassResult<Option<String>> {
         let mut ident = peek_ident_no_interpolation(self.toks, false, self.span_before)?.node;
         ident.make_ascii_lowercase();
@@ -277,7 +276,21 @@ impl<'a> Parser<'a> {
         }
         self.toks.advance_cursor();
         ident.push('(');
-        todo!("special functions inside `min()` or `max()`")
+
+        let mut nesting = 0;
+        while let Some(tok) = self.toks.peek() {
+            let kind = tok.kind;
+            self.toks.advance_cursor();
+            ident.push(kind);
+            match kind {
+                '(' => nesting += 1,
+                ')' if nesting == 0 => return Ok(Some(ident)),
+                ')' => nesting -= 1,
+                _ => {}
+            }
+        }
+
+        Ok(None)
     }
 }
 
@@ -361,11 +374,12 @@ impl<'a> Parser<'a> {
                     Some(t) => t,
                     None => break,
                 };
-                if !next.kind.is_ascii_hexdigit() {
-                    break;
-                }
+                let digit = match next.kind.to_digit(16) {
+                    Some(digit) => digit,
+                    None => break,
+                };
                 value *= 16;
-                value += as_hex(next.kind);
+                value += digit;
                 span = span.merge(next.pos);
                 self.toks.peek_forward(1);
             }
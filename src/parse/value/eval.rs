@@ -1,15 +1,18 @@
 #![allow(unused_variables)]
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, mem, ops::Deref};
 
 use codemap::{Span, Spanned};
 
 use crate::{
-    args::CallArgs,
-    common::{Op, QuoteKind},
+    args::UnevaluatedCallArgs,
+    common::{Identifier, Op, QuoteKind},
+    deprecation::Deprecation,
     error::SassResult,
-    unit::{Unit, UNIT_CONVERSION_TABLE},
+    unit::Unit,
+    utils::did_you_mean,
     value::{SassFunction, Value},
+    Token,
 };
 
 use super::super::Parser;
@@ -18,21 +21,157 @@ use super::super::Parser;
 pub(crate) enum HigherIntermediateValue {
     Literal(Value),
     /// A function that hasn't yet been evaluated
-    Function(SassFunction, CallArgs),
-    BinaryOp(Box<Self>, Op, Box<Self>),
+    Function(SassFunction, UnevaluatedCallArgs),
+    BinaryOp(Operand, Op, Operand),
     UnaryOp(Op, Box<Self>),
     Paren(Box<Self>),
+    /// A variable, which is looked up only if its value is needed
+    Variable(Spanned<Identifier>),
+    /// An expression that hasn't yet been parsed, such as the contents of
+    /// parentheses, which is only evaluated if its value is needed
+    Unevaluated(Vec<Token>),
 }
 
 impl HigherIntermediateValue {
+    /// Construct a binary operation, respecting operator precedence
+    ///
+    /// Binary operations are parsed from left to right, so `left` may be an
+    /// operation with lower precedence than `op`, in which case `op` binds
+    /// to its rightmost operand instead
+    pub fn binary_op(left: Self, op: Op, right: Self) -> Self {
+        match left {
+            HigherIntermediateValue::BinaryOp(left_1, op2, left_2)
+                if op2.precedence() < op.precedence() =>
+            {
+                HigherIntermediateValue::BinaryOp(
+                    left_1,
+                    op2,
+                    Operand::new(Self::binary_op(left_2.into_inner(), op, right)),
+                )
+            }
+            left => HigherIntermediateValue::BinaryOp(Operand::new(left), op, Operand::new(right)),
+        }
+    }
+
     pub const fn span(self, span: Span) -> Spanned<Self> {
         Spanned { node: self, span }
     }
 }
 
+/// An operand of a binary operation
+///
+/// Chains such as `1 + 2 + 3` nest to the left and may be arbitrarily long,
+/// so their operands are dropped in a loop rather than recursively
+#[derive(Clone, Debug)]
+pub(crate) struct Operand(Box<HigherIntermediateValue>);
+
+impl Operand {
+    pub fn new(value: HigherIntermediateValue) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn into_inner(mut self) -> HigherIntermediateValue {
+        mem::replace(&mut *self.0, HigherIntermediateValue::Literal(Value::Null))
+    }
+}
+
+impl Deref for Operand {
+    type Target = HigherIntermediateValue;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for Operand {
+    fn drop(&mut self) {
+        let mut value = mem::replace(&mut *self.0, HigherIntermediateValue::Literal(Value::Null));
+        while let HigherIntermediateValue::BinaryOp(left, ..) = value {
+            value = left.into_inner();
+        }
+    }
+}
+
 impl<'a> Parser<'a> {
-    fn call_function(&mut self, function: SassFunction, args: CallArgs) -> SassResult<Value> {
-        function.call(args, self)
+    fn call_function(
+        &mut self,
+        function: SassFunction,
+        args: UnevaluatedCallArgs,
+    ) -> SassResult<Value> {
+        if matches!(function, SassFunction::Builtin(..))
+            && function.name().as_str() == "if"
+            && !args.0.iter().any(|arg| arg.is_splat)
+        {
+            return self.eval_if(args);
+        }
+
+        let span = args.1;
+        let args = self.eval_call_args(args)?;
+        let value = function.call(args, self)?;
+        self.charge(&value, span)?;
+        Ok(value)
+    }
+
+    /// `if()` is a special form, which only evaluates the branch that is
+    /// chosen by its condition
+    fn eval_if(&mut self, args: UnevaluatedCallArgs) -> SassResult<Value> {
+        let UnevaluatedCallArgs(args, span) = args;
+
+        if args.len() > 3 {
+            return Err((
+                format!("Only 3 arguments allowed, but {} were passed.", args.len()),
+                span,
+            )
+                .into());
+        }
+
+        let mut condition = None;
+        let mut if_true = None;
+        let mut if_false = None;
+
+        for (position, arg) in args.into_iter().enumerate() {
+            match arg.name {
+                Some(name) => match name.as_str() {
+                    "condition" => condition = Some(arg.toks),
+                    "if-true" => if_true = Some(arg.toks),
+                    "if-false" => if_false = Some(arg.toks),
+                    _ => {
+                        return Err((
+                            did_you_mean(
+                                &format!("No argument named ${}.", name),
+                                name.as_str(),
+                                vec!["condition", "if-true", "if-false"],
+                                "$",
+                            ),
+                            span,
+                        )
+                            .into())
+                    }
+                },
+                None => {
+                    let slot = match position {
+                        0 => &mut condition,
+                        1 => &mut if_true,
+                        _ => &mut if_false,
+                    };
+                    if slot.is_none() {
+                        *slot = Some(arg.toks);
+                    }
+                }
+            }
+        }
+
+        let condition = condition.ok_or(("Missing argument $condition.", span))?;
+        let if_true = if_true.ok_or(("Missing argument $if-true.", span))?;
+        let if_false = if_false.ok_or(("Missing argument $if-false.", span))?;
+
+        let branch = if self.parse_value_from_vec(condition, true)?.node.is_true() {
+            if_true
+        } else {
+            if_false
+        };
+
+        Ok(self.parse_value_from_vec(branch, true)?.node)
     }
 }
 
@@ -52,9 +191,23 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 Ok(Value::Dimension(n, u, true))
             }
             HigherIntermediateValue::Literal(v) => Ok(v),
-            HigherIntermediateValue::BinaryOp(v1, op, v2) => self.bin_op(*v1, op, *v2, in_parens),
+            HigherIntermediateValue::BinaryOp(v1, op, v2) => {
+                self.bin_op(v1.into_inner(), op, v2.into_inner(), in_parens)
+            }
             HigherIntermediateValue::UnaryOp(op, val) => self.unary_op(op, *val, in_parens),
             HigherIntermediateValue::Paren(val) => self.eval(*val, true),
+            HigherIntermediateValue::Variable(name) => {
+                let val = self
+                    .parser
+                    .scopes
+                    .get_var(name, self.parser.global_scope)?
+                    .clone();
+                self.eval(HigherIntermediateValue::Literal(val), in_parens)
+            }
+            HigherIntermediateValue::Unevaluated(toks) => {
+                let val = self.parser.parse_value_from_vec(toks, in_parens)?.node;
+                self.eval(HigherIntermediateValue::Literal(val), in_parens)
+            }
             HigherIntermediateValue::Function(function, args) => {
                 self.parser.call_function(function, args)
             }
@@ -68,27 +221,81 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         val2: HigherIntermediateValue,
         in_parens: bool,
     ) -> SassResult<Value> {
-        let mut val1 = self.paren_or_unary(val1, in_parens)?;
-        let val2 = self.paren_or_unary(val2, in_parens)?;
-
-        if let HigherIntermediateValue::BinaryOp(val1_1, op2, val1_2) = val1 {
-            let in_parens = op != Op::Div || op2 != Op::Div;
-            if op2.precedence() >= op.precedence() {
-                val1 = HigherIntermediateValue::Literal(
-                    self.bin_op(*val1_1, op2, *val1_2, in_parens)?,
-                );
-            } else {
-                let val2 =
-                    HigherIntermediateValue::Literal(self.bin_op(*val1_2, op, val2, in_parens)?);
-                return self.bin_op(*val1_1, op2, val2, in_parens);
+        // chains such as `1 + 2 + 3` nest to the left and may be arbitrarily
+        // long, so their left operands are unwound into a stack rather than
+        // evaluated recursively
+        let mut pending = Vec::new();
+        let (mut left, mut op, mut right, mut in_parens) = (val1, op, val2, in_parens);
+        loop {
+            match left {
+                HigherIntermediateValue::BinaryOp(left_1, op2, left_2)
+                    if op2.precedence() >= op.precedence() =>
+                {
+                    pending.push((op, right, in_parens));
+                    in_parens = op != Op::Div || op2 != Op::Div;
+                    left = left_1.into_inner();
+                    op = op2;
+                    right = left_2.into_inner();
+                }
+                HigherIntermediateValue::BinaryOp(left_1, op2, left_2) => {
+                    // the right hand side is left unevaluated so that `and` and `or`
+                    // are able to short circuit
+                    right = HigherIntermediateValue::BinaryOp(left_2, op, Operand::new(right));
+                    left = left_1.into_inner();
+                    op = op2;
+                }
+                _ => break,
             }
         }
 
+        let val1 = self.operand(left, in_parens)?;
+        let mut value = self.eval_bin_op(val1, op, right, in_parens)?;
+        while let Some((op, right, in_parens)) = pending.pop() {
+            value = self.eval_bin_op(value, op, right, in_parens)?;
+        }
+        Ok(value)
+    }
+
+    fn eval_bin_op(
+        &mut self,
+        val1: Value,
+        op: Op,
+        val2: HigherIntermediateValue,
+        in_parens: bool,
+    ) -> SassResult<Value> {
+        let val1 = val1.without_slash();
+
+        match op {
+            Op::And if !val1.is_true() => return Ok(val1),
+            Op::Or if val1.is_true() => return Ok(val1),
+            _ => {}
+        }
+
+        let val2 = match val2 {
+            HigherIntermediateValue::BinaryOp(val2_1, op2, val2_2) => {
+                let in_parens = op != Op::Div || op2 != Op::Div;
+                self.bin_op(val2_1.into_inner(), op2, val2_2.into_inner(), in_parens)?
+            }
+            val2 => self.operand(val2, in_parens)?,
+        }
+        .without_slash();
+
         Ok(match op {
             Op::Plus => self.add(val1, val2)?,
             Op::Minus => self.sub(val1, val2)?,
             Op::Mul => self.mul(val1, val2)?,
-            Op::Div => self.div(val1, val2, in_parens)?,
+            Op::Div => {
+                if self.parser.options.warn_slash_div
+                    || self
+                        .parser
+                        .options
+                        .fatal_deprecations
+                        .contains(&Deprecation::SlashDiv)
+                {
+                    self.warn_slash_div(&val1, &val2, in_parens)?;
+                }
+                self.div(val1, val2, in_parens)?
+            }
             Op::Rem => self.rem(val1, val2)?,
             Op::And => Self::and(val1, val2)?,
             Op::Or => Self::or(val1, val2)?,
@@ -102,13 +309,35 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         })
     }
 
+    /// Warn when `/` is about to perform numeric division, rather than
+    /// acting as a separator
+    fn warn_slash_div(&mut self, left: &Value, right: &Value, in_parens: bool) -> SassResult<()> {
+        if let (Value::Dimension(_, _, should_divide1), Value::Dimension(_, _, should_divide2)) =
+            (left, right)
+        {
+            if *should_divide1 || *should_divide2 || in_parens {
+                let left = left.inspect(self.span)?;
+                let right = right.inspect(self.span)?;
+                self.parser.warn_deprecation(
+                    Deprecation::SlashDiv,
+                    &format!(
+                        "Using / for division outside of calc() is deprecated.\n\nRecommendation: math.div({0}, {1}) or calc({0} / {1})",
+                        left, right,
+                    ),
+                    self.span,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     fn unary_op(
         &mut self,
         op: Op,
         val: HigherIntermediateValue,
         in_parens: bool,
     ) -> SassResult<Value> {
-        let val = self.eval(val, in_parens)?;
+        let val = self.eval(val, in_parens)?.without_slash();
         match op {
             Op::Minus => self.unary_minus(val),
             Op::Not => Self::unary_not(&val),
@@ -120,14 +349,20 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
     fn unary_minus(&self, val: Value) -> SassResult<Value> {
         Ok(match val {
             Value::Dimension(n, u, should_divide) => Value::Dimension(-n, u, should_divide),
-            v => Value::String(format!("-{}", v.to_css_string(self.span)?), QuoteKind::None),
+            v => Value::String(
+                format!("-{}", v.to_css_string(self.span)?).into(),
+                QuoteKind::None,
+            ),
         })
     }
 
     fn unary_plus(&self, val: Value) -> SassResult<Value> {
         Ok(match val {
             v @ Value::Dimension(..) => v,
-            v => Value::String(format!("+{}", v.to_css_string(self.span)?), QuoteKind::None),
+            v => Value::String(
+                format!("+{}", v.to_css_string(self.span)?).into(),
+                QuoteKind::None,
+            ),
         })
     }
 
@@ -135,46 +370,29 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         Ok(Value::bool(!val.is_true()))
     }
 
-    fn paren(&mut self, val: HigherIntermediateValue) -> SassResult<HigherIntermediateValue> {
-        Ok(if let HigherIntermediateValue::Paren(v) = val {
-            HigherIntermediateValue::Literal(self.eval(*v, true)?)
-        } else {
-            val
-        })
-    }
-
-    fn paren_or_unary(
-        &mut self,
-        val: HigherIntermediateValue,
-        in_parens: bool,
-    ) -> SassResult<HigherIntermediateValue> {
-        let val = self.paren(val)?;
-        Ok(match val {
-            HigherIntermediateValue::UnaryOp(op, val) => {
-                HigherIntermediateValue::Literal(self.unary_op(op, *val, in_parens)?)
-            }
+    /// Evaluate an operand of a binary operation which is not itself a
+    /// binary operation
+    fn operand(&mut self, val: HigherIntermediateValue, in_parens: bool) -> SassResult<Value> {
+        match val {
+            HigherIntermediateValue::Literal(v) => Ok(v),
+            HigherIntermediateValue::Paren(v) => self.eval(*v, true),
+            HigherIntermediateValue::UnaryOp(op, val) => self.unary_op(op, *val, in_parens),
             HigherIntermediateValue::Function(function, args) => {
-                HigherIntermediateValue::Literal(self.parser.call_function(function, args)?)
+                self.parser.call_function(function, args)
             }
-            val => val,
-        })
+            HigherIntermediateValue::Variable(name) => Ok(self
+                .parser
+                .scopes
+                .get_var(name, self.parser.global_scope)?
+                .clone()),
+            val @ HigherIntermediateValue::BinaryOp(..)
+            | val @ HigherIntermediateValue::Unevaluated(..) => self.eval(val, in_parens),
+        }
     }
 
-    fn add(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn add(&self, left: Value, right: Value) -> SassResult<Value> {
         Ok(match left {
-            Value::Map(..) | Value::FunctionRef(..) => {
+            Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", left.inspect(self.span)?),
                     self.span,
@@ -183,25 +401,27 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             }
             Value::Important | Value::True | Value::False => match right {
                 Value::String(s, QuoteKind::Quoted) => Value::String(
-                    format!("{}{}", left.to_css_string(self.span)?, s),
+                    format!("{}{}", left.to_css_string(self.span)?, s).into(),
                     QuoteKind::Quoted,
                 ),
-                Value::Null => {
-                    Value::String(left.to_css_string(self.span)?.into_owned(), QuoteKind::None)
-                }
+                Value::Null => Value::String(
+                    left.to_css_string(self.span)?.into_owned().into(),
+                    QuoteKind::None,
+                ),
                 _ => Value::String(
                     format!(
                         "{}{}",
                         left.to_css_string(self.span)?,
                         right.to_css_string(self.span)?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ),
             },
             Value::Null => match right {
                 Value::Null => Value::Null,
                 _ => Value::String(
-                    right.to_css_string(self.span)?.into_owned(),
+                    right.to_css_string(self.span)?.into_owned().into(),
                     QuoteKind::None,
                 ),
             },
@@ -221,23 +441,16 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     } else if unit2 == Unit::None {
                         Value::Dimension(num + num2, unit, true)
                     } else {
-                        Value::Dimension(
-                            num + num2
-                                * UNIT_CONVERSION_TABLE[unit.to_string().as_str()]
-                                    [unit2.to_string().as_str()]
-                                .clone(),
-                            unit,
-                            true,
-                        )
+                        Value::Dimension(num + num2 * unit.conversion_factor(&unit2), unit, true)
                     }
                 }
-                Value::String(s, q) => Value::String(format!("{}{}{}", num, unit, s), q),
-                Value::Null => Value::String(format!("{}{}", num, unit), QuoteKind::None),
+                Value::String(s, q) => Value::String(format!("{}{}{}", num, unit, s).into(), q),
+                Value::Null => Value::String(format!("{}{}", num, unit).into(), QuoteKind::None),
                 Value::True | Value::False | Value::List(..) => Value::String(
-                    format!("{}{}{}", num, unit, right.to_css_string(self.span)?),
+                    format!("{}{}{}", num, unit, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -258,10 +471,10 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 }
             },
             Value::Color(c) => match right {
-                Value::String(s, q) => Value::String(format!("{}{}", c, s), q),
-                Value::Null => Value::String(c.to_string(), QuoteKind::None),
+                Value::String(s, q) => Value::String(format!("{}{}", c, s).into(), q),
+                Value::Null => Value::String(c.to_string().into(), QuoteKind::None),
                 Value::List(..) => Value::String(
-                    format!("{}{}", c, right.to_css_string(self.span)?),
+                    format!("{}{}", c, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
                 _ => {
@@ -277,41 +490,51 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 }
             },
             Value::String(text, quotes) => match right {
-                Value::String(text2, ..) => Value::String(text + &text2, quotes),
-                _ => Value::String(text + &right.to_css_string(self.span)?, quotes),
+                Value::String(text2, ..) => {
+                    Value::String(format!("{}{}", text, text2).into(), quotes)
+                }
+                _ => Value::String(
+                    format!("{}{}", text, right.to_css_string(self.span)?).into(),
+                    quotes,
+                ),
             },
-            Value::List(..) | Value::ArgList(..) => match right {
+            Value::Calculation(..) => match right {
                 Value::String(s, q) => {
-                    Value::String(format!("{}{}", left.to_css_string(self.span)?, s), q)
+                    Value::String(format!("{}{}", left.to_css_string(self.span)?, s).into(), q)
+                }
+                _ => {
+                    return Err((
+                        format!(
+                            "Undefined operation \"{} + {}\".",
+                            left.inspect(self.span)?,
+                            right.inspect(self.span)?
+                        ),
+                        self.span,
+                    )
+                        .into())
+                }
+            },
+            Value::List(..) | Value::ArgList(..) | Value::SlashNumber(..) => match right {
+                Value::String(s, q) => {
+                    Value::String(format!("{}{}", left.to_css_string(self.span)?, s).into(), q)
                 }
                 _ => Value::String(
                     format!(
                         "{}{}",
                         left.to_css_string(self.span)?,
                         right.to_css_string(self.span)?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ),
             },
         })
     }
 
-    fn sub(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn sub(&self, left: Value, right: Value) -> SassResult<Value> {
         Ok(match left {
             Value::Null => Value::String(
-                format!("-{}", right.to_css_string(self.span)?),
+                format!("-{}", right.to_css_string(self.span)?).into(),
                 QuoteKind::None,
             ),
             Value::Dimension(num, unit, _) => match right {
@@ -330,14 +553,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     } else if unit2 == Unit::None {
                         Value::Dimension(num - num2, unit, true)
                     } else {
-                        Value::Dimension(
-                            num - num2
-                                * UNIT_CONVERSION_TABLE[unit.to_string().as_str()]
-                                    [unit2.to_string().as_str()]
-                                .clone(),
-                            unit,
-                            true,
-                        )
+                        Value::Dimension(num - num2 * unit.conversion_factor(&unit2), unit, true)
                     }
                 }
                 Value::List(..)
@@ -345,18 +561,19 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 | Value::Important
                 | Value::True
                 | Value::False
-                | Value::ArgList(..) => Value::String(
-                    format!("{}{}-{}", num, unit, right.to_css_string(self.span)?),
+                | Value::ArgList(..)
+                | Value::SlashNumber(..) => Value::String(
+                    format!("{}{}-{}", num, unit, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
                     )
                         .into())
                 }
-                Value::Color(..) => {
+                Value::Color(..) | Value::Calculation(..) => {
                     return Err((
                         format!(
                             "Undefined operation \"{}{} - {}\".",
@@ -368,13 +585,13 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     )
                         .into())
                 }
-                Value::Null => Value::String(format!("{}{}-", num, unit), QuoteKind::None),
+                Value::Null => Value::String(format!("{}{}-", num, unit).into(), QuoteKind::None),
             },
             Value::Color(c) => match right {
                 Value::String(s, q) => {
-                    Value::String(format!("{}-{}{}{}", c, q, s, q), QuoteKind::None)
+                    Value::String(format!("{}-{}{}{}", c, q, s, q).into(), QuoteKind::None)
                 }
-                Value::Null => Value::String(format!("{}-", c), QuoteKind::None),
+                Value::Null => Value::String(format!("{}-", c).into(), QuoteKind::None),
                 Value::Dimension(..) | Value::Color(..) => {
                     return Err((
                         format!(
@@ -387,7 +604,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         .into())
                 }
                 _ => Value::String(
-                    format!("{}-{}", c, right.to_css_string(self.span)?),
+                    format!("{}-{}", c, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
             },
@@ -396,16 +613,17 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     "{}-{}",
                     left.to_css_string(self.span)?,
                     right.to_css_string(self.span)?
-                ),
+                )
+                .into(),
                 QuoteKind::None,
             ),
             _ => match right {
                 Value::String(s, q) => Value::String(
-                    format!("{}-{}{}{}", left.to_css_string(self.span)?, q, s, q),
+                    format!("{}-{}{}{}", left.to_css_string(self.span)?, q, s, q).into(),
                     QuoteKind::None,
                 ),
                 Value::Null => Value::String(
-                    format!("{}-", left.to_css_string(self.span)?),
+                    format!("{}-", left.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
                 _ => Value::String(
@@ -413,26 +631,15 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         "{}-{}",
                         left.to_css_string(self.span)?,
                         right.to_css_string(self.span)?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ),
             },
         })
     }
 
-    fn mul(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn mul(&self, left: Value, right: Value) -> SassResult<Value> {
         Ok(match left {
             Value::Dimension(num, unit, _) => match right {
                 Value::Dimension(num2, unit2, _) => {
@@ -441,7 +648,8 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     } else if unit2 == Unit::None {
                         Value::Dimension(num * num2, unit, true)
                     } else {
-                        Value::Dimension(num * num2, unit * unit2, true)
+                        let (unit, factor) = unit.mul_with_factor(&unit2);
+                        Value::Dimension(num * num2 * factor, unit, true)
                     }
                 }
                 _ => {
@@ -471,23 +679,10 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         })
     }
 
-    fn div(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-        in_parens: bool,
-    ) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    pub fn div(&self, left: Value, right: Value, in_parens: bool) -> SassResult<Value> {
         Ok(match left {
             Value::Null => Value::String(
-                format!("/{}", right.to_css_string(self.span)?),
+                format!("/{}", right.to_css_string(self.span)?).into(),
                 QuoteKind::None,
             ),
             Value::Dimension(num, unit, should_divide1) => match right {
@@ -497,52 +692,41 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         if unit == unit2 {
                             Value::Dimension(num / num2, Unit::None, true)
 
-                        // `unit(1 / 1em)` => `"em^-1"`
-                        } else if unit == Unit::None {
-                            Value::Dimension(num / num2, Unit::None / unit2, true)
-
                         // `unit(1em / 1)` => `"em"`
                         } else if unit2 == Unit::None {
                             Value::Dimension(num / num2, unit, true)
 
                         // `unit(1in / 1px)` => `""`
-                        } else if unit.comparable(&unit2) {
-                            Value::Dimension(
-                                num / (num2
-                                    * UNIT_CONVERSION_TABLE[unit.to_string().as_str()]
-                                        [unit2.to_string().as_str()]
-                                    .clone()),
-                                Unit::None,
-                                true,
-                            )
                         // `unit(1em / 1px)` => `"em/px"`
-                        // todo: this should probably be its own variant
-                        // within the `Value` enum
+                        // `unit(1 / 1em)` => `"em^-1"`
                         } else {
-                            // todo: remember to account for `Mul` and `Div`
-                            todo!("non-comparable inverse units")
+                            let (unit, factor) = unit.div_with_factor(&unit2);
+                            Value::Dimension(num / num2 * factor, unit, true)
                         }
                     } else {
                         Value::String(
-                            format!("{}{}/{}{}", num, unit, num2, unit2),
+                            format!("{}{}/{}{}", num, unit, num2, unit2).into(),
                             QuoteKind::None,
                         )
                     }
                 }
-                Value::String(s, q) => {
-                    Value::String(format!("{}{}/{}{}{}", num, unit, q, s, q), QuoteKind::None)
-                }
+                Value::String(s, q) => Value::String(
+                    format!("{}{}/{}{}{}", num, unit, q, s, q).into(),
+                    QuoteKind::None,
+                ),
                 Value::List(..)
                 | Value::True
                 | Value::False
                 | Value::Important
                 | Value::Color(..)
-                | Value::ArgList(..) => Value::String(
-                    format!("{}{}/{}", num, unit, right.to_css_string(self.span)?),
+                | Value::ArgList(..)
+                | Value::Calculation(..)
+                | Value::SlashNumber(..) => Value::String(
+                    format!("{}{}/{}", num, unit, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
-                Value::Null => Value::String(format!("{}{}/", num, unit), QuoteKind::None),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Null => Value::String(format!("{}{}/", num, unit).into(), QuoteKind::None),
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -552,9 +736,9 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             },
             Value::Color(c) => match right {
                 Value::String(s, q) => {
-                    Value::String(format!("{}/{}{}{}", c, q, s, q), QuoteKind::None)
+                    Value::String(format!("{}/{}{}{}", c, q, s, q).into(), QuoteKind::None)
                 }
-                Value::Null => Value::String(format!("{}/", c), QuoteKind::None),
+                Value::Null => Value::String(format!("{}/", c).into(), QuoteKind::None),
                 Value::Dimension(..) | Value::Color(..) => {
                     return Err((
                         format!(
@@ -567,13 +751,13 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         .into())
                 }
                 _ => Value::String(
-                    format!("{}/{}", c, right.to_css_string(self.span)?),
+                    format!("{}/{}", c, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
             },
             Value::String(s1, q1) => match right {
                 Value::String(s2, q2) => Value::String(
-                    format!("{}{}{}/{}{}{}", q1, s1, q1, q2, s2, q2),
+                    format!("{}{}{}/{}{}{}", q1, s1, q1, q2, s2, q2).into(),
                     QuoteKind::None,
                 ),
                 Value::Important
@@ -582,12 +766,16 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 | Value::Dimension(..)
                 | Value::Color(..)
                 | Value::List(..)
-                | Value::ArgList(..) => Value::String(
-                    format!("{}{}{}/{}", q1, s1, q1, right.to_css_string(self.span)?),
+                | Value::ArgList(..)
+                | Value::Calculation(..)
+                | Value::SlashNumber(..) => Value::String(
+                    format!("{}{}{}/{}", q1, s1, q1, right.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
-                Value::Null => Value::String(format!("{}{}{}/", q1, s1, q1), QuoteKind::None),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Null => {
+                    Value::String(format!("{}{}{}/", q1, s1, q1).into(), QuoteKind::None)
+                }
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -597,11 +785,11 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             },
             _ => match right {
                 Value::String(s, q) => Value::String(
-                    format!("{}/{}{}{}", left.to_css_string(self.span)?, q, s, q),
+                    format!("{}/{}{}{}", left.to_css_string(self.span)?, q, s, q).into(),
                     QuoteKind::None,
                 ),
                 Value::Null => Value::String(
-                    format!("{}/", left.to_css_string(self.span)?),
+                    format!("{}/", left.to_css_string(self.span)?).into(),
                     QuoteKind::None,
                 ),
                 _ => Value::String(
@@ -609,26 +797,15 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         "{}/{}",
                         left.to_css_string(self.span)?,
                         right.to_css_string(self.span)?
-                    ),
+                    )
+                    .into(),
                     QuoteKind::None,
                 ),
             },
         })
     }
 
-    fn rem(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn rem(&self, left: Value, right: Value) -> SassResult<Value> {
         Ok(match left {
             Value::Dimension(n, u, _) => match right {
                 Value::Dimension(n2, u2, _) => {
@@ -673,68 +850,23 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         })
     }
 
-    fn and(left: HigherIntermediateValue, right: HigherIntermediateValue) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn and(left: Value, right: Value) -> SassResult<Value> {
         Ok(if left.is_true() { right } else { left })
     }
 
-    fn or(left: HigherIntermediateValue, right: HigherIntermediateValue) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn or(left: Value, right: Value) -> SassResult<Value> {
         Ok(if left.is_true() { left } else { right })
     }
 
-    pub fn equal(left: HigherIntermediateValue, right: HigherIntermediateValue) -> Value {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    pub fn equal(left: Value, right: Value) -> Value {
         Value::bool(left == right)
     }
 
-    fn not_equal(left: HigherIntermediateValue, right: HigherIntermediateValue) -> Value {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn not_equal(left: Value, right: Value) -> Value {
         Value::bool(left.not_equals(&right))
     }
 
-    fn cmp(
-        &self,
-        left: HigherIntermediateValue,
-        op: Op,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
-        let left = match left {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
-        let right = match right {
-            HigherIntermediateValue::Literal(v) => v,
-            v => panic!("{:?}", v),
-        };
+    fn cmp(&self, left: Value, op: Op, right: Value) -> SassResult<Value> {
         let ordering = match left {
             Value::Dimension(num, unit, _) => match &right {
                 Value::Dimension(num2, unit2, _) => {
@@ -748,12 +880,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     if &unit == unit2 || unit == Unit::None || unit2 == &Unit::None {
                         num.cmp(num2)
                     } else {
-                        num.cmp(
-                            &(num2.clone()
-                                * UNIT_CONVERSION_TABLE[unit.to_string().as_str()]
-                                    [unit2.to_string().as_str()]
-                                .clone()),
-                        )
+                        num.cmp(&(*num2 * unit.conversion_factor(unit2)))
                     }
                 }
                 v => {
@@ -803,35 +930,19 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         })
     }
 
-    pub fn greater_than(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
+    pub fn greater_than(&self, left: Value, right: Value) -> SassResult<Value> {
         self.cmp(left, Op::GreaterThan, right)
     }
 
-    fn greater_than_or_equal(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
+    pub fn greater_than_or_equal(&self, left: Value, right: Value) -> SassResult<Value> {
         self.cmp(left, Op::GreaterThanEqual, right)
     }
 
-    pub fn less_than(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
+    pub fn less_than(&self, left: Value, right: Value) -> SassResult<Value> {
         self.cmp(left, Op::LessThan, right)
     }
 
-    fn less_than_or_equal(
-        &self,
-        left: HigherIntermediateValue,
-        right: HigherIntermediateValue,
-    ) -> SassResult<Value> {
+    fn less_than_or_equal(&self, left: Value, right: Value) -> SassResult<Value> {
         self.cmp(left, Op::LessThanEqual, right)
     }
 }
@@ -1,5 +1,6 @@
-pub(crate) use eval::{HigherIntermediateValue, ValueVisitor};
+pub(crate) use eval::ValueVisitor;
 
+mod calculation;
 mod css_function;
 mod eval;
 mod parse;
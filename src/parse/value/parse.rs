@@ -1,24 +1,21 @@
-use std::{iter::Iterator, mem};
-
-use num_bigint::BigInt;
-use num_rational::{BigRational, Rational64};
-use num_traits::{pow, One, ToPrimitive};
+use std::{iter::Iterator, mem, rc::Rc};
 
 use codemap::{Span, Spanned};
 
 use peekmore::PeekMore;
 
 use crate::{
-    builtin::GLOBAL_FUNCTIONS,
+    builtin::{Module, GLOBAL_FUNCTIONS},
     color::{Color, NAMED_COLORS},
     common::{Brackets, Identifier, ListSeparator, Op, QuoteKind},
+    deprecation::Deprecation,
     error::SassResult,
     unit::Unit,
     utils::{
-        devour_whitespace, eat_number, read_until_char, read_until_closing_paren,
-        read_until_closing_square_brace, IsWhitespace,
+        devour_whitespace, did_you_mean, eat_number, ensure_stack, read_until_char,
+        read_until_closing_paren, read_until_closing_square_brace, IsWhitespace,
     },
-    value::{Number, SassFunction, SassMap, Value},
+    value::{CalculationName, Number, SassFunction, SassMap, SlashNumber, Value},
     Token,
 };
 
@@ -105,8 +102,9 @@ impl<'a> Parser<'a> {
                                         span = span.merge(a.span);
                                         a.node
                                     })
-                                    .map(|a| ValueVisitor::new(iter.parser, span).eval(a, in_paren))
-                                    .collect::<SassResult<Vec<Value>>>()?,
+                                    .map(|a| iter.parser.eval_list_element(a, span, in_paren))
+                                    .collect::<SassResult<Vec<Value>>>()
+                                    .map(Rc::new)?,
                                 ListSeparator::Space,
                                 Brackets::None,
                             ))
@@ -122,9 +120,11 @@ impl<'a> Parser<'a> {
                                 Value::List(v, sep, Brackets::None) => {
                                     Value::List(v, sep, Brackets::Bracketed)
                                 }
-                                v => {
-                                    Value::List(vec![v], ListSeparator::Space, Brackets::Bracketed)
-                                }
+                                v => Value::List(
+                                    Rc::new(vec![v]),
+                                    ListSeparator::Space,
+                                    Brackets::Bracketed,
+                                ),
                             },
                         )
                         .span(val.span),
@@ -140,7 +140,7 @@ impl<'a> Parser<'a> {
             }
         }
 
-        Ok(if !comma_separated.is_empty() {
+        let value = if !comma_separated.is_empty() {
             if space_separated.len() == 1 {
                 comma_separated.push(space_separated.pop().unwrap());
             } else if !space_separated.is_empty() {
@@ -148,8 +148,9 @@ impl<'a> Parser<'a> {
                     HigherIntermediateValue::Literal(Value::List(
                         space_separated
                             .into_iter()
-                            .map(|a| ValueVisitor::new(self, span).eval(a.node, in_paren))
-                            .collect::<SassResult<Vec<Value>>>()?,
+                            .map(|a| self.eval_list_element(a.node, span, in_paren))
+                            .collect::<SassResult<Vec<Value>>>()
+                            .map(Rc::new)?,
                         ListSeparator::Space,
                         Brackets::None,
                     ))
@@ -160,7 +161,8 @@ impl<'a> Parser<'a> {
                 comma_separated
                     .into_iter()
                     .map(|a| ValueVisitor::new(self, span).eval(a.node, in_paren))
-                    .collect::<SassResult<Vec<Value>>>()?,
+                    .collect::<SassResult<Vec<Value>>>()
+                    .map(Rc::new)?,
                 ListSeparator::Comma,
                 Brackets::None,
             )
@@ -173,13 +175,72 @@ impl<'a> Parser<'a> {
             Value::List(
                 space_separated
                     .into_iter()
-                    .map(|a| ValueVisitor::new(self, span).eval(a.node, in_paren))
-                    .collect::<SassResult<Vec<Value>>>()?,
+                    .map(|a| self.eval_list_element(a.node, span, in_paren))
+                    .collect::<SassResult<Vec<Value>>>()
+                    .map(Rc::new)?,
                 ListSeparator::Space,
                 Brackets::None,
             )
             .span(span)
-        })
+        };
+
+        self.charge(&value.node, span)?;
+
+        Ok(value)
+    }
+
+    /// Evaluate a single element of a space separated list
+    ///
+    /// Inside parentheses and function arguments, `/` between two number
+    /// literals in a space separated list may separate values rather than
+    /// divide them, as in `hwb(120deg 30% 40% / 0.5)`, so the operands are
+    /// kept alongside the quotient
+    fn eval_list_element(
+        &mut self,
+        value: HigherIntermediateValue,
+        span: Span,
+        in_paren: bool,
+    ) -> SassResult<Value> {
+        /// A number literal, optionally negated, which may appear on either
+        /// side of a slash separator
+        fn slash_operand(value: &HigherIntermediateValue) -> Option<Value> {
+            match value {
+                HigherIntermediateValue::Literal(v @ Value::Dimension(_, _, false)) => {
+                    Some(v.clone())
+                }
+                HigherIntermediateValue::UnaryOp(Op::Minus, v) => match &**v {
+                    HigherIntermediateValue::Literal(Value::Dimension(n, u, false)) => {
+                        Some(Value::Dimension(-*n, u.clone(), false))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+
+        match value {
+            HigherIntermediateValue::BinaryOp(left, Op::Div, right) if in_paren => {
+                match (slash_operand(&left), slash_operand(&right)) {
+                    (Some(numerator), Some(denominator)) => {
+                        let quotient = ValueVisitor::new(self, span).div(
+                            numerator.clone(),
+                            denominator.clone(),
+                            true,
+                        )?;
+                        Ok(Value::SlashNumber(Box::new(SlashNumber::new(
+                            quotient,
+                            numerator,
+                            denominator,
+                        ))))
+                    }
+                    _ => ValueVisitor::new(self, span).eval(
+                        HigherIntermediateValue::BinaryOp(left, Op::Div, right),
+                        in_paren,
+                    ),
+                }
+            }
+            value => ValueVisitor::new(self, span).eval(value, in_paren),
+        }
     }
 
     pub(crate) fn parse_value_from_vec(
@@ -187,22 +248,33 @@ impl<'a> Parser<'a> {
         toks: Vec<Token>,
         in_paren: bool,
     ) -> SassResult<Spanned<Value>> {
-        Parser {
-            toks: &mut toks.into_iter().peekmore(),
-            map: self.map,
-            path: self.path,
-            scopes: self.scopes,
-            global_scope: self.global_scope,
-            super_selectors: self.super_selectors,
-            span_before: self.span_before,
-            content: self.content,
-            flags: self.flags,
-            at_root: self.at_root,
-            at_root_has_selector: self.at_root_has_selector,
-            extender: self.extender,
-            content_scopes: self.content_scopes,
-        }
-        .parse_value(in_paren)
+        let depth = self.nested_depth(self.span_before)?;
+        ensure_stack(|| {
+            Parser {
+                toks: &mut toks.into_iter().peekmore(),
+                map: self.map,
+                path: self.path,
+                scopes: self.scopes,
+                global_scope: self.global_scope,
+                super_selectors: self.super_selectors,
+                span_before: self.span_before,
+                content: self.content,
+                flags: self.flags,
+                at_root: self.at_root,
+                at_root_has_selector: self.at_root_has_selector,
+                extender: self.extender,
+                options: self.options,
+                content_scopes: self.content_scopes,
+                depth,
+                import_stack: self.import_stack,
+                budget: self.budget,
+                call_stack: self.call_stack,
+                deprecations: self.deprecations,
+                errors: self.errors,
+                dependencies: self.dependencies,
+            }
+            .parse_value(in_paren)
+        })
     }
 
     fn parse_ident_value(&mut self) -> SassResult<Spanned<IntermediateValue>> {
@@ -210,6 +282,10 @@ impl<'a> Parser<'a> {
 
         self.span_before = span;
 
+        if self.next_is_module_member() {
+            return self.parse_module_member(Spanned { node: s, span });
+        }
+
         let lower = s.to_ascii_lowercase();
 
         if lower == "progid" && matches!(self.toks.peek(), Some(Token { kind: ':', .. })) {
@@ -219,7 +295,7 @@ impl<'a> Parser<'a> {
             s.push_str(&self.parse_progid()?);
             return Ok(Spanned {
                 node: IntermediateValue::Value(HigherIntermediateValue::Literal(Value::String(
-                    s,
+                    s.into(),
                     QuoteKind::None,
                 ))),
                 span,
@@ -232,10 +308,13 @@ impl<'a> Parser<'a> {
             if lower == "min" {
                 match self.try_parse_min_max("min", true)? {
                     Some(val) => {
-                        self.toks.truncate_iterator_to_cursor();
-                        self.toks.next();
+                        self.toks.reset_cursor();
+                        let toks = read_until_closing_paren(self.toks)?;
+                        let value = self
+                            .parse_calculation(CalculationName::Min, toks)?
+                            .unwrap_or(Value::String(val.into(), QuoteKind::None));
                         return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                            Value::String(val, QuoteKind::None),
+                            value,
                         ))
                         .span(span));
                     }
@@ -246,10 +325,13 @@ impl<'a> Parser<'a> {
             } else if lower == "max" {
                 match self.try_parse_min_max("max", true)? {
                     Some(val) => {
-                        self.toks.truncate_iterator_to_cursor();
-                        self.toks.next();
+                        self.toks.reset_cursor();
+                        let toks = read_until_closing_paren(self.toks)?;
+                        let value = self
+                            .parse_calculation(CalculationName::Max, toks)?
+                            .unwrap_or(Value::String(val.into(), QuoteKind::None));
                         return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                            Value::String(val, QuoteKind::None),
+                            value,
                         ))
                         .span(span));
                     }
@@ -269,16 +351,41 @@ impl<'a> Parser<'a> {
             ) {
                 Some(f) => f,
                 None => {
-                    if let Some(f) = GLOBAL_FUNCTIONS.get(as_ident.as_str()) {
+                    if let Some(f) = GLOBAL_FUNCTIONS
+                        .get(as_ident.as_str())
+                        .cloned()
+                        .or_else(|| self.global_scope.get_global_module_fn(as_ident))
+                    {
+                        self.warn_global_builtin(as_ident, span)?;
                         return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
-                            SassFunction::Builtin(f.clone(), as_ident),
-                            self.parse_call_args()?,
+                            SassFunction::Builtin(f, as_ident),
+                            self.parse_unevaluated_call_args()?,
                         ))
                         .span(span));
                     } else {
                         // check for special cased CSS functions
                         match lower.as_str() {
-                            "calc" | "element" | "expression" => {
+                            "calc" => {
+                                return Ok(IntermediateValue::Value(
+                                    HigherIntermediateValue::Literal(
+                                        self.parse_calculation_or_css_function(
+                                            CalculationName::Calc,
+                                        )?,
+                                    ),
+                                )
+                                .span(span));
+                            }
+                            "clamp" => {
+                                return Ok(IntermediateValue::Value(
+                                    HigherIntermediateValue::Literal(
+                                        self.parse_calculation_or_css_function(
+                                            CalculationName::Clamp,
+                                        )?,
+                                    ),
+                                )
+                                .span(span));
+                            }
+                            "element" | "expression" => {
                                 s = lower;
                                 self.parse_calc_args(&mut s)?;
                             }
@@ -290,14 +397,14 @@ impl<'a> Parser<'a> {
                         }
 
                         return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                            Value::String(s, QuoteKind::None),
+                            Value::String(s.into(), QuoteKind::None),
                         ))
                         .span(span));
                     }
                 }
             };
 
-            let call_args = self.parse_call_args()?;
+            let call_args = self.parse_unevaluated_call_args()?;
             return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
                 SassFunction::UserDefined(Box::new(func), as_ident),
                 call_args,
@@ -324,13 +431,122 @@ impl<'a> Parser<'a> {
             "and" => IntermediateValue::Op(Op::And),
             "or" => IntermediateValue::Op(Op::Or),
             _ => IntermediateValue::Value(HigherIntermediateValue::Literal(Value::String(
-                s,
+                s.into(),
                 QuoteKind::None,
             ))),
         }
         .span(span))
     }
 
+    /// Warns about a call to a global function which is also in a built-in module
+    fn warn_global_builtin(&mut self, name: Identifier, span: Span) -> SassResult<()> {
+        // functions which share their name with a plain CSS function are not deprecated
+        if matches!(
+            name.as_str(),
+            "min" | "max" | "round" | "abs" | "hwb" | "alpha" | "opacity" | "grayscale" | "invert"
+        ) || self.global_scope.get_global_module_fn(name).is_some()
+        {
+            return Ok(());
+        }
+
+        match Module::member_for_global(name.as_str()) {
+            Some((module, member)) => self.warn_deprecation(
+                Deprecation::GlobalBuiltin,
+                &format!(
+                    "Global built-in functions are deprecated and will be removed in Dart Sass 3.0.0.\nUse {}.{} instead.\n\nMore info and automated migrator: https://sass-lang.com/d/import",
+                    module.default_namespace(),
+                    member
+                ),
+                span,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the identifier just parsed is the namespace of a module
+    /// member, such as the `math` in `math.$pi` or `math.div(1, 2)`
+    fn next_is_module_member(&mut self) -> bool {
+        if !matches!(self.toks.peek(), Some(Token { kind: '.', .. })) {
+            return false;
+        }
+
+        let next = self.toks.peek_forward(1).map(|tok| tok.kind);
+        self.toks.reset_cursor();
+
+        matches!(next, Some(c) if c == '$' || c == '_' || c == '-' || c.is_alphabetic())
+    }
+
+    fn parse_module_member(
+        &mut self,
+        namespace: Spanned<String>,
+    ) -> SassResult<Spanned<IntermediateValue>> {
+        // consume the `.`
+        self.toks.next();
+
+        let module = self.global_scope.get_module(Spanned {
+            node: Identifier::from(&namespace.node),
+            span: namespace.span,
+        })?;
+
+        if let Some(Token { kind: '$', .. }) = self.toks.peek() {
+            self.toks.next();
+            let Spanned { node: name, span } = self.parse_identifier_no_interpolation(false)?;
+            let span = namespace.span.merge(span);
+
+            return match module.get_var(Identifier::from(&name)) {
+                Some(value) => Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
+                    value,
+                ))
+                .span(span)),
+                None => Err((
+                    did_you_mean(
+                        "Undefined variable.",
+                        &name,
+                        module.var_names(),
+                        &format!("{}.$", namespace.node),
+                    ),
+                    span,
+                )
+                    .into()),
+            };
+        }
+
+        let Spanned { node: name, span } = self.parse_identifier_no_interpolation(false)?;
+        let span = namespace.span.merge(span);
+
+        match self.toks.peek() {
+            Some(Token { kind: '(', .. }) => {
+                self.toks.next();
+            }
+            Some(Token { pos, .. }) => return Err(("expected \"(\".", *pos).into()),
+            None => return Err(("expected \"(\".", span).into()),
+        }
+
+        let name = Identifier::from(name);
+
+        let func = match module.get_fn(name) {
+            Some(f) => f,
+            None => {
+                return Err((
+                    did_you_mean(
+                        "Undefined function.",
+                        name.as_str(),
+                        module.fn_names(),
+                        &format!("{}.", namespace.node),
+                    ),
+                    span,
+                )
+                    .into())
+            }
+        };
+
+        Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
+            SassFunction::Builtin(func, name),
+            self.parse_unevaluated_call_args()?,
+        ))
+        .span(span))
+    }
+
     fn next_is_hypen(&mut self) -> bool {
         self.toks.peek_forward(1).is_some()
             && matches!(self.toks.peek().unwrap().kind, '-' | '_' | 'a'..='z' | 'A'..='Z')
@@ -389,67 +605,8 @@ impl<'a> Parser<'a> {
                     Unit::None
                 };
 
-                let n = if val.dec_len == 0 {
-                    if val.num.len() <= 18 && val.times_ten.is_empty() {
-                        let n = Rational64::new_raw(parse_i64(&val.num), 1);
-                        return Some(Ok(IntermediateValue::Value(
-                            HigherIntermediateValue::Literal(Value::Dimension(
-                                Number::new_small(n),
-                                unit,
-                                false,
-                            )),
-                        )
-                        .span(span)));
-                    }
-                    BigRational::new_raw(val.num.parse::<BigInt>().unwrap(), BigInt::one())
-                } else {
-                    if val.num.len() <= 18 && val.times_ten.is_empty() {
-                        let n = Rational64::new(parse_i64(&val.num), pow(10, val.dec_len));
-                        return Some(Ok(IntermediateValue::Value(
-                            HigherIntermediateValue::Literal(Value::Dimension(
-                                Number::new_small(n),
-                                unit,
-                                false,
-                            )),
-                        )
-                        .span(span)));
-                    }
-                    BigRational::new(val.num.parse().unwrap(), pow(BigInt::from(10), val.dec_len))
-                };
-
-                if val.times_ten.is_empty() {
-                    return Some(Ok(IntermediateValue::Value(
-                        HigherIntermediateValue::Literal(Value::Dimension(
-                            Number::new_big(n),
-                            unit,
-                            false,
-                        )),
-                    )
-                    .span(span)));
-                }
-
-                let times_ten = pow(
-                    BigInt::from(10),
-                    match val
-                        .times_ten
-                        .parse::<BigInt>()
-                        .unwrap()
-                        .to_usize()
-                        .ok_or(("Exponent too large (expected usize).", span))
-                    {
-                        Ok(v) => v,
-                        Err(e) => return Some(Err(e.into())),
-                    },
-                );
-
-                let times_ten = if val.times_ten_is_postive {
-                    BigRational::new_raw(times_ten, BigInt::one())
-                } else {
-                    BigRational::new(BigInt::one(), times_ten)
-                };
-
                 IntermediateValue::Value(HigherIntermediateValue::Literal(Value::Dimension(
-                    Number::new_big(n * times_ten),
+                    Number(val.to_f64()),
                     unit,
                     false,
                 )))
@@ -472,6 +629,8 @@ impl<'a> Parser<'a> {
             }
             '&' => {
                 let span = self.toks.next().unwrap().pos();
+                // the parent selector depends on where a function is called
+                self.global_scope.function_cache().taint();
                 if self.super_selectors.is_empty() && !self.at_root_has_selector && !self.at_root {
                     IntermediateValue::Value(HigherIntermediateValue::Literal(Value::Null))
                         .span(span)
@@ -520,7 +679,7 @@ impl<'a> Parser<'a> {
                 }
                 if inner.is_empty() {
                     IntermediateValue::Value(HigherIntermediateValue::Literal(Value::List(
-                        Vec::new(),
+                        Rc::new(Vec::new()),
                         ListSeparator::Space,
                         Brackets::Bracketed,
                     )))
@@ -535,13 +694,7 @@ impl<'a> Parser<'a> {
                     Ok(v) => v.map_node(|i| i.into()),
                     Err(e) => return Some(Err(e)),
                 };
-                IntermediateValue::Value(HigherIntermediateValue::Literal(
-                    match self.scopes.get_var(val, self.global_scope) {
-                        Ok(v) => v.clone(),
-                        Err(e) => return Some(Err(e)),
-                    },
-                ))
-                .span(val.span)
+                IntermediateValue::Value(HigherIntermediateValue::Variable(val)).span(val.span)
             }
             '+' => {
                 let span = self.toks.next().unwrap().pos();
@@ -672,14 +825,14 @@ impl<'a> Parser<'a> {
                 s.push_str(&ident.node);
             } else {
                 return Ok(Spanned {
-                    node: Value::String(format!("#{}", ident.node), QuoteKind::None),
+                    node: Value::String(format!("#{}", ident.node).into(), QuoteKind::None),
                     span: ident.span,
                 });
             }
         }
         let v = match u32::from_str_radix(&s[1..], 16) {
             Ok(a) => a,
-            Err(_) => return Ok(Value::String(s, QuoteKind::None).span(self.span_before)),
+            Err(_) => return Ok(Value::String(s.into(), QuoteKind::None).span(self.span_before)),
         };
         let (red, green, blue, alpha) = match s.len().saturating_sub(1) {
             3 => (
@@ -772,11 +925,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                 let right = self.single_value(in_paren)?;
                 if let Some(left) = space_separated.pop() {
                     space_separated.push(Spanned {
-                        node: HigherIntermediateValue::BinaryOp(
-                            Box::new(left.node),
-                            op.node,
-                            Box::new(right.node),
-                        ),
+                        node: HigherIntermediateValue::binary_op(left.node, op.node, right.node),
                         span: left.span.merge(right.span),
                     });
                 } else {
@@ -788,7 +937,8 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                                 ValueVisitor::new(self.parser, right.span)
                                     .eval(right.node, false)?
                                     .to_css_string(right.span)?
-                            ),
+                            )
+                            .into(),
                             QuoteKind::None,
                         )),
                         span: op.span.merge(right.span),
@@ -800,11 +950,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                     self.whitespace();
                     let right = self.single_value(in_paren)?;
                     space_separated.push(Spanned {
-                        node: HigherIntermediateValue::BinaryOp(
-                            Box::new(left.node),
-                            op.node,
-                            Box::new(right.node),
-                        ),
+                        node: HigherIntermediateValue::binary_op(left.node, op.node, right.node),
                         span: left.span.merge(right.span),
                     });
                 } else {
@@ -821,10 +967,8 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                     let right = self.single_value(in_paren)?;
                     if let Some(left) = space_separated.pop() {
                         space_separated.push(Spanned {
-                            node: HigherIntermediateValue::BinaryOp(
-                                Box::new(left.node),
-                                op.node,
-                                Box::new(right.node),
+                            node: HigherIntermediateValue::binary_op(
+                                left.node, op.node, right.node,
                             ),
                             span: left.span.merge(right.span),
                         });
@@ -842,96 +986,25 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                     );
                 }
             }
-            Op::And => {
+            Op::And | Op::Or => {
                 self.whitespace();
-                // special case when the value is literally "and"
+                // special case when the value is literally "and" or "or"
                 if self.peek().is_none() {
                     space_separated.push(
                         HigherIntermediateValue::Literal(Value::String(
-                            op.to_string(),
+                            op.to_string().into(),
                             QuoteKind::None,
                         ))
                         .span(op.span),
                     );
                 } else if let Some(left) = space_separated.pop() {
-                    self.whitespace();
-                    if ValueVisitor::new(self.parser, left.span)
-                        .eval(left.node.clone(), false)?
-                        .is_true()
-                    {
-                        let right = self.single_value(in_paren)?;
-                        space_separated.push(
-                            HigherIntermediateValue::BinaryOp(
-                                Box::new(left.node),
-                                op.node,
-                                Box::new(right.node),
-                            )
-                            .span(left.span.merge(right.span)),
-                        );
-                    } else {
-                        // we explicitly ignore errors here as a workaround for short circuiting
-                        while let Some(value) = self.peek() {
-                            if let Ok(Spanned {
-                                node: IntermediateValue::Comma,
-                                ..
-                            }) = value
-                            {
-                                break;
-                            }
-                            self.next();
-                        }
-                        space_separated.push(left);
-                    }
-                } else {
-                    return Err(("Expected expression.", op.span).into());
-                }
-            }
-            Op::Or => {
-                self.whitespace();
-                // special case when the value is literally "or"
-                if self.peek().is_none() {
+                    // the right hand side is only evaluated if needed; see
+                    // `ValueVisitor::bin_op`
+                    let right = self.single_value(in_paren)?;
                     space_separated.push(
-                        HigherIntermediateValue::Literal(Value::String(
-                            op.to_string(),
-                            QuoteKind::None,
-                        ))
-                        .span(op.span),
-                    );
-                } else if let Some(left) = space_separated.pop() {
-                    self.whitespace();
-                    if ValueVisitor::new(self.parser, left.span)
-                        .eval(left.node.clone(), false)?
-                        .is_true()
-                    {
-                        // we explicitly ignore errors here as a workaround for short circuiting
-                        while let Some(value) = self.peek() {
-                            match value {
-                                Ok(Spanned {
-                                    node: IntermediateValue::Comma,
-                                    ..
-                                }) => break,
-                                Ok(..) => {
-                                    self.next();
-                                }
-                                Err(..) => {
-                                    if let Some(v) = self.next() {
-                                        v?;
-                                    }
-                                }
-                            }
-                        }
-                        space_separated.push(left);
-                    } else {
-                        let right = self.single_value(in_paren)?;
-                        space_separated.push(
-                            HigherIntermediateValue::BinaryOp(
-                                Box::new(left.node),
-                                op.node,
-                                Box::new(right.node),
-                            )
+                        HigherIntermediateValue::binary_op(left.node, op.node, right.node)
                             .span(left.span.merge(right.span)),
-                        );
-                    }
+                    );
                 } else {
                     return Err(("Expected expression.", op.span).into());
                 }
@@ -941,12 +1014,8 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                     self.whitespace();
                     let right = self.single_value(in_paren)?;
                     space_separated.push(
-                        HigherIntermediateValue::BinaryOp(
-                            Box::new(left.node),
-                            op.node,
-                            Box::new(right.node),
-                        )
-                        .span(left.span.merge(right.span)),
+                        HigherIntermediateValue::binary_op(left.node, op.node, right.node)
+                            .span(left.span.merge(right.span)),
                     );
                 } else {
                     return Err(("Expected expression.", op.span).into());
@@ -956,6 +1025,16 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
         Ok(())
     }
 
+    /// Parse the operand of a unary operator, which may itself be a unary
+    /// operation
+    fn unary_operand(&mut self, in_paren: bool) -> SassResult<Spanned<HigherIntermediateValue>> {
+        let depth = self.parser.depth;
+        self.parser.depth = self.parser.nested_depth(self.parser.span_before)?;
+        let val = ensure_stack(|| self.single_value(in_paren));
+        self.parser.depth = depth;
+        val
+    }
+
     fn single_value(&mut self, in_paren: bool) -> SassResult<Spanned<HigherIntermediateValue>> {
         let next = self
             .next()
@@ -965,7 +1044,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
             IntermediateValue::Op(op) => match op {
                 Op::Minus => {
                     self.whitespace();
-                    let val = self.single_value(in_paren)?;
+                    let val = self.unary_operand(in_paren)?;
                     Spanned {
                         node: HigherIntermediateValue::UnaryOp(Op::Minus, Box::new(val.node)),
                         span: next.span.merge(val.span),
@@ -973,7 +1052,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                 }
                 Op::Not => {
                     self.whitespace();
-                    let val = self.single_value(in_paren)?;
+                    let val = self.unary_operand(in_paren)?;
                     Spanned {
                         node: HigherIntermediateValue::UnaryOp(Op::Not, Box::new(val.node)),
                         span: next.span.merge(val.span),
@@ -981,11 +1060,11 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                 }
                 Op::Plus => {
                     self.whitespace();
-                    self.single_value(in_paren)?
+                    self.unary_operand(in_paren)?
                 }
                 Op::Div => {
                     self.whitespace();
-                    let val = self.single_value(in_paren)?;
+                    let val = self.unary_operand(in_paren)?;
                     Spanned {
                         node: HigherIntermediateValue::Literal(Value::String(
                             format!(
@@ -993,7 +1072,8 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                                 ValueVisitor::new(self.parser, val.span)
                                     .eval(val.node, false)?
                                     .to_css_string(val.span)?
-                            ),
+                            )
+                            .into(),
                             QuoteKind::None,
                         )),
                         span: next.span.merge(val.span),
@@ -1025,7 +1105,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                 let v = self.parser.parse_value_from_vec(t, in_paren)?;
                 HigherIntermediateValue::Literal(match v.node {
                     Value::List(v, sep, Brackets::None) => Value::List(v, sep, Brackets::Bracketed),
-                    v => Value::List(vec![v], ListSeparator::Space, Brackets::Bracketed),
+                    v => Value::List(Rc::new(vec![v]), ListSeparator::Space, Brackets::Bracketed),
                 })
                 .span(v.span)
             }
@@ -1048,7 +1128,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
     ) -> SassResult<Spanned<HigherIntermediateValue>> {
         if t.is_empty() {
             return Ok(HigherIntermediateValue::Literal(Value::List(
-                Vec::new(),
+                Rc::new(Vec::new()),
                 ListSeparator::Space,
                 Brackets::None,
             ))
@@ -1058,19 +1138,22 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
         let paren_toks = &mut t.node.into_iter().peekmore();
 
         let mut map = SassMap::new();
-        let key = self
-            .parser
-            .parse_value_from_vec(read_until_char(paren_toks, ':')?, true)?;
+        let key_toks = read_until_char(paren_toks, ':')?;
 
+        // a parenthesized expression is only evaluated once its value is needed,
+        // so that e.g. `$map and (map-get($map, a) == 1)` doesn't throw an error
+        // when `$map` is null
         if paren_toks.peek().is_none() {
             return Ok(Spanned {
-                node: HigherIntermediateValue::Paren(Box::new(HigherIntermediateValue::Literal(
-                    key.node,
-                ))),
-                span: key.span,
+                node: HigherIntermediateValue::Paren(Box::new(
+                    HigherIntermediateValue::Unevaluated(key_toks),
+                )),
+                span: t.span,
             });
         }
 
+        let key = self.parser.parse_value_from_vec(key_toks, true)?;
+
         let val = self
             .parser
             .parse_value_from_vec(read_until_char(paren_toks, ',')?, true)?;
@@ -1120,9 +1203,3 @@ impl IsWhitespace for SassResult<Spanned<IntermediateValue>> {
         }
     }
 }
-
-fn parse_i64(s: &str) -> i64 {
-    s.as_bytes()
-        .iter()
-        .fold(0, |total, this| total * 10 + i64::from(this - b'0'))
-}
@@ -1,12 +1,16 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{Hash, Hasher},
+};
 
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 
 use crate::{
     atrule::{Function, Mixin},
-    builtin::GLOBAL_FUNCTIONS,
+    builtin::{Builtin, Module, GLOBAL_FUNCTIONS},
     common::Identifier,
     error::SassResult,
+    utils::did_you_mean,
     value::Value,
 };
 
@@ -15,6 +19,12 @@ pub(crate) struct Scope {
     vars: BTreeMap<Identifier, Spanned<Value>>,
     mixins: BTreeMap<Identifier, Mixin>,
     functions: BTreeMap<Identifier, Function>,
+    /// Built-in modules loaded with `@use`, keyed by namespace
+    modules: BTreeMap<Identifier, Module>,
+    /// Built-in modules loaded with `@use ... as *`
+    global_modules: Vec<Module>,
+    /// Only used by the global scope
+    function_cache: FunctionCache,
 }
 
 impl Scope {
@@ -26,6 +36,9 @@ impl Scope {
             vars: BTreeMap::new(),
             mixins: BTreeMap::new(),
             functions: BTreeMap::new(),
+            modules: BTreeMap::new(),
+            global_modules: Vec::new(),
+            function_cache: FunctionCache::default(),
         }
     }
 
@@ -37,6 +50,7 @@ impl Scope {
     }
 
     pub fn insert_var(&mut self, s: Identifier, v: Spanned<Value>) -> Option<Spanned<Value>> {
+        self.function_cache.invalidate();
         self.vars.insert(s, v)
     }
 
@@ -52,6 +66,7 @@ impl Scope {
     }
 
     pub fn insert_mixin<T: Into<Identifier>>(&mut self, s: T, v: Mixin) -> Option<Mixin> {
+        self.function_cache.invalidate();
         self.mixins.insert(s.into(), v)
     }
 
@@ -64,6 +79,7 @@ impl Scope {
     }
 
     pub fn insert_fn<T: Into<Identifier>>(&mut self, s: T, v: Function) -> Option<Function> {
+        self.function_cache.invalidate();
         self.functions.insert(s.into(), v)
     }
 
@@ -74,13 +90,151 @@ impl Scope {
         self.functions.contains_key(&name)
     }
 
+    pub fn insert_module(&mut self, namespace: Identifier, module: Module) -> Option<Module> {
+        self.function_cache.invalidate();
+        self.modules.insert(namespace, module)
+    }
+
+    pub fn get_module(&self, namespace: Spanned<Identifier>) -> SassResult<Module> {
+        match self.modules.get(&namespace.node) {
+            Some(module) => Ok(*module),
+            None => Err((
+                format!(
+                    "There is no module with the namespace \"{}\".",
+                    namespace.node
+                ),
+                namespace.span,
+            )
+                .into()),
+        }
+    }
+
+    pub fn insert_global_module(&mut self, module: Module) {
+        self.function_cache.invalidate();
+        if !self.global_modules.contains(&module) {
+            self.global_modules.push(module);
+        }
+    }
+
+    /// Look up a function from a module loaded with `@use ... as *`
+    pub fn get_global_module_fn(&self, name: Identifier) -> Option<Builtin> {
+        self.global_modules
+            .iter()
+            .find_map(|module| module.get_fn(name))
+    }
+
+    /// Whether a module loaded with `@use ... as *` exposes the builtin mixin `name`
+    pub fn global_module_mixin_exists(&self, name: Identifier) -> bool {
+        self.global_modules
+            .iter()
+            .any(|module| module.mixin_exists(name))
+    }
+
+    pub fn function_cache(&mut self) -> &mut FunctionCache {
+        &mut self.function_cache
+    }
+
     fn merge(&mut self, other: Scope) {
+        self.function_cache.invalidate();
         self.vars.extend(other.vars);
         self.mixins.extend(other.mixins);
         self.functions.extend(other.functions);
     }
 }
 
+/// Memoized results of calls to user-defined functions declared at the root
+/// of the stylesheet
+///
+/// Such functions can only see their arguments and the global scope, so their
+/// results may be reused until the global scope changes, as long as the call
+/// had no side effects, such as `@debug` or assigning a global variable
+#[derive(Debug, Default)]
+pub(crate) struct FunctionCache {
+    results: HashMap<CallKey, Value>,
+    /// Incremented whenever the global scope changes or a call is tainted, so
+    /// that we can tell whether that happened during a call
+    generation: usize,
+}
+
+impl FunctionCache {
+    pub fn get(&self, key: &CallKey) -> Option<Value> {
+        self.results.get(key).cloned()
+    }
+
+    pub const fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Memoize the result of a call, unless the global scope changed or the
+    /// call was tainted since `generation` was taken
+    pub fn insert(&mut self, key: CallKey, value: Value, generation: usize) {
+        if generation == self.generation {
+            self.results.insert(key, value);
+        }
+    }
+
+    /// Prevents the calls currently being evaluated from being memoized,
+    /// either because they had a side effect or because they depend on more
+    /// than their arguments and the global scope (e.g. by using `&`)
+    pub fn taint(&mut self) {
+        self.generation += 1;
+    }
+
+    fn invalidate(&mut self) {
+        self.generation += 1;
+        if !self.results.is_empty() {
+            self.results.clear();
+        }
+    }
+}
+
+/// A call to a user-defined function, keyed on the position of the function
+/// declaration and its evaluated arguments
+///
+/// Arguments are compared by identity rather than Sass equality, since e.g.
+/// `f(1in)` and `f(96px)` may return different values
+#[derive(Debug)]
+pub(crate) struct CallKey {
+    function: Span,
+    args: Vec<(Identifier, Value)>,
+}
+
+impl CallKey {
+    pub fn new(function: Span, args: &Scope) -> Self {
+        CallKey {
+            function,
+            args: args
+                .vars
+                .iter()
+                .map(|(name, value)| (*name, value.node.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl PartialEq for CallKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.function == other.function
+            && self.args.len() == other.args.len()
+            && self
+                .args
+                .iter()
+                .zip(&other.args)
+                .all(|((name1, value1), (name2, value2))| {
+                    name1 == name2 && value1.is_identical(value2)
+                })
+    }
+}
+
+impl Eq for CallKey {}
+
+impl Hash for CallKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.function.hash(state);
+        self.args.hash(state);
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct Scopes(Vec<Scope>);
 
@@ -101,11 +255,20 @@ impl Scopes {
         self.0.pop();
     }
 
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Exits every scope entered after the first `len`
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
     pub fn merge(&mut self, other: Scope) {
         if let Some(scope) = self.0.last_mut() {
             scope.merge(other)
         } else {
-            panic!()
+            self.0.push(other);
         }
     }
 }
@@ -154,7 +317,10 @@ impl Scopes {
                 scope.insert_var(s, v)
             }
         } else {
-            panic!()
+            let mut scope = Scope::new();
+            scope.insert_var(s, v);
+            self.0.push(scope);
+            None
         }
     }
 
@@ -168,7 +334,20 @@ impl Scopes {
                 return scope.get_var(name);
             }
         }
-        global_scope.get_var(name)
+        if global_scope.var_exists(name.node) {
+            return global_scope.get_var(name);
+        }
+        let names = self.visible_names(global_scope, |scope| scope.vars.keys());
+        Err((
+            did_you_mean(
+                "Undefined variable.",
+                name.node.as_str(),
+                names.iter().map(Identifier::as_str),
+                "$",
+            ),
+            name.span,
+        )
+            .into())
     }
 
     pub fn var_exists(&self, name: Identifier, global_scope: &Scope) -> bool {
@@ -204,7 +383,26 @@ impl Scopes {
                 return scope.get_mixin(name);
             }
         }
-        global_scope.get_mixin(name)
+        if global_scope.mixin_exists(name.node) {
+            return global_scope.get_mixin(name);
+        }
+        Err((
+            self.undefined_mixin("Undefined mixin.", name.node, global_scope),
+            name.span,
+        )
+            .into())
+    }
+
+    /// An error message for an undefined mixin, suggesting the closest
+    /// visible mixin if there is one
+    pub fn undefined_mixin(&self, message: &str, name: Identifier, global_scope: &Scope) -> String {
+        let names = self.visible_names(global_scope, |scope| scope.mixins.keys());
+        did_you_mean(
+            message,
+            name.as_str(),
+            names.iter().map(Identifier::as_str),
+            "",
+        )
     }
 
     pub fn mixin_exists(&self, name: Identifier, global_scope: &Scope) -> bool {
@@ -249,6 +447,42 @@ impl Scopes {
                 return true;
             }
         }
-        global_scope.fn_exists(name) || GLOBAL_FUNCTIONS.contains_key(name.as_str())
+        global_scope.fn_exists(name)
+            || GLOBAL_FUNCTIONS.contains_key(name.as_str())
+            || global_scope.get_global_module_fn(name).is_some()
+    }
+
+    /// An error message for an undefined function, suggesting the closest
+    /// visible user-defined or builtin function if there is one
+    pub fn undefined_fn(&self, message: &str, name: Identifier, global_scope: &Scope) -> String {
+        let names = self.visible_names(global_scope, |scope| scope.functions.keys());
+        let mut candidates: Vec<&str> = names.iter().map(Identifier::as_str).collect();
+        for name in GLOBAL_FUNCTIONS.keys().copied().chain(
+            global_scope
+                .global_modules
+                .iter()
+                .flat_map(|module| module.fn_names()),
+        ) {
+            candidates.push(name);
+        }
+        did_you_mean(message, name.as_str(), candidates, "")
+    }
+}
+
+impl Scopes {
+    /// The names of the members of every scope visible from the innermost
+    /// one, including the global scope, from innermost to outermost
+    fn visible_names<'a, F, I>(&'a self, global_scope: &'a Scope, names: F) -> Vec<Identifier>
+    where
+        F: Fn(&'a Scope) -> I,
+        I: Iterator<Item = &'a Identifier>,
+    {
+        self.0
+            .iter()
+            .rev()
+            .chain(std::iter::once(global_scope))
+            .flat_map(names)
+            .copied()
+            .collect()
     }
 }
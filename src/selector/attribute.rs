@@ -137,7 +137,7 @@ impl Attribute {
             q @ '\'' | q @ '"' => {
                 parser.toks.next();
                 match parser.parse_quoted_string(q)?.node {
-                    Value::String(s, ..) => s,
+                    Value::String(s, ..) => s.to_string(),
                     _ => unreachable!(),
                 }
             }
@@ -193,7 +193,7 @@ impl Display for Attribute {
                 // or having special emitter for quoted strings?
                 // (also avoids the clone because we can consume/modify self)
                 f.write_str(
-                    &Value::String(self.value.clone(), QuoteKind::Quoted)
+                    &Value::String(self.value.as_str().into(), QuoteKind::Quoted)
                         .to_css_string(self.span)
                         .unwrap(),
                 )?;
@@ -186,7 +186,9 @@ fn weave_parents(
     for group in lcs {
         choices.push(
             chunks(&mut groups_one, &mut groups_two, |sequence| {
-                complex_is_parent_superselector(sequence.get(0).unwrap().clone(), group.clone())
+                sequence.front().map_or(true, |first| {
+                    complex_is_parent_superselector(first.clone(), group.clone())
+                })
             })
             .into_iter()
             .map(|chunk| chunk.into_iter().flatten().collect())
@@ -340,20 +342,6 @@ fn merge_final_combinators(
 ) -> Option<Vec<Vec<Vec<ComplexSelectorComponent>>>> {
     let mut result = result.unwrap_or_default();
 
-    if (components_one.is_empty()
-        || !components_one
-            .get(components_one.len() - 1)
-            .unwrap()
-            .is_combinator())
-        && (components_two.is_empty()
-            || !components_two
-                .get(components_two.len() - 1)
-                .unwrap()
-                .is_combinator())
-    {
-        return Some(Vec::from(result));
-    }
-
     let mut combinators_one = Vec::new();
 
     while let Some(ComplexSelectorComponent::Combinator(combinator)) =
@@ -395,21 +383,9 @@ fn merge_final_combinators(
         return Some(Vec::from(result));
     }
 
-    let combinator_one = if combinators_one.is_empty() {
-        None
-    } else {
-        combinators_one.first()
-    };
-
-    let combinator_two = if combinators_two.is_empty() {
-        None
-    } else {
-        combinators_two.first()
-    };
-
     // This code looks complicated, but it's actually just a bunch of special
     // cases for interactions between different combinators.
-    match (combinator_one, combinator_two) {
+    match (combinators_one.first(), combinators_two.first()) {
         (Some(combinator_one), Some(combinator_two)) => {
             let compound_one = match components_one.pop_back() {
                 Some(ComplexSelectorComponent::Compound(c)) => c,
@@ -574,7 +550,7 @@ fn merge_final_combinators(
             ]]);
             merge_final_combinators(components_one, components_two, Some(result))
         }
-        (None, None) => todo!("the above, but we dont have access to combinator_two"),
+        (None, None) => Some(Vec::from(result)),
     }
 }
 
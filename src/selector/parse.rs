@@ -4,7 +4,7 @@ use crate::{
     common::unvendor,
     error::SassResult,
     parse::Parser,
-    utils::{is_name, is_name_start, read_until_closing_paren},
+    utils::{ensure_stack, is_name, is_name_start, read_until_closing_paren},
     Token,
 };
 
@@ -84,6 +84,15 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
         Ok(tmp)
     }
 
+    /// Parse the selector argument of a pseudo selector, such as `:not(...)`
+    fn parse_nested_selector_list(&mut self) -> SassResult<SelectorList> {
+        let depth = self.parser.depth;
+        self.parser.depth = self.parser.nested_depth(self.span)?;
+        let selector = ensure_stack(|| self.parse_selector_list());
+        self.parser.depth = depth;
+        selector
+    }
+
     fn parse_selector_list(&mut self) -> SassResult<SelectorList> {
         let mut components = vec![self.parse_complex_selector(false)?];
 
@@ -315,14 +324,14 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
         if element {
             // todo: lowercase?
             if SELECTOR_PSEUDO_ELEMENTS.contains(&unvendored) {
-                selector = Some(Box::new(self.parse_selector_list()?));
+                selector = Some(Box::new(self.parse_nested_selector_list()?));
                 self.parser.whitespace();
                 self.expect_closing_paren()?;
             } else {
                 argument = Some(self.declaration_value()?.into_boxed_str());
             }
         } else if SELECTOR_PSEUDO_CLASSES.contains(&unvendored) {
-            selector = Some(Box::new(self.parse_selector_list()?));
+            selector = Some(Box::new(self.parse_nested_selector_list()?));
             self.parser.whitespace();
             self.expect_closing_paren()?;
         } else if unvendored == "nth-child" || unvendored == "nth-last-child" {
@@ -335,7 +344,7 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
                     self.expect_identifier("of")?;
                     this_arg.push_str(" of");
                     self.parser.whitespace();
-                    selector = Some(Box::new(self.parse_selector_list()?));
+                    selector = Some(Box::new(self.parse_nested_selector_list()?));
                 }
                 _ => {}
             }
@@ -75,7 +75,7 @@ impl fmt::Display for SimpleSelector {
             Self::Pseudo(pseudo) => write!(f, "{}", pseudo),
             Self::Type(name) => write!(f, "{}", name),
             Self::Attribute(attr) => write!(f, "{}", attr),
-            Self::Parent(..) => todo!(),
+            Self::Parent(suffix) => write!(f, "&{}", suffix.as_deref().unwrap_or("")),
         }
     }
 }
@@ -117,12 +117,12 @@ impl SimpleSelector {
             | Self::Type(..)
             | Self::Id(..)
             | Self::Class(..)
-            | Self::Attribute(..) => false,
+            | Self::Attribute(..)
+            | Self::Parent(..) => false,
             Self::Pseudo(Pseudo { name, selector, .. }) => {
                 name != "not" && selector.as_ref().map_or(false, |sel| sel.is_invisible())
             }
             Self::Placeholder(..) => true,
-            Self::Parent(..) => todo!(),
         }
     }
 
@@ -217,8 +217,11 @@ impl SimpleSelector {
     }
 
     fn unify_universal(self, mut compound: Vec<Self>) -> Option<Vec<Self>> {
-        if let Self::Universal(..) | Self::Type(..) = compound[0] {
-            let mut unified = vec![self.unify_universal_and_element(&compound[0])?];
+        if let (Some(element1), Some(element2)) = (
+            self.universal_or_element(),
+            compound[0].universal_or_element(),
+        ) {
+            let mut unified = vec![Self::unify_universal_and_element(element1, element2)?];
             unified.extend(compound.into_iter().skip(1));
             return Some(unified);
         }
@@ -237,39 +240,28 @@ impl SimpleSelector {
         Some(vec![self])
     }
 
+    /// The namespace and name of a `SimpleSelector::Universal` (whose name is
+    /// empty) or a `SimpleSelector::Type`
+    fn universal_or_element(&self) -> Option<(&Namespace, &str)> {
+        match self {
+            Self::Universal(namespace) => Some((namespace, "")),
+            Self::Type(name) => Some((&name.namespace, &name.ident)),
+            _ => None,
+        }
+    }
+
     /// Returns a `SimpleSelector` that matches only elements that are matched by
-    /// both `selector1` and `selector2`, which must both be either
-    /// `SimpleSelector::Universal`s or `SimpleSelector::Type`s.
+    /// both of the universal or type selectors with the given namespaces and
+    /// names (see `SimpleSelector::universal_or_element`).
     ///
     /// If no such selector can be produced, returns `None`.
-    fn unify_universal_and_element(&self, other: &Self) -> Option<Self> {
-        let namespace1;
-        let name1;
-        if let SimpleSelector::Type(name) = self.clone() {
-            namespace1 = name.namespace;
-            name1 = name.ident;
-        } else if let SimpleSelector::Universal(namespace) = self.clone() {
-            namespace1 = namespace;
-            name1 = String::new();
-        } else {
-            todo!("ArgumentError.value(selector1, 'selector1', 'must be a UniversalSelector or a TypeSelector')")
-        }
-
-        let namespace2;
-        let mut name2 = String::new();
-
-        if let SimpleSelector::Universal(namespace) = other {
-            namespace2 = namespace.clone();
-        } else if let SimpleSelector::Type(name) = other {
-            namespace2 = name.namespace.clone();
-            name2 = name.ident.clone();
-        } else {
-            todo!("ArgumentError.value(selector2, 'selector2', 'must be a UniversalSelector or a TypeSelector');")
-        }
-
-        let namespace = if namespace1 == namespace2 || namespace2 == Namespace::Asterisk {
+    fn unify_universal_and_element(
+        (namespace1, name1): (&Namespace, &str),
+        (namespace2, name2): (&Namespace, &str),
+    ) -> Option<Self> {
+        let namespace = if namespace1 == namespace2 || namespace2 == &Namespace::Asterisk {
             namespace1
-        } else if namespace1 == Namespace::Asterisk {
+        } else if namespace1 == &Namespace::Asterisk {
             namespace2
         } else {
             return None;
@@ -284,18 +276,21 @@ impl SimpleSelector {
         };
 
         Some(if name.is_empty() {
-            SimpleSelector::Universal(namespace)
+            SimpleSelector::Universal(namespace.clone())
         } else {
             SimpleSelector::Type(QualifiedName {
-                namespace,
-                ident: name,
+                namespace: namespace.clone(),
+                ident: name.to_owned(),
             })
         })
     }
 
     fn unify_type(self, mut compound: Vec<Self>) -> Option<Vec<Self>> {
-        if let Self::Universal(..) | Self::Type(..) = compound[0] {
-            let mut unified = vec![self.unify_universal_and_element(&compound[0])?];
+        if let (Some(element1), Some(element2)) = (
+            self.universal_or_element(),
+            compound[0].universal_or_element(),
+        ) {
+            let mut unified = vec![Self::unify_universal_and_element(element1, element2)?];
             unified.extend(compound.into_iter().skip(1));
             Some(unified)
         } else {
@@ -1,9 +1,8 @@
-use std::{
-    fmt,
-    ops::{Div, Mul},
-};
+use std::fmt;
 
-use crate::interner::InternedString;
+use num_traits::One;
+
+use crate::{interner::InternedString, value::Number};
 
 pub(crate) use conversion::UNIT_CONVERSION_TABLE;
 
@@ -113,7 +112,7 @@ pub(crate) enum Unit {
     /// Units divided by each other
     Div(Box<DivUnit>),
 }
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub(crate) enum UnitKind {
     Absolute,
     FontRelative,
@@ -140,104 +139,164 @@ impl DivUnit {
 
 impl fmt::Display for DivUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.numer == Unit::None {
-            write!(f, "{}^-1", self.denom)
-        } else {
-            write!(f, "{}/{}", self.numer, self.denom)
+        match (&self.numer, &self.denom) {
+            (Unit::None, Unit::Mul(..)) => write!(f, "({})^-1", self.denom),
+            (Unit::None, _) => write!(f, "{}^-1", self.denom),
+            _ => write!(f, "{}/{}", self.numer, self.denom),
         }
     }
 }
 
-#[allow(clippy::match_same_arms)]
-impl Mul<Unit> for DivUnit {
-    type Output = Unit;
-    fn mul(self, rhs: Unit) -> Self::Output {
-        match rhs {
-            Unit::Mul(..) => todo!(),
-            Unit::Div(..) => todo!(),
-            Unit::None => todo!(),
-            _ => {
-                if self.denom == rhs {
-                    self.numer
-                } else {
-                    match self.denom {
-                        Unit::Mul(..) => todo!(),
-                        Unit::Div(..) => unreachable!(),
-                        _ => match self.numer {
-                            Unit::Mul(..) => todo!(),
-                            Unit::Div(..) => unreachable!(),
-                            Unit::None => {
-                                let numer = Unit::Mul(Box::new(vec![rhs]));
-                                Unit::Div(Box::new(DivUnit::new(numer, self.denom)))
-                            }
-                            _ => {
-                                let numer = Unit::Mul(Box::new(vec![self.numer, rhs]));
-                                Unit::Div(Box::new(DivUnit::new(numer, self.denom)))
-                            }
-                        },
-                    }
-                }
-            }
+impl Unit {
+    /// Build the canonical unit for a number with the given numerator and
+    /// denominator units
+    pub fn from_numer_and_denom(mut numer: Vec<Unit>, denom: Vec<Unit>) -> Self {
+        if denom.is_empty() {
+            return match numer.len() {
+                0 => Unit::None,
+                1 => numer.pop().unwrap(),
+                _ => Unit::Mul(Box::new(numer)),
+            };
         }
-    }
-}
 
-// impl Div<Unit> for DivUnit {
-//     type Output = Unit;
-//     fn div(self, rhs: Unit) -> Self::Output {
-//         todo!()
-//     }
-// }
+        Unit::Div(Box::new(DivUnit::new(
+            Unit::from_numer_and_denom(numer, Vec::new()),
+            Unit::from_numer_and_denom(denom, Vec::new()),
+        )))
+    }
 
-impl Mul<Unit> for Unit {
-    type Output = Unit;
-    fn mul(self, rhs: Unit) -> Self::Output {
+    /// Split this unit into its numerator and denominator units
+    ///
+    /// `px*em/s` => `([px, em], [s])`
+    pub fn numer_and_denom(&self) -> (Vec<Unit>, Vec<Unit>) {
         match self {
-            Unit::Mul(u) => match rhs {
-                Unit::Mul(u2) => {
-                    let mut unit1 = *u;
-                    unit1.extend_from_slice(&*u2);
-                    Unit::Mul(Box::new(unit1))
-                }
-                Unit::Div(..) => todo!(),
-                _ => {
-                    let mut unit1 = *u;
-                    unit1.push(rhs);
-                    Unit::Mul(Box::new(unit1))
+            Unit::None => (Vec::new(), Vec::new()),
+            Unit::Mul(units) => (units.to_vec(), Vec::new()),
+            Unit::Div(div) => (div.numer.numer_and_denom().0, div.denom.numer_and_denom().0),
+            _ => (vec![self.clone()], Vec::new()),
+        }
+    }
+
+    /// Multiply two units together, cancelling out any units that appear in
+    /// one numerator and the other denominator
+    ///
+    /// Compatible units (e.g. `in` and `px`) also cancel out, so this returns the
+    /// factor that the product of the two numbers must be multiplied by to
+    /// account for the conversion
+    pub fn mul_with_factor(&self, rhs: &Unit) -> (Unit, Number) {
+        let (mut numer, mut denom) = self.numer_and_denom();
+        let (rhs_numer, rhs_denom) = rhs.numer_and_denom();
+        let mut factor = Number::one();
+
+        for unit in rhs_numer {
+            match denom
+                .iter()
+                .position(|d| unit.simple_conversion_factor(d).is_some())
+            {
+                Some(idx) => {
+                    let d = denom.remove(idx);
+                    factor /= unit.simple_conversion_factor(&d).unwrap();
                 }
-            },
-            Unit::Div(div) => *div * rhs,
-            _ => match rhs {
-                Unit::Mul(u2) => {
-                    let mut unit1 = vec![self];
-                    unit1.extend_from_slice(&*u2);
-                    Unit::Mul(Box::new(unit1))
+                None => numer.push(unit),
+            }
+        }
+
+        for unit in rhs_denom {
+            match numer
+                .iter()
+                .position(|n| n.simple_conversion_factor(&unit).is_some())
+            {
+                Some(idx) => {
+                    let n = numer.remove(idx);
+                    factor /= n.simple_conversion_factor(&unit).unwrap();
                 }
-                Unit::Div(..) => todo!(),
-                _ => Unit::Mul(Box::new(vec![self, rhs])),
-            },
+                None => denom.push(unit),
+            }
         }
+
+        (Unit::from_numer_and_denom(numer, denom), factor)
     }
-}
 
-impl Div<Unit> for Unit {
-    type Output = Unit;
-    fn div(self, rhs: Unit) -> Self::Output {
-        if let Unit::Div(..) = self {
-            todo!()
-        } else if let Unit::Div(..) = rhs {
-            todo!()
-        } else {
-            Unit::Div(Box::new(DivUnit::new(self, rhs)))
+    /// Divide `self` by `rhs`, with the same cancellation as `Unit::mul_with_factor`
+    pub fn div_with_factor(&self, rhs: &Unit) -> (Unit, Number) {
+        let (numer, denom) = rhs.numer_and_denom();
+        self.mul_with_factor(&Unit::from_numer_and_denom(denom, numer))
+    }
+
+    /// The number that a value with the unit `from` must be multiplied by to
+    /// get the same value in this unit
+    ///
+    /// The two units are assumed to be comparable
+    pub fn conversion_factor(&self, from: &Unit) -> Number {
+        if let Some(factor) = self.simple_conversion_factor(from) {
+            return factor;
         }
+
+        self.complex_conversion_factor(from)
+            .unwrap_or_else(Number::one)
+    }
+
+    /// The conversion factor between two units with no numerator or
+    /// denominator of their own, if they can be converted between at all
+    fn simple_conversion_factor(&self, from: &Unit) -> Option<Number> {
+        if self == from {
+            return Some(Number::one());
+        }
+
+        match (self.kind(), from.kind()) {
+            (UnitKind::Absolute, UnitKind::Absolute)
+            | (UnitKind::Angle, UnitKind::Angle)
+            | (UnitKind::Time, UnitKind::Time)
+            | (UnitKind::Frequency, UnitKind::Frequency)
+            | (UnitKind::Resolution, UnitKind::Resolution) => UNIT_CONVERSION_TABLE
+                .get(self.to_string().as_str())?
+                .get(from.to_string().as_str())
+                .cloned(),
+            _ => None,
+        }
+    }
+
+    /// The conversion factor between two units with multiple numerators or
+    /// denominators, if every unit in one has a compatible unit in the other
+    fn complex_conversion_factor(&self, from: &Unit) -> Option<Number> {
+        let (numer, denom) = self.numer_and_denom();
+        let (mut from_numer, mut from_denom) = from.numer_and_denom();
+
+        if numer.len() != from_numer.len() || denom.len() != from_denom.len() {
+            return None;
+        }
+
+        let mut factor = Number::one();
+
+        for unit in numer {
+            let idx = from_numer
+                .iter()
+                .position(|u| unit.simple_conversion_factor(u).is_some())?;
+            factor *= unit.simple_conversion_factor(&from_numer.remove(idx))?;
+        }
+
+        for unit in denom {
+            let idx = from_denom
+                .iter()
+                .position(|u| unit.simple_conversion_factor(u).is_some())?;
+            factor /= unit.simple_conversion_factor(&from_denom.remove(idx))?;
+        }
+
+        Some(factor)
+    }
+
+    /// Whether this unit has more than one numerator or any denominators
+    pub fn is_complex(&self) -> bool {
+        matches!(self, Unit::Mul(..) | Unit::Div(..))
     }
-}
 
-impl Unit {
     pub fn comparable(&self, other: &Unit) -> bool {
-        if other == &Unit::None {
+        if self == &Unit::None || other == &Unit::None {
             return true;
         }
+        if self.is_complex() || other.is_complex() {
+            return self.complex_conversion_factor(other).is_some();
+        }
         match self.kind() {
             UnitKind::FontRelative | UnitKind::ViewportRelative | UnitKind::Other => self == other,
             UnitKind::None => true,
@@ -245,8 +304,27 @@ impl Unit {
         }
     }
 
+    /// Whether numbers with these units may be combined by the browser in a
+    /// calculation, even if they can't be combined at compile time
+    ///
+    /// For example, `px` and `em` are both lengths and `%` may resolve to
+    /// anything, while `px` and `s` can never be combined
+    pub fn is_possibly_compatible(&self, other: &Unit) -> bool {
+        if self == &Unit::None || other == &Unit::None {
+            return self == other;
+        }
+        match (self.kind(), other.kind()) {
+            (UnitKind::Other, _) | (_, UnitKind::Other) => true,
+            (
+                UnitKind::Absolute | UnitKind::FontRelative | UnitKind::ViewportRelative,
+                UnitKind::Absolute | UnitKind::FontRelative | UnitKind::ViewportRelative,
+            ) => true,
+            (u1, u2) => u1 == u2,
+        }
+    }
+
     /// Used internally to determine if two units are comparable or not
-    fn kind(&self) -> UnitKind {
+    pub fn kind(&self) -> UnitKind {
         match self {
             Unit::Px | Unit::Mm | Unit::In | Unit::Cm | Unit::Q | Unit::Pt | Unit::Pc => {
                 UnitKind::Absolute
@@ -54,12 +54,3 @@ pub(crate) fn is_name_start(c: char) -> bool {
     // how this is handled.
     c == '_' || c.is_alphanumeric() || c as u32 >= 0x0080
 }
-
-pub(crate) fn as_hex(c: char) -> u32 {
-    match c {
-        '0'..='9' => c as u32 - '0' as u32,
-        'A'..='F' => 10 + c as u32 - 'A' as u32,
-        'a'..='f' => 10 + c as u32 - 'a' as u32,
-        _ => panic!(),
-    }
-}
@@ -3,11 +3,15 @@ pub(crate) use comment_whitespace::*;
 pub(crate) use number::*;
 pub(crate) use peek_until::*;
 pub(crate) use read_until::*;
+pub(crate) use stack::*;
 pub(crate) use strings::*;
+pub(crate) use suggest::*;
 
 mod chars;
 mod comment_whitespace;
 mod number;
 mod peek_until;
 mod read_until;
+mod stack;
 mod strings;
+mod suggest;
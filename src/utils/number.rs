@@ -45,6 +45,27 @@ impl ParsedNumber {
             times_ten_is_postive,
         }
     }
+
+    /// The closest double precision float to this number
+    pub fn to_f64(&self) -> f64 {
+        let times_ten = if self.times_ten.is_empty() {
+            0
+        } else {
+            // exponents this large will always over- or underflow
+            self.times_ten.parse::<i64>().unwrap_or(i64::from(i32::MAX))
+        };
+
+        #[allow(clippy::cast_possible_wrap)]
+        let exponent = if self.times_ten_is_postive {
+            times_ten
+        } else {
+            -times_ten
+        } - self.dec_len as i64;
+
+        format!("{}e{}", self.num, exponent)
+            .parse()
+            .unwrap_or_default()
+    }
 }
 
 pub(crate) fn eat_number(
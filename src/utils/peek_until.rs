@@ -6,7 +6,7 @@ use peekmore::PeekMoreIterator;
 
 use crate::{error::SassResult, Token};
 
-use super::{as_hex, hex_char_for, is_name, is_name_start, peek_whitespace};
+use super::{hex_char_for, is_name, is_name_start, peek_whitespace};
 
 pub(crate) fn peek_until_closing_curly_brace(
     toks: &mut PeekMoreIterator<IntoIter<Token>>,
@@ -121,11 +121,12 @@ pub(crate) fn peek_escape(toks: &mut PeekMoreIterator<IntoIter<Token>>) -> SassR
                 Some(t) => t,
                 None => break,
             };
-            if !next.kind.is_ascii_hexdigit() {
-                break;
-            }
+            let digit = match next.kind.to_digit(16) {
+                Some(digit) => digit,
+                None => break,
+            };
             value *= 16;
-            value += as_hex(next.kind);
+            value += digit;
             span = span.merge(next.pos);
             toks.peek_forward(1);
         }
@@ -133,7 +134,10 @@ pub(crate) fn peek_escape(toks: &mut PeekMoreIterator<IntoIter<Token>>) -> SassR
             toks.peek_forward(1);
         }
     } else {
-        value = toks.peek_forward(1).unwrap().kind as u32;
+        value = match toks.peek_forward(1) {
+            Some(t) => t.kind as u32,
+            None => return Err(("Expected escape sequence.", span).into()),
+        };
     }
 
     let c = std::char::from_u32(value).ok_or(("Invalid escape sequence.", span))?;
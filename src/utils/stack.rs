@@ -0,0 +1,15 @@
+/// The amount of native stack which must remain before recursing further
+const RED_ZONE: usize = 256 * 1024;
+
+/// The size of each new segment of stack, once the red zone is reached
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+/// Runs `f`, first allocating more native stack should it be running out
+///
+/// This is called at each point where parsing and evaluation recurse, so that
+/// deeply nested input and recursive `@function`s are limited only by
+/// `Options::max_depth` and `Options::max_call_depth`, rather than by the size
+/// of the stack of the calling thread
+pub(crate) fn ensure_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_SEGMENT_SIZE, f)
+}
@@ -0,0 +1,88 @@
+//! Suggestions for misspelled names, e.g. "Undefined variable. Did you mean $color?"
+
+/// Appends a suggestion of the candidate closest to `name`, written with
+/// `prefix` (e.g. `$` for variables), to an error `message`
+pub(crate) fn did_you_mean<'a, I: IntoIterator<Item = &'a str>>(
+    message: &str,
+    name: &str,
+    candidates: I,
+    prefix: &str,
+) -> String {
+    match closest_match(name, candidates) {
+        Some(suggestion) => format!(
+            "{}{} Did you mean {}{}?",
+            message,
+            if message.ends_with('.') { "" } else { "." },
+            prefix,
+            suggestion
+        ),
+        None => message.to_owned(),
+    }
+}
+
+/// Returns the candidate closest to `name`, if any is close enough that
+/// `name` is likely a typo of it
+///
+/// Names are compared by edit distance, allowing roughly one edit for
+/// every three characters, but never so many that every character of `name`
+/// would have been changed. Ties are broken in favor of the earliest candidate.
+pub(crate) fn closest_match<'a, I: IntoIterator<Item = &'a str>>(
+    name: &str,
+    candidates: I,
+) -> Option<&'a str> {
+    let len = name.chars().count();
+    let max_distance = (len.max(3) / 3).min(len.saturating_sub(1));
+
+    let mut best: Option<(usize, &str)> = None;
+
+    for candidate in candidates {
+        if candidate == name {
+            continue;
+        }
+
+        let distance = edit_distance(name, candidate);
+
+        if distance <= max_distance && best.map_or(true, |(best, ..)| distance < best) {
+            best = Some((distance, candidate));
+        }
+    }
+
+    best.map(|(_, candidate)| candidate)
+}
+
+/// The number of insertions, deletions, substitutions, and transpositions
+/// of adjacent characters needed to turn `a` into `b`
+///
+/// Transpositions are counted as a single edit since swapping two letters is
+/// one of the most common typos (this is the "optimal string alignment"
+/// variant of Damerau–Levenshtein distance).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // rows for `a[..i - 2]`, `a[..i - 1]`, and `a[..i]`
+    let mut prev_prev = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
@@ -0,0 +1,88 @@
+use std::{cell::Cell, rc::Rc};
+
+use codemap::{Span, Spanned};
+
+use crate::{
+    error::SassResult,
+    value::{SassMap, Value},
+};
+
+/// The value of a variadic parameter, such as `$args` in
+/// `@mixin foo($args...)`
+///
+/// Along with the extra positional arguments, this holds the extra keyword
+/// arguments, which can be read with `keywords()` or passed on to another
+/// function or mixin using `$args...`
+#[derive(Debug, Clone)]
+pub(crate) struct SassArgList {
+    pub elems: Vec<Spanned<Value>>,
+    /// Keyword arguments, keyed by their name (without the `$`) as an
+    /// unquoted string
+    keywords: SassMap,
+    /// Shared between clones, so that reading the keywords of a copy of
+    /// the arglist marks the original as accessed
+    were_keywords_accessed: Rc<Cell<bool>>,
+}
+
+impl SassArgList {
+    pub fn new(elems: Vec<Spanned<Value>>, keywords: SassMap) -> Self {
+        SassArgList {
+            elems,
+            keywords,
+            were_keywords_accessed: Rc::new(Cell::new(false)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Get the keyword arguments, marking them as accessed
+    pub fn keywords(&self) -> SassMap {
+        self.were_keywords_accessed.set(true);
+        self.keywords.clone()
+    }
+
+    /// Whether the two arglists have identical positional and keyword
+    /// arguments
+    pub fn is_identical(&self, other: &SassArgList) -> bool {
+        self.elems.len() == other.elems.len()
+            && self
+                .elems
+                .iter()
+                .zip(&other.elems)
+                .all(|(a, b)| a.node.is_identical(&b.node))
+            && self.keywords.is_identical(&other.keywords)
+    }
+
+    /// Sass allows keyword arguments to be passed to a variadic parameter
+    /// only if they are actually used, so that misspelled argument names
+    /// aren't silently ignored
+    pub fn check_keywords_accessed(&self, span: Span) -> SassResult<()> {
+        if self.keywords.is_empty() || self.were_keywords_accessed.get() {
+            return Ok(());
+        }
+
+        let mut names = self
+            .keywords
+            .iter()
+            .map(|(name, _)| Ok(format!("${}", name.to_css_string(span)?)))
+            .collect::<SassResult<Vec<String>>>()?;
+
+        let last = names.pop().unwrap();
+
+        Err((
+            if names.is_empty() {
+                format!("No argument named {}.", last)
+            } else {
+                format!("No arguments named {} or {}.", names.join(", "), last)
+            },
+            span,
+        )
+            .into())
+    }
+}
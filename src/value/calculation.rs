@@ -0,0 +1,376 @@
+use std::fmt::{self, Write};
+
+use codemap::Span;
+
+use num_traits::Zero;
+
+use crate::{
+    common::{Op, QuoteKind},
+    error::SassResult,
+    unit::Unit,
+    value::{Number, Value},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum CalculationName {
+    Calc,
+    Min,
+    Max,
+    Clamp,
+}
+
+impl CalculationName {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Calc => "calc",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Clamp => "clamp",
+        }
+    }
+}
+
+impl fmt::Display for CalculationName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single argument to a calculation, after as much simplification as
+/// is possible at compile time
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum CalculationArg {
+    Number(Number, Unit),
+    Calculation(SassCalculation),
+    /// An unquoted string, such as `var(--foo)`
+    String(String),
+    /// The result of interpolation. Unlike `CalculationArg::String`, this is
+    /// parenthesized when used as the operand of an operation
+    Interpolation(String),
+    Operation(Box<CalculationArg>, Op, Box<CalculationArg>),
+}
+
+impl CalculationArg {
+    /// Convert an arbitrary SassScript value into a calculation argument,
+    /// erroring if the value could never appear inside a calculation
+    pub fn from_value(value: Value, span: Span) -> SassResult<Self> {
+        Ok(match value {
+            Value::Dimension(n, u, _) => CalculationArg::Number(n, u),
+            // a nested `calc()` is redundant, so we only keep its argument
+            Value::Calculation(SassCalculation {
+                name: CalculationName::Calc,
+                mut args,
+            }) if args.len() == 1 => args.pop().unwrap(),
+            Value::Calculation(c) => CalculationArg::Calculation(c),
+            Value::String(s, QuoteKind::None) => CalculationArg::String(s.to_string()),
+            v => {
+                return Err((
+                    format!("Value {} can't be used in a calculation.", v.inspect(span)?),
+                    span,
+                )
+                    .into())
+            }
+        })
+    }
+
+    /// Convert this argument back into a SassScript value, as returned by
+    /// `calc-args()`
+    pub fn into_value(self) -> Value {
+        match self {
+            CalculationArg::Number(n, u) => Value::Dimension(n, u, true),
+            CalculationArg::Calculation(c) => Value::Calculation(c),
+            CalculationArg::String(s) | CalculationArg::Interpolation(s) => {
+                Value::String(s.into(), QuoteKind::None)
+            }
+            v @ CalculationArg::Operation(..) => {
+                Value::String(v.to_string().into(), QuoteKind::None)
+            }
+        }
+    }
+
+    fn is_valid_css(&self) -> bool {
+        match self {
+            CalculationArg::Number(_, Unit::Mul(..)) | CalculationArg::Number(_, Unit::Div(..)) => {
+                false
+            }
+            CalculationArg::Number(..)
+            | CalculationArg::String(..)
+            | CalculationArg::Interpolation(..) => true,
+            CalculationArg::Calculation(c) => c.args.iter().all(CalculationArg::is_valid_css),
+            CalculationArg::Operation(lhs, _, rhs) => lhs.is_valid_css() && rhs.is_valid_css(),
+        }
+    }
+
+    fn precedence(&self) -> Option<usize> {
+        match self {
+            CalculationArg::Operation(_, op, _) => Some(op.precedence()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CalculationArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalculationArg::Number(n, u) => write!(f, "{}{}", n, u),
+            CalculationArg::Calculation(c) => write!(f, "{}", c),
+            CalculationArg::String(s) | CalculationArg::Interpolation(s) => f.write_str(s),
+            CalculationArg::Operation(lhs, op, rhs) => {
+                let parenthesize_lhs = match lhs.precedence() {
+                    Some(p) => p < op.precedence(),
+                    None => false,
+                };
+
+                if parenthesize_lhs || matches!(**lhs, CalculationArg::Interpolation(..)) {
+                    write!(f, "({})", lhs)?;
+                } else {
+                    write!(f, "{}", lhs)?;
+                }
+
+                write!(f, " {} ", op)?;
+
+                let parenthesize_rhs = match **rhs {
+                    CalculationArg::Operation(_, rhs_op, _) => match op {
+                        Op::Div => true,
+                        Op::Plus => false,
+                        _ => matches!(rhs_op, Op::Plus | Op::Minus),
+                    },
+                    CalculationArg::Interpolation(..) => true,
+                    _ => false,
+                };
+
+                if parenthesize_rhs {
+                    write!(f, "({})", rhs)
+                } else {
+                    write!(f, "{}", rhs)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct SassCalculation {
+    pub name: CalculationName,
+    pub args: Vec<CalculationArg>,
+}
+
+impl SassCalculation {
+    /// Create a `calc()` calculation, or the simplified value if `arg`
+    /// can be resolved at compile time
+    pub fn calc(arg: CalculationArg) -> Value {
+        match arg {
+            CalculationArg::Number(n, u) => Value::Dimension(n, u, true),
+            CalculationArg::Calculation(c) => Value::Calculation(c),
+            arg => Value::Calculation(SassCalculation {
+                name: CalculationName::Calc,
+                args: vec![arg],
+            }),
+        }
+    }
+
+    /// Create a `min()` or `max()` calculation, resolving it to a single number
+    /// if all of its arguments are numbers with compatible units
+    pub fn min_max(
+        name: CalculationName,
+        args: Vec<CalculationArg>,
+        span: Span,
+    ) -> SassResult<Value> {
+        if args.is_empty() {
+            return Err((format!("{}() must have at least one argument.", name), span).into());
+        }
+
+        let mut extremum: Option<(Number, Unit)> = None;
+        for arg in &args {
+            let (n, u) = match arg {
+                CalculationArg::Number(n, u) => (n, u),
+                _ => {
+                    extremum = None;
+                    break;
+                }
+            };
+
+            extremum = match extremum {
+                None => Some((*n, u.clone())),
+                Some((n2, u2)) => {
+                    if !are_compatible(u, &u2) {
+                        verify_possibly_compatible(n, u, &n2, &u2, span)?;
+                        extremum = None;
+                        break;
+                    }
+                    let converted = convert(*n, u, &u2);
+                    let replace = match name {
+                        CalculationName::Min => converted < n2,
+                        _ => converted > n2,
+                    };
+                    if replace {
+                        Some((*n, u.clone()))
+                    } else {
+                        Some((n2, u2))
+                    }
+                }
+            };
+        }
+
+        Ok(match extremum {
+            Some((n, u)) => Value::Dimension(n, u, true),
+            None => Value::Calculation(SassCalculation { name, args }),
+        })
+    }
+
+    /// Create a `clamp()` calculation, resolving it to a single number if
+    /// all of its arguments are numbers with compatible units
+    pub fn clamp(
+        min: CalculationArg,
+        value: CalculationArg,
+        max: CalculationArg,
+        span: Span,
+    ) -> SassResult<Value> {
+        if let (
+            CalculationArg::Number(min_n, min_u),
+            CalculationArg::Number(n, u),
+            CalculationArg::Number(max_n, max_u),
+        ) = (&min, &value, &max)
+        {
+            if are_compatible(min_u, u) && are_compatible(max_u, u) {
+                let min_n = convert(*min_n, min_u, u);
+                let max_n = convert(*max_n, max_u, u);
+                let n = if n <= &min_n {
+                    min_n
+                } else if n >= &max_n {
+                    max_n
+                } else {
+                    *n
+                };
+                return Ok(Value::Dimension(n, u.clone(), true));
+            }
+
+            verify_possibly_compatible(min_n, min_u, n, u, span)?;
+            verify_possibly_compatible(max_n, max_u, n, u, span)?;
+        }
+
+        Ok(Value::Calculation(SassCalculation {
+            name: CalculationName::Clamp,
+            args: vec![min, value, max],
+        }))
+    }
+
+    /// Combine two calculation arguments with `op`, simplifying the result
+    /// where possible
+    ///
+    /// `op` must be one of `+`, `-`, `*`, or `/`; any other operator is an error
+    pub fn operate(
+        op: Op,
+        lhs: CalculationArg,
+        rhs: CalculationArg,
+        span: Span,
+    ) -> SassResult<CalculationArg> {
+        let (n1, u1, n2, u2) = match (lhs, rhs) {
+            (CalculationArg::Number(n1, u1), CalculationArg::Number(n2, u2)) => (n1, u1, n2, u2),
+            (lhs, rhs) => {
+                return Ok(CalculationArg::Operation(Box::new(lhs), op, Box::new(rhs)));
+            }
+        };
+
+        let simplified = match op {
+            Op::Plus | Op::Minus => {
+                if are_compatible(&u1, &u2) {
+                    let n2 = convert(n2, &u2, &u1);
+                    Some(if op == Op::Plus {
+                        CalculationArg::Number(n1 + n2, u1.clone())
+                    } else {
+                        CalculationArg::Number(n1 - n2, u1.clone())
+                    })
+                } else {
+                    verify_possibly_compatible(&n1, &u1, &n2, &u2, span)?;
+                    None
+                }
+            }
+            Op::Mul => {
+                let (unit, factor) = u1.mul_with_factor(&u2);
+                Some(CalculationArg::Number(n1 * n2 * factor, unit))
+            }
+            Op::Div if n2.is_zero() => None,
+            Op::Div => {
+                let (unit, factor) = u1.div_with_factor(&u2);
+                Some(CalculationArg::Number(n1 / n2 * factor, unit))
+            }
+            _ => {
+                return Err((
+                    format!("\"{}\" is not a valid calculation operator.", op),
+                    span,
+                )
+                    .into())
+            }
+        };
+
+        Ok(match simplified {
+            Some(v) => v,
+            None => CalculationArg::Operation(
+                Box::new(CalculationArg::Number(n1, u1)),
+                op,
+                Box::new(CalculationArg::Number(n2, u2)),
+            ),
+        })
+    }
+
+    pub fn to_css_string(&self, span: Span) -> SassResult<String> {
+        if !self.args.iter().all(CalculationArg::is_valid_css) {
+            return Err((format!("{} isn't a valid CSS value.", self), span).into());
+        }
+        Ok(self.to_string())
+    }
+}
+
+impl fmt::Display for SassCalculation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name.as_str())?;
+        f.write_char('(')?;
+        for (idx, arg) in self.args.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", arg)?;
+        }
+        f.write_char(')')
+    }
+}
+
+/// Whether numbers with these units can be combined at compile time
+fn are_compatible(u1: &Unit, u2: &Unit) -> bool {
+    if u1 == &Unit::None || u2 == &Unit::None {
+        return u1 == u2;
+    }
+    u1.comparable(u2)
+}
+
+/// Convert `n` from `from` units into `to` units
+///
+/// The units are assumed to be compatible
+fn convert(n: Number, from: &Unit, to: &Unit) -> Number {
+    if from == to || from == &Unit::None || to == &Unit::None {
+        return n;
+    }
+    n * to.conversion_factor(from)
+}
+
+/// Units such as `px` and `%` can't be combined at compile time, but may be
+/// resolved by the browser. Units such as `px` and `s`, however, are never
+/// compatible
+fn verify_possibly_compatible(
+    n1: &Number,
+    u1: &Unit,
+    n2: &Number,
+    u2: &Unit,
+    span: Span,
+) -> SassResult<()> {
+    if u1.is_possibly_compatible(u2) {
+        return Ok(());
+    }
+
+    Err((
+        format!("{}{} and {}{} are incompatible.", n1, u1, n2, u2),
+        span,
+    )
+        .into())
+}
@@ -1,4 +1,6 @@
-use std::{slice::Iter, vec::IntoIter};
+use std::rc::Rc;
+
+use indexmap::{map::IntoIter, map::Iter, IndexMap};
 
 use crate::{
     common::{Brackets, ListSeparator},
@@ -6,99 +8,108 @@ use crate::{
     value::Value,
 };
 
+/// A Sass map, which preserves the order in which keys were inserted
+///
+/// Keys are hashed consistently with `Value`'s equality, so lookups are
+/// constant time even though e.g. `1in` and `96px` are the same key
+///
+/// The entries are reference counted and copied on write, so cloning a map
+/// (e.g. when reading a variable) is cheap
 #[derive(Debug, Clone)]
-pub(crate) struct SassMap(Vec<(Value, Value)>);
+pub(crate) struct SassMap(Rc<IndexMap<Value, Value>>);
 
 impl PartialEq for SassMap {
     fn eq(&self, other: &Self) -> bool {
         if self.0.len() != other.0.len() {
             return false;
         }
-        for (key, value) in &self.0 {
-            if !other
-                .0
-                .iter()
-                .any(|(key2, value2)| key == key2 && value == value2)
-            {
-                return false;
-            }
-        }
-        true
+        self.0
+            .iter()
+            .all(|(key, value)| other.0.get(key).map_or(false, |value2| value == value2))
     }
 }
 
 impl Eq for SassMap {}
 
 impl SassMap {
-    pub const fn new() -> SassMap {
-        SassMap(Vec::new())
+    pub fn new() -> SassMap {
+        SassMap(Rc::new(IndexMap::new()))
     }
 
-    /// We take by value here (consuming the map) in order to
-    /// save a clone of the value, since the only place this
-    /// should be called is in a builtin function, which throws
-    /// away the map immediately anyway
     pub fn get(self, key: &Value) -> SassResult<Option<Value>> {
-        for (k, v) in self.0 {
-            if &k == key {
-                return Ok(Some(v));
-            }
-        }
-        Ok(None)
+        Ok(self.0.get(key).cloned())
     }
 
     pub fn remove(&mut self, key: &Value) {
-        self.0.retain(|(ref k, ..)| k.not_equals(key));
+        Rc::make_mut(&mut self.0).shift_remove(key);
     }
 
     pub fn merge(&mut self, other: SassMap) {
+        if self.0.is_empty() {
+            *self = other;
+            return;
+        }
+
         for (key, value) in other {
             self.insert(key, value);
         }
     }
 
-    pub fn iter(&self) -> Iter<(Value, Value)> {
+    pub fn iter(&self) -> Iter<'_, Value, Value> {
         self.0.iter()
     }
 
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the two maps have identical entries, in the same order
+    pub fn is_identical(&self, other: &SassMap) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+            || (self.len() == other.len()
+                && self
+                    .iter()
+                    .zip(other.iter())
+                    .all(|((k1, v1), (k2, v2))| k1.is_identical(k2) && v1.is_identical(v2)))
+    }
+
     pub fn keys(self) -> Vec<Value> {
-        self.0.into_iter().map(|(k, ..)| k).collect()
+        self.0.keys().cloned().collect()
     }
 
     pub fn values(self) -> Vec<Value> {
-        self.0.into_iter().map(|(.., v)| v).collect()
+        self.0.values().cloned().collect()
     }
 
     pub fn as_list(self) -> Vec<Value> {
-        self.0
-            .into_iter()
-            .map(|(k, v)| Value::List(vec![k, v], ListSeparator::Space, Brackets::None))
+        self.into_iter()
+            .map(|(k, v)| Value::List(Rc::new(vec![k, v]), ListSeparator::Space, Brackets::None))
             .collect()
     }
 
-    #[allow(clippy::missing_const_for_fn)]
     pub fn entries(self) -> Vec<(Value, Value)> {
-        self.0
+        self.into_iter().collect()
     }
 
     /// Returns true if the key already exists
+    ///
+    /// If it does, the original key and its position are kept
     pub fn insert(&mut self, key: Value, value: Value) -> bool {
-        for (ref k, ref mut v) in &mut self.0 {
-            if k == &key {
-                *v = value;
-                return true;
-            }
-        }
-        self.0.push((key, value));
-        false
+        Rc::make_mut(&mut self.0).insert(key, value).is_some()
     }
 }
 
 impl IntoIterator for SassMap {
     type Item = (Value, Value);
-    type IntoIter = IntoIter<Self::Item>;
+    type IntoIter = IntoIter<Value, Value>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        Rc::try_unwrap(self.0)
+            .unwrap_or_else(|map| (*map).clone())
+            .into_iter()
     }
 }
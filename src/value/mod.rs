@@ -1,3 +1,8 @@
+use std::{
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
 use peekmore::PeekMore;
 
 use codemap::{Span, Spanned};
@@ -8,20 +13,28 @@ use crate::{
     error::SassResult,
     parse::Parser,
     selector::Selector,
-    unit::{Unit, UNIT_CONVERSION_TABLE},
+    unit::{Unit, UnitKind},
     utils::hex_char_for,
     {Cow, Token},
 };
 
+pub(crate) use arglist::SassArgList;
+pub(crate) use calculation::{CalculationArg, CalculationName, SassCalculation};
 use css_function::is_special_function;
 pub(crate) use map::SassMap;
-pub(crate) use number::Number;
+pub(crate) use number::{Number, PrecisionGuard, DEFAULT_PRECISION};
 pub(crate) use sass_function::SassFunction;
+pub(crate) use sass_mixin::SassMixin;
+pub(crate) use slash::SlashNumber;
 
+mod arglist;
+mod calculation;
 pub(crate) mod css_function;
 mod map;
 mod number;
 mod sass_function;
+mod sass_mixin;
+mod slash;
 
 #[derive(Debug, Clone)]
 pub(crate) enum Value {
@@ -30,18 +43,33 @@ pub(crate) enum Value {
     False,
     Null,
     Dimension(Number, Unit, bool),
-    List(Vec<Value>, ListSeparator, Brackets),
+    /// Lists and strings are reference counted, so that they are cheap to
+    /// clone when reading a variable or passing an argument
+    List(Rc<Vec<Value>>, ListSeparator, Brackets),
     Color(Box<Color>),
-    String(String, QuoteKind),
+    String(Rc<str>, QuoteKind),
     Map(SassMap),
-    ArgList(Vec<Spanned<Value>>),
+    ArgList(SassArgList),
     /// Returned by `get-function()`
     FunctionRef(SassFunction),
+    /// Returned by `get-mixin()`
+    MixinRef(SassMixin),
+    /// A `calc()`, `min()`, `max()`, or `clamp()` expression that could not
+    /// be fully resolved at compile time
+    Calculation(SassCalculation),
+    /// Two numbers separated by `/` in a space separated list, which is
+    /// printed as written
+    SlashNumber(Box<SlashNumber>),
 }
 
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
+        if let Value::SlashNumber(slash) = other {
+            return self == slash.quotient();
+        }
+
         match self {
+            Value::SlashNumber(slash) => slash.quotient() == other,
             Value::String(s1, ..) => match other {
                 Value::String(s2, ..) => s1 == s2,
                 _ => false,
@@ -55,10 +83,7 @@ impl PartialEq for Value {
                     } else if unit == &Unit::None || unit2 == &Unit::None {
                         false
                     } else {
-                        n == &(n2.clone()
-                            * UNIT_CONVERSION_TABLE[unit.to_string().as_str()]
-                                [unit2.to_string().as_str()]
-                            .clone())
+                        n == &(*n2 * unit.conversion_factor(unit2))
                     }
                 }
                 _ => false,
@@ -68,7 +93,7 @@ impl PartialEq for Value {
                     if sep1 != sep2 || brackets1 != brackets2 || list1.len() != list2.len() {
                         false
                     } else {
-                        for (a, b) in list1.iter().zip(list2) {
+                        for (a, b) in list1.iter().zip(list2.iter()) {
                             if a != b {
                                 return false;
                             }
@@ -89,6 +114,13 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::MixinRef(mixin1) => {
+                if let Value::MixinRef(mixin2) = other {
+                    mixin1 == mixin2
+                } else {
+                    false
+                }
+            }
             Value::Map(map1) => {
                 if let Value::Map(map2) = other {
                     map1 == map2
@@ -105,7 +137,14 @@ impl PartialEq for Value {
             }
             Value::ArgList(list1) => {
                 if let Value::ArgList(list2) = other {
-                    list1 == list2
+                    list1.elems == list2.elems
+                } else {
+                    false
+                }
+            }
+            Value::Calculation(calc1) => {
+                if let Value::Calculation(calc2) = other {
+                    calc1 == calc2
                 } else {
                     false
                 }
@@ -116,6 +155,66 @@ impl PartialEq for Value {
 
 impl Eq for Value {}
 
+/// Values which are equal must hash identically, so numbers are hashed in a
+/// canonical unit (`1in` and `96px` are equal), slash separated numbers as
+/// their quotient, strings ignore their quotes, and maps, whose equality
+/// ignores order, hash only their length
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Value::SlashNumber(slash) = self {
+            return slash.quotient().hash(state);
+        }
+
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Dimension(num, unit, _) => {
+                let canonical = match unit.kind() {
+                    UnitKind::Absolute => Some(Unit::Px),
+                    UnitKind::Angle => Some(Unit::Deg),
+                    UnitKind::Time => Some(Unit::S),
+                    UnitKind::Frequency => Some(Unit::Hz),
+                    UnitKind::Resolution => Some(Unit::Dppx),
+                    _ => None,
+                };
+                match canonical {
+                    Some(canonical) => {
+                        unit.kind().hash(state);
+                        (*num * canonical.conversion_factor(unit)).hash(state);
+                    }
+                    // complex units may be equal to one another after
+                    // conversion, so we don't attempt to hash them
+                    None if unit.is_complex() => {}
+                    None => {
+                        unit.hash(state);
+                        num.hash(state);
+                    }
+                }
+            }
+            Value::List(list, sep, brackets) => {
+                sep.hash(state);
+                brackets.hash(state);
+                list.hash(state);
+            }
+            Value::Color(color) => color.hash(state),
+            Value::String(s, _) => s.hash(state),
+            Value::Map(map) => map.len().hash(state),
+            Value::ArgList(args) => {
+                for arg in &args.elems {
+                    arg.node.hash(state);
+                }
+            }
+            Value::FunctionRef(f) => f.name().hash(state),
+            Value::MixinRef(mixin) => mixin.name().hash(state),
+            Value::Important
+            | Value::True
+            | Value::False
+            | Value::Null
+            | Value::Calculation(..)
+            | Value::SlashNumber(..) => {}
+        }
+    }
+}
+
 fn visit_quoted_string(buf: &mut String, force_double_quote: bool, string: &str) {
     let mut has_single_quote = false;
     let mut has_double_quote = false;
@@ -190,7 +289,7 @@ impl Value {
             Value::List(v, _, Brackets::Bracketed) if v.is_empty() => false,
             Value::List(v, ..) => v.iter().map(Value::is_null).all(|f| f),
             Value::ArgList(v, ..) if v.is_empty() => false,
-            Value::ArgList(v, ..) => v.iter().map(|v| v.node.is_null()).all(|f| f),
+            Value::ArgList(v, ..) => v.elems.iter().map(|v| v.node.is_null()).all(|f| f),
             _ => false,
         }
     }
@@ -204,7 +303,7 @@ impl Value {
                 }
                 _ => Cow::owned(format!("{}{}", num, unit)),
             },
-            Value::Map(..) | Value::FunctionRef(..) => {
+            Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", self.inspect(span)?),
                     span,
@@ -229,6 +328,8 @@ impl Value {
                 )),
             },
             Value::Color(c) => Cow::owned(c.to_string()),
+            Value::Calculation(c) => Cow::owned(c.to_css_string(span)?),
+            Value::SlashNumber(slash) => slash.to_css_string(span)?,
             Value::String(string, QuoteKind::None) => {
                 let mut after_newline = false;
                 let mut buf = String::with_capacity(string.len());
@@ -263,7 +364,8 @@ impl Value {
                 return Err(("() isn't a valid CSS value.", span).into());
             }
             Value::ArgList(args) => Cow::owned(
-                args.iter()
+                args.elems
+                    .iter()
                     .filter(|x| !x.is_null())
                     .map(|a| Ok(a.node.to_css_string(span)?))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
@@ -279,12 +381,23 @@ impl Value {
         }
     }
 
+    /// Replace a slash separated number with the result of the division, for
+    /// use as an operand
+    pub fn without_slash(self) -> Self {
+        match self {
+            Value::SlashNumber(slash) => slash.into_quotient(),
+            v => v,
+        }
+    }
+
     pub fn unquote(self) -> Self {
         match self {
             Value::String(s1, _) => Value::String(s1, QuoteKind::None),
-            Value::List(v, sep, bracket) => {
-                Value::List(v.into_iter().map(Value::unquote).collect(), sep, bracket)
-            }
+            Value::List(v, sep, bracket) => Value::List(
+                Rc::new(v.iter().cloned().map(Value::unquote).collect()),
+                sep,
+                bracket,
+            ),
             v => v,
         }
     }
@@ -297,13 +410,15 @@ impl Value {
         match self {
             Value::Color(..) => "color",
             Value::String(..) | Value::Important => "string",
-            Value::Dimension(..) => "number",
+            Value::Dimension(..) | Value::SlashNumber(..) => "number",
             Value::List(..) => "list",
             Value::FunctionRef(..) => "function",
+            Value::MixinRef(..) => "mixin",
             Value::ArgList(..) => "arglist",
             Value::True | Value::False => "bool",
             Value::Null => "null",
             Value::Map(..) => "map",
+            Value::Calculation(..) => "calculation",
         }
     }
 
@@ -323,7 +438,12 @@ impl Value {
     }
 
     pub fn not_equals(&self, other: &Self) -> bool {
+        if let Value::SlashNumber(slash) = other {
+            return self.not_equals(slash.quotient());
+        }
+
         match self {
+            Value::SlashNumber(slash) => slash.quotient().not_equals(other),
             Value::String(s1, ..) => match other {
                 Value::String(s2, ..) => s1 != s2,
                 _ => true,
@@ -337,10 +457,7 @@ impl Value {
                     } else if unit == &Unit::None || unit2 == &Unit::None {
                         true
                     } else {
-                        n != &(n2.clone()
-                            * UNIT_CONVERSION_TABLE[unit.to_string().as_str()]
-                                [unit2.to_string().as_str()]
-                            .clone())
+                        n != &(*n2 * unit.conversion_factor(unit2))
                     }
                 }
                 _ => true,
@@ -350,7 +467,7 @@ impl Value {
                     if sep1 != sep2 || brackets1 != brackets2 || list1.len() != list2.len() {
                         true
                     } else {
-                        for (a, b) in list1.iter().zip(list2) {
+                        for (a, b) in list1.iter().zip(list2.iter()) {
                             if a.not_equals(b) {
                                 return true;
                             }
@@ -376,10 +493,12 @@ impl Value {
                 Brackets::None => match sep {
                     ListSeparator::Space => v[0].inspect(span)?,
                     ListSeparator::Comma => Cow::owned(format!("({},)", v[0].inspect(span)?)),
+                    ListSeparator::Slash => Cow::owned(format!("({}/)", v[0].inspect(span)?)),
                 },
                 Brackets::Bracketed => match sep {
                     ListSeparator::Space => Cow::owned(format!("[{}]", v[0].inspect(span)?)),
                     ListSeparator::Comma => Cow::owned(format!("[{},]", v[0].inspect(span)?)),
+                    ListSeparator::Slash => Cow::owned(format!("[{}/]", v[0].inspect(span)?)),
                 },
             },
             Value::List(vals, sep, brackets) => Cow::owned(match brackets {
@@ -397,6 +516,9 @@ impl Value {
                 ),
             }),
             Value::FunctionRef(f) => Cow::owned(format!("get-function(\"{}\")", f.name())),
+            Value::MixinRef(mixin) => Cow::owned(format!("get-mixin(\"{}\")", mixin.name())),
+            Value::Calculation(c) => Cow::owned(c.to_string()),
+            Value::SlashNumber(slash) => slash.inspect(span)?,
             Value::Null => Cow::const_str("null"),
             Value::Map(map) => Cow::owned(format!(
                 "({})",
@@ -409,14 +531,16 @@ impl Value {
             Value::ArgList(args) if args.is_empty() => Cow::const_str("()"),
             Value::ArgList(args) if args.len() == 1 => Cow::owned(format!(
                 "({},)",
-                args.iter()
+                args.elems
+                    .iter()
                     .filter(|x| !x.is_null())
                     .map(|a| Ok(a.node.inspect(span)?))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
                     .join(", "),
             )),
             Value::ArgList(args) => Cow::owned(
-                args.iter()
+                args.elems
+                    .iter()
                     .filter(|x| !x.is_null())
                     .map(|a| Ok(a.node.inspect(span)?))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
@@ -430,15 +554,66 @@ impl Value {
         })
     }
 
+    /// Whether `self` and `other` cannot be told apart in any way, which is
+    /// stricter than Sass equality: `1in == 96px` and `"a" == a`, but neither
+    /// pair is identical
+    pub fn is_identical(&self, other: &Value) -> bool {
+        match (self, other) {
+            (
+                Value::Dimension(n1, unit1, should_divide1),
+                Value::Dimension(n2, unit2, should_divide2),
+            ) => {
+                n1.0.to_bits() == n2.0.to_bits()
+                    && unit1 == unit2
+                    && should_divide1 == should_divide2
+            }
+            (Value::List(list1, sep1, brackets1), Value::List(list2, sep2, brackets2)) => {
+                sep1 == sep2
+                    && brackets1 == brackets2
+                    && (Rc::ptr_eq(list1, list2)
+                        || (list1.len() == list2.len()
+                            && list1
+                                .iter()
+                                .zip(list2.iter())
+                                .all(|(a, b)| a.is_identical(b))))
+            }
+            (Value::Color(color1), Value::Color(color2)) => color1.is_identical(color2),
+            (Value::String(s1, quotes1), Value::String(s2, quotes2)) => {
+                s1 == s2 && quotes1 == quotes2
+            }
+            (Value::Map(map1), Value::Map(map2)) => map1.is_identical(map2),
+            (Value::ArgList(args1), Value::ArgList(args2)) => args1.is_identical(args2),
+            (Value::FunctionRef(f1), Value::FunctionRef(f2)) => f1 == f2,
+            (Value::MixinRef(mixin1), Value::MixinRef(mixin2)) => mixin1 == mixin2,
+            (Value::Calculation(calc1), Value::Calculation(calc2)) => calc1 == calc2,
+            (Value::Important, Value::Important)
+            | (Value::True, Value::True)
+            | (Value::False, Value::False)
+            | (Value::Null, Value::Null) => true,
+            _ => false,
+        }
+    }
+
     pub fn as_list(self) -> Vec<Value> {
         match self {
-            Value::List(v, ..) => v,
+            Value::List(v, ..) => Rc::try_unwrap(v).unwrap_or_else(|v| (*v).clone()),
             Value::Map(m) => m.as_list(),
-            Value::ArgList(v) => v.into_iter().map(|val| val.node).collect(),
+            Value::ArgList(v) => v.elems.into_iter().map(|val| val.node).collect(),
             v => vec![v],
         }
     }
 
+    /// The elements of `self` when treated as a list, which are borrowed
+    /// rather than cloned if `self` is already a list
+    pub fn as_slice(&self) -> Cow<'_, [Value]> {
+        match self {
+            Value::List(v, ..) => Cow::borrowed(v),
+            Value::Map(m) => Cow::owned(m.clone().as_list()),
+            Value::ArgList(v) => Cow::owned(v.elems.iter().map(|val| val.node.clone()).collect()),
+            v => Cow::borrowed(std::slice::from_ref(v)),
+        }
+    }
+
     /// Parses `self` as a selector list, in the same manner as the
     /// `selector-parse()` function.
     ///
@@ -475,24 +650,32 @@ impl Value {
             at_root: parser.at_root,
             at_root_has_selector: parser.at_root_has_selector,
             extender: parser.extender,
+            options: parser.options,
             content_scopes: parser.content_scopes,
+            depth: parser.depth,
+            import_stack: parser.import_stack,
+            budget: parser.budget,
+            call_stack: parser.call_stack,
+            deprecations: parser.deprecations,
+            errors: parser.errors,
+            dependencies: parser.dependencies,
         }
         .parse_selector(allows_parent, true, String::new())
     }
 
     fn selector_string(self, span: Span) -> SassResult<Option<String>> {
         Ok(Some(match self {
-            Value::String(text, ..) => text,
+            Value::String(text, ..) => text.to_string(),
             Value::List(list, sep, ..) if !list.is_empty() => {
                 let mut result = Vec::new();
                 match sep {
                     ListSeparator::Comma => {
-                        for complex in list {
+                        for complex in list.iter().cloned() {
                             if let Value::String(text, ..) = complex {
                                 result.push(text);
                             } else if let Value::List(_, ListSeparator::Space, ..) = complex {
                                 result.push(match complex.selector_string(span)? {
-                                    Some(v) => v,
+                                    Some(v) => v.into(),
                                     None => return Ok(None),
                                 });
                             } else {
@@ -501,7 +684,7 @@ impl Value {
                         }
                     }
                     ListSeparator::Space => {
-                        for compound in list {
+                        for compound in list.iter().cloned() {
                             if let Value::String(text, ..) = compound {
                                 result.push(text);
                             } else {
@@ -509,6 +692,7 @@ impl Value {
                             }
                         }
                     }
+                    ListSeparator::Slash => return Ok(None),
                 }
 
                 result.join(sep.as_str())
@@ -1,92 +1,145 @@
 use std::{
+    cell::Cell,
     cmp::Ordering,
-    convert::{From, TryFrom},
+    convert::From,
     fmt::{self, Display, Write},
-    mem,
+    hash::{Hash, Hasher},
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
 };
 
-use num_bigint::BigInt;
-use num_rational::{BigRational, Rational64};
-use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Signed, Zero};
+use num_traits::{Num, One, Signed, Zero};
 
-use integer::Integer;
+/// The number of digits after the decimal point that are significant when
+/// comparing numbers, and by default when printing them
+pub(crate) const DEFAULT_PRECISION: usize = 10;
 
-mod integer;
+/// Numbers closer together than this are considered equal
+///
+/// This is `10^(-DEFAULT_PRECISION - 1)`
+const EPSILON: f64 = 1e-11;
 
-const PRECISION: usize = 10;
+thread_local!(static PRECISION: Cell<usize> = const { Cell::new(DEFAULT_PRECISION) });
 
-#[derive(Clone, Eq, PartialEq, Ord)]
-pub(crate) enum Number {
-    Small(Rational64),
-    Big(Box<BigRational>),
-}
+/// Sets the number of digits after the decimal point with which numbers are
+/// printed on this thread, until it is dropped
+///
+/// This is a thread local rather than an argument to `Display` so that it
+/// applies to every number printed during a compilation, including those in
+/// colors and `inspect()`, while compilations on other threads are unaffected
+pub(crate) struct PrecisionGuard(usize);
 
-impl Number {
-    pub const fn new_small(val: Rational64) -> Number {
-        Number::Small(val)
+impl PrecisionGuard {
+    pub fn new(precision: usize) -> Self {
+        Self(PRECISION.with(|p| p.replace(precision)))
     }
+}
 
-    pub fn new_big(val: BigRational) -> Number {
-        Number::Big(Box::new(val))
+impl Drop for PrecisionGuard {
+    fn drop(&mut self) {
+        PRECISION.with(|p| p.set(self.0));
     }
+}
 
-    pub fn to_integer(&self) -> Integer {
-        match self {
-            Self::Small(val) => Integer::Small(val.to_integer()),
-            Self::Big(val) => Integer::Big(val.to_integer()),
-        }
-    }
+/// A SassScript number, without units
+///
+/// Like `dart-sass`, numbers are double precision floats which are compared
+/// using "fuzzy" equality: two numbers are equal if they are the same to
+/// `DEFAULT_PRECISION` decimal places
+#[derive(Clone, Copy)]
+pub(crate) struct Number(pub f64);
+
+/// Whether `a` and `b` are equal to `DEFAULT_PRECISION` decimal places
+pub(crate) fn fuzzy_equals(a: f64, b: f64) -> bool {
+    #[allow(clippy::float_cmp)]
+    let exact = a == b;
+    exact || (a - b).abs() <= EPSILON
+}
 
+/// Whether `a < b`, and the two are not fuzzily equal
+pub(crate) fn fuzzy_less_than(a: f64, b: f64) -> bool {
+    a < b && !fuzzy_equals(a, b)
+}
+
+/// Whether `a <= b`, or the two are fuzzily equal
+pub(crate) fn fuzzy_less_than_or_equals(a: f64, b: f64) -> bool {
+    a < b || fuzzy_equals(a, b)
+}
+
+impl Number {
     pub fn small_ratio<A: Into<i64>, B: Into<i64>>(a: A, b: B) -> Self {
-        Number::new_small(Rational64::new(a.into(), b.into()))
+        #[allow(clippy::cast_precision_loss)]
+        Number(a.into() as f64 / b.into() as f64)
     }
 
-    #[allow(dead_code)]
-    pub fn big_ratio<A: Into<BigInt>, B: Into<BigInt>>(a: A, b: B) -> Self {
-        Number::new_big(BigRational::new(a.into(), b.into()))
+    /// Whether this number is within `EPSILON` of an integer
+    pub fn is_int(self) -> bool {
+        if !self.0.is_finite() {
+            return false;
+        }
+        fuzzy_equals(self.0, self.0.round())
     }
 
-    pub fn round(&self) -> Self {
-        match self {
-            Self::Small(val) => Self::Small(val.round()),
-            Self::Big(val) => Self::Big(Box::new(val.round())),
+    /// Returns this number as an integer if it is fuzzily equal to one
+    pub fn as_int(self) -> Option<f64> {
+        if self.is_int() {
+            Some(self.0.round())
+        } else {
+            None
         }
     }
 
-    pub fn ceil(&self) -> Self {
-        match self {
-            Self::Small(val) => Self::Small(val.ceil()),
-            Self::Big(val) => Self::Big(Box::new(val.ceil())),
-        }
+    /// Truncate this number to an integer, rounding first if it is fuzzily
+    /// equal to an integer
+    ///
+    /// Numbers outside of the range of an `i64` saturate to `i64::MIN` or
+    /// `i64::MAX`, which is larger than any valid index
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_integer(self) -> i64 {
+        self.as_int().unwrap_or_else(|| self.0.trunc()) as i64
     }
 
-    pub fn floor(&self) -> Self {
-        match self {
-            Self::Small(val) => Self::Small(val.floor()),
-            Self::Big(val) => Self::Big(Box::new(val.floor())),
+    /// Round this number to the nearest integer, rounding numbers that are
+    /// within `EPSILON` of `.5` away from zero
+    pub fn round(self) -> Self {
+        if !self.0.is_finite() {
+            return self;
         }
+
+        let fract = self.0.rem_euclid(1.0);
+
+        Number(if self.0 > 0.0 {
+            if fuzzy_less_than(fract, 0.5) {
+                self.0.floor()
+            } else {
+                self.0.ceil()
+            }
+        } else if fuzzy_less_than_or_equals(fract, 0.5) {
+            self.0.floor()
+        } else {
+            self.0.ceil()
+        })
     }
 
-    pub fn abs(&self) -> Self {
-        match self {
-            Self::Small(val) => Self::Small(val.abs()),
-            Self::Big(val) => Self::Big(Box::new(val.abs())),
+    pub fn ceil(self) -> Self {
+        match self.as_int() {
+            Some(val) => Number(val),
+            None => Number(self.0.ceil()),
         }
     }
 
-    pub fn is_decimal(&self) -> bool {
-        match self {
-            Self::Small(v) => !v.is_integer(),
-            Self::Big(v) => !v.is_integer(),
+    pub fn floor(self) -> Self {
+        match self.as_int() {
+            Some(val) => Number(val),
+            None => Number(self.0.floor()),
         }
     }
 
-    pub fn fract(&mut self) -> Number {
-        match self {
-            Self::Small(v) => Number::new_small(v.fract()),
-            Self::Big(v) => Number::new_big(v.fract()),
-        }
+    pub fn abs(self) -> Self {
+        Number(self.0.abs())
+    }
+
+    pub fn is_decimal(self) -> bool {
+        !self.is_int()
     }
 
     pub fn clamp<A: Into<Number> + Zero, B: Into<Number>>(self, min: A, max: B) -> Self {
@@ -106,6 +159,72 @@ impl Number {
 
         self
     }
+
+    /// Write this number with at most `precision` digits after the decimal
+    /// point, removing any trailing zeros
+    fn write_rounded(
+        self,
+        text: &str,
+        precision: usize,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let (is_negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+
+        let (whole, fract) = match text.find('.') {
+            Some(idx) => (&text[..idx], &text[idx + 1..]),
+            None => (text, ""),
+        };
+
+        let mut digits: Vec<u8> = whole.bytes().map(|b| b - b'0').collect();
+        let mut whole_len = digits.len();
+
+        let mut fract_digits = fract.bytes().map(|b| b - b'0');
+        digits.extend(fract_digits.by_ref().take(precision));
+
+        if fract_digits.next().map_or(false, |digit| digit >= 5) {
+            let mut idx = digits.len();
+            loop {
+                if idx == 0 {
+                    digits.insert(0, 1);
+                    whole_len += 1;
+                    break;
+                }
+                idx -= 1;
+                if digits[idx] == 9 {
+                    digits[idx] = 0;
+                } else {
+                    digits[idx] += 1;
+                    break;
+                }
+            }
+        }
+
+        while digits.len() > whole_len && digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        let is_zero = digits.iter().all(|&d| d == 0);
+
+        if is_negative && !is_zero {
+            f.write_char('-')?;
+        }
+
+        for &digit in &digits[..whole_len] {
+            f.write_char(char::from(digit + b'0'))?;
+        }
+
+        if digits.len() > whole_len {
+            f.write_char('.')?;
+            for &digit in &digits[whole_len..] {
+                f.write_char(char::from(digit + b'0'))?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for Number {
@@ -116,27 +235,21 @@ impl Default for Number {
 
 impl Zero for Number {
     fn zero() -> Self {
-        Number::new_small(Rational64::from_integer(0))
+        Number(0.0)
     }
 
     fn is_zero(&self) -> bool {
-        match self {
-            Self::Small(v) => v.is_zero(),
-            Self::Big(v) => v.is_zero(),
-        }
+        fuzzy_equals(self.0, 0.0)
     }
 }
 
 impl One for Number {
     fn one() -> Self {
-        Number::new_small(Rational64::from_integer(1))
+        Number(1.0)
     }
 
     fn is_one(&self) -> bool {
-        match self {
-            Self::Small(v) => v.is_one(),
-            Self::Big(v) => v.is_one(),
-        }
+        fuzzy_equals(self.0, 1.0)
     }
 }
 
@@ -150,7 +263,7 @@ impl Num for Number {
 
 impl Signed for Number {
     fn abs(&self) -> Self {
-        self.abs()
+        Number(self.0.abs())
     }
 
     #[cold]
@@ -170,153 +283,112 @@ impl Signed for Number {
     }
 
     fn is_positive(&self) -> bool {
-        match self {
-            Self::Small(v) => v.is_positive(),
-            Self::Big(v) => v.is_positive(),
-        }
+        self.0 > 0.0 && !self.is_zero()
     }
 
     fn is_negative(&self) -> bool {
-        match self {
-            Self::Small(v) => v.is_negative(),
-            Self::Big(v) => v.is_negative(),
-        }
+        self.0 < 0.0 && !self.is_zero()
     }
 }
 
 macro_rules! from_integer {
     ($ty:ty) => {
         impl From<$ty> for Number {
+            #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
             fn from(b: $ty) -> Self {
-                if let Ok(v) = i64::try_from(b) {
-                    Number::Small(Rational64::from_integer(v))
-                } else {
-                    Number::Big(Box::new(BigRational::from_integer(BigInt::from(b))))
-                }
+                Number(b as f64)
             }
         }
     };
 }
 
-macro_rules! from_smaller_integer {
-    ($ty:ty) => {
-        impl From<$ty> for Number {
-            fn from(val: $ty) -> Self {
-                Number::new_small(Rational64::from_integer(val as i64))
-            }
-        }
-    };
-}
-
-impl From<i64> for Number {
-    fn from(val: i64) -> Self {
-        Number::new_small(Rational64::from_integer(val))
-    }
-}
-
-#[allow(clippy::fallible_impl_from)]
 impl From<f64> for Number {
     fn from(b: f64) -> Self {
-        Number::Big(Box::new(BigRational::from_float(b).unwrap()))
+        Number(b)
     }
 }
 
+from_integer!(i64);
 from_integer!(usize);
 from_integer!(isize);
-from_smaller_integer!(i32);
-from_smaller_integer!(u32);
-from_smaller_integer!(u8);
+from_integer!(i32);
+from_integer!(u32);
+from_integer!(u8);
 
 impl fmt::Debug for Number {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Small(..) => write!(f, "Number::Small( {} )", self),
-            Self::Big(..) => write!(f, "Number::Big( {} )", self),
-        }
+        write!(f, "Number( {} )", self)
     }
 }
 
 impl Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut whole = self.to_integer().abs();
-        let has_decimal = self.is_decimal();
-        let mut frac = self.abs().fract();
-        let mut dec = String::with_capacity(if has_decimal { PRECISION } else { 0 });
-        if has_decimal {
-            for _ in 0..(PRECISION - 1) {
-                frac *= 10_i64;
-                write!(dec, "{}", frac.to_integer())?;
-                frac = frac.fract();
-                if frac.is_zero() {
-                    break;
-                }
-            }
-            if !frac.is_zero() {
-                let end = (frac * 10_i64).round().to_integer();
-                if end.is_ten() {
-                    loop {
-                        match dec.pop() {
-                            Some('9') => continue,
-                            Some(c) => {
-                                dec.push(char::from(c as u8 + 1));
-                                break;
-                            }
-                            None => {
-                                whole += 1;
-                                break;
-                            }
-                        }
-                    }
-                } else if end.is_zero() {
-                    loop {
-                        match dec.pop() {
-                            Some('0') => continue,
-                            Some(c) => {
-                                dec.push(c);
-                                break;
-                            }
-                            None => break,
-                        }
-                    }
-                } else {
-                    write!(dec, "{}", end)?;
-                }
-            }
+        if self.0.is_nan() {
+            return f.write_str("NaN");
         }
 
-        if self.is_negative() && (!whole.is_zero() || !dec.is_empty()) {
-            f.write_char('-')?;
+        if self.0.is_infinite() {
+            return f.write_str(if self.0 > 0.0 {
+                "Infinity"
+            } else {
+                "-Infinity"
+            });
         }
-        write!(f, "{}", whole)?;
-        if !dec.is_empty() {
-            f.write_char('.')?;
-            write!(f, "{}", dec)?;
+
+        if let Some(int) = self.as_int() {
+            // avoid emitting `-0`
+            return write!(f, "{}", if int == 0.0 { 0.0 } else { int });
         }
-        Ok(())
+
+        let text = self.0.to_string();
+        let precision = PRECISION.with(Cell::get);
+
+        // any number with fewer than `precision + 2` characters has at most
+        // `precision` digits after the decimal point, so it is safe to emit
+        // directly
+        if text.len() < precision + 2 {
+            return f.write_str(&text);
+        }
+
+        self.write_rounded(&text, precision, f)
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        fuzzy_equals(self.0, other.0)
+    }
+}
+
+impl Eq for Number {}
+
+/// Numbers which are fuzzily equal must hash identically, so we hash the
+/// number rounded to the nearest multiple of `EPSILON`
+///
+/// Like `dart-sass`, this may rarely give different hashes to two numbers
+/// that straddle a rounding boundary
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let rounded = (self.0 / EPSILON).round();
+        // `0.0` and `-0.0` are equal, but have different bit patterns
+        #[allow(clippy::float_cmp)]
+        let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+        rounded.to_bits().hash(state);
     }
 }
 
 impl PartialOrd for Number {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => val1.partial_cmp(val2),
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = (*val1).into();
-                    BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1))
-                        .partial_cmp(val2)
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = (*val2).into();
-                    (**val1).partial_cmp(&BigRational::new_raw(
-                        BigInt::from(tuple.0),
-                        BigInt::from(tuple.1),
-                    ))
-                }
-                Self::Big(val2) => val1.partial_cmp(val2),
-            },
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if fuzzy_equals(self.0, other.0) {
+            Ordering::Equal
+        } else {
+            self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
         }
     }
 }
@@ -325,40 +397,7 @@ impl Add for Number {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_add(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                + BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) + *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 + *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        (*val1)
-                            + BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
-            },
-        }
+        Number(self.0 + other.0)
     }
 }
 
@@ -366,47 +405,13 @@ impl Add<&Self> for Number {
     type Output = Self;
 
     fn add(self, other: &Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_add(val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = (*val2).into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                + BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1))
-                            + *val2.clone(),
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 + *val2.clone())),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = (*val2).into();
-                    Self::Big(Box::new(
-                        *val1 + BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
-            },
-        }
+        Number(self.0 + other.0)
     }
 }
 
 impl AddAssign for Number {
     fn add_assign(&mut self, other: Self) {
-        let tmp = mem::take(self);
-        *self = tmp + other;
+        self.0 += other.0;
     }
 }
 
@@ -414,46 +419,13 @@ impl Sub for Number {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_sub(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                - BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) - *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 - *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 - BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
-            },
-        }
+        Number(self.0 - other.0)
     }
 }
 
 impl SubAssign for Number {
     fn sub_assign(&mut self, other: Self) {
-        let tmp = mem::take(self);
-        *self = tmp - other;
+        self.0 -= other.0;
     }
 }
 
@@ -461,64 +433,28 @@ impl Mul for Number {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_mul(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                * BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) * *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 * *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 * BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
-            },
-        }
+        Number(self.0 * other.0)
     }
 }
 
 impl Mul<i64> for Number {
     type Output = Self;
 
+    #[allow(clippy::cast_precision_loss)]
     fn mul(self, other: i64) -> Self {
-        match self {
-            Self::Small(val1) => Self::Small(val1 * other),
-            Self::Big(val1) => Self::Big(Box::new(*val1 * BigInt::from(other))),
-        }
+        Number(self.0 * other as f64)
     }
 }
 
 impl MulAssign<i64> for Number {
     fn mul_assign(&mut self, other: i64) {
-        let tmp = mem::take(self);
-        *self = tmp * other;
+        *self = *self * other;
     }
 }
 
 impl MulAssign for Number {
     fn mul_assign(&mut self, other: Self) {
-        let tmp = mem::take(self);
-        *self = tmp * other;
+        self.0 *= other.0;
     }
 }
 
@@ -526,46 +462,13 @@ impl Div for Number {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_div(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                / BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) / *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 / *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 / BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
-            },
-        }
+        Number(self.0 / other.0)
     }
 }
 
 impl DivAssign for Number {
     fn div_assign(&mut self, other: Self) {
-        let tmp = mem::take(self);
-        *self = tmp / other;
+        self.0 /= other.0;
     }
 }
 
@@ -573,41 +476,13 @@ impl Rem for Number {
     type Output = Self;
 
     fn rem(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                // todo: checked_rem for ratio?
-                Self::Small(val2) => {
-                    let tuple1: (i64, i64) = val1.into();
-                    let tuple2: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                            % BigRational::new_raw(BigInt::from(tuple2.0), BigInt::from(tuple2.1)),
-                    ))
-                }
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) % *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 % *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 % BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
-            },
-        }
+        Number(self.0 % other.0)
     }
 }
 
 impl RemAssign for Number {
     fn rem_assign(&mut self, other: Self) {
-        let tmp = mem::take(self);
-        *self = tmp % other;
+        *self = *self % other;
     }
 }
 
@@ -615,9 +490,6 @@ impl Neg for Number {
     type Output = Self;
 
     fn neg(self) -> Self {
-        match self {
-            Self::Small(v) => Self::Small(-v),
-            Self::Big(v) => Self::Big(Box::new(-*v)),
-        }
+        Number(-self.0)
     }
 }
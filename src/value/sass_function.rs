@@ -50,8 +50,8 @@ impl SassFunction {
 
     pub fn call(self, args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         match self {
-            Self::Builtin(f, ..) => f.0(args, parser),
-            Self::UserDefined(f, ..) => parser.eval_function(*f, args),
+            Self::Builtin(f, ..) => args.call_builtin(f.0, parser),
+            Self::UserDefined(f, name) => parser.eval_function(*f, name, args),
         }
     }
 }
@@ -0,0 +1,52 @@
+//! Sass mixins are first-class values that can be obtained with `get-mixin()`
+//! and included with `meta.apply()`
+//!
+//! Only user-defined mixins may be referenced, as there are no builtin mixins
+//! other than `meta.apply()` itself.
+
+use std::fmt;
+
+use crate::{atrule::Mixin, common::Identifier};
+
+/// A reference to a Sass mixin
+///
+/// The mixin name is stored in addition to the body
+/// for use in the builtin function `inspect()`
+#[derive(Clone, Eq, PartialEq)]
+pub(crate) struct SassMixin {
+    mixin: Box<Mixin>,
+    name: Identifier,
+}
+
+impl SassMixin {
+    pub fn new(mixin: Mixin, name: Identifier) -> Self {
+        SassMixin {
+            mixin: Box::new(mixin),
+            name,
+        }
+    }
+
+    /// Get the name of the mixin referenced
+    ///
+    /// Used mainly in debugging and `inspect()`
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    /// Whether the mixin contains an `@content` rule
+    pub fn accepts_content(&self) -> bool {
+        self.mixin.accepts_content_block
+    }
+
+    pub fn into_mixin(self) -> Mixin {
+        *self.mixin
+    }
+}
+
+impl fmt::Debug for SassMixin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SassMixin")
+            .field("name", &self.name())
+            .finish()
+    }
+}
@@ -0,0 +1,58 @@
+//! Numbers separated by `/` inside a space separated list, such as the alpha
+//! channel in `rgb(1 2 3 / 0.5)` or the `1px/2px` in `foo(1px/2px 3px)`
+//!
+//! The slash may be either a separator or a division, so the original operands
+//! are kept and printed as they were written. Operators act on the quotient.
+
+use codemap::Span;
+
+use crate::{error::SassResult, Cow};
+
+use super::Value;
+
+#[derive(Debug, Clone)]
+pub(crate) struct SlashNumber {
+    quotient: Value,
+    numerator: Value,
+    denominator: Value,
+}
+
+impl SlashNumber {
+    pub const fn new(quotient: Value, numerator: Value, denominator: Value) -> Self {
+        SlashNumber {
+            quotient,
+            numerator,
+            denominator,
+        }
+    }
+
+    /// The result of dividing the numerator by the denominator
+    pub fn quotient(&self) -> &Value {
+        &self.quotient
+    }
+
+    pub fn into_quotient(self) -> Value {
+        self.quotient
+    }
+
+    /// The operands on either side of the slash
+    pub fn operands(&self) -> (&Value, &Value) {
+        (&self.numerator, &self.denominator)
+    }
+
+    pub fn to_css_string(&self, span: Span) -> SassResult<Cow<'static, str>> {
+        Ok(Cow::owned(format!(
+            "{}/{}",
+            self.numerator.to_css_string(span)?,
+            self.denominator.to_css_string(span)?
+        )))
+    }
+
+    pub fn inspect(&self, span: Span) -> SassResult<Cow<'static, str>> {
+        Ok(Cow::owned(format!(
+            "{}/{}",
+            self.numerator.inspect(span)?,
+            self.denominator.inspect(span)?
+        )))
+    }
+}
@@ -56,7 +56,35 @@ test!(
     "a {\n  color: false;\n}\n"
 );
 error!(
-    #[ignore = "blocked on a rewrite of value eval"]
     properly_bubbles_error_when_invalid_char_after_and,
     "a {\n  color: false and? foo;\n}\n", "Error: expected \";\"."
 );
+test!(
+    short_circuits_undefined_variable,
+    "a {\n  color: false and $undefined;\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    short_circuits_parenthesized_rhs,
+    "$map: null;\na {\n  color: $map and (map-get($map, a) == 1);\n}\n",
+    ""
+);
+test!(
+    short_circuits_function_arguments,
+    "@function throw() {\n  @error \"evaluated\";\n}\n\na {\n  color: false and inspect(throw());\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    evaluates_lhs_once,
+    "$i: 0;\n@function inc() {\n  $i: $i + 1 !global;\n  @return $i;\n}\n\na {\n  color: inc() and true;\n  count: $i;\n}\n",
+    "a {\n  color: true;\n  count: 1;\n}\n"
+);
+test!(
+    short_circuit_continues_to_or,
+    "a {\n  color: false and $undefined or 2;\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+error!(
+    does_not_short_circuit_when_lhs_is_true,
+    "a {\n  color: true and $undefined;\n}\n", "Error: Undefined variable."
+);
@@ -94,3 +94,52 @@ test!(
     }",
     ""
 );
+test!(
+    keywords_of_arglist,
+    "@function foo($args...) {\n  @return inspect(keywords($args));\n}\na {\n  color: foo(1, $b: 2, $c: 3);\n}\n",
+    "a {\n  color: (b: 2, c: 3);\n}\n"
+);
+test!(
+    keywords_of_arglist_without_keywords,
+    "@function foo($args...) {\n  @return inspect(keywords($args));\n}\na {\n  color: foo(1, 2);\n}\n",
+    "a {\n  color: ();\n}\n"
+);
+test!(
+    keywords_in_meta_module,
+    "@use \"sass:meta\";\n@function foo($args...) {\n  @return inspect(meta.keywords($args));\n}\na {\n  color: foo($a: 1);\n}\n",
+    "a {\n  color: (a: 1);\n}\n"
+);
+test!(
+    keywords_are_passed_on_through_function_splat,
+    "@function inner($a, $b: 0) {\n  @return $a $b;\n}\n@function outer($args...) {\n  @return inner($args...);\n}\na {\n  color: outer(1, $b: 2);\n}\n",
+    "a {\n  color: 1 2;\n}\n"
+);
+test!(
+    keywords_are_passed_on_through_mixin_splat,
+    "@mixin inner($a, $b: 0) {\n  color: $a $b;\n}\n@mixin outer($args...) {\n  @include inner($args...);\n}\na {\n  @include outer(1, $b: 2);\n}\n",
+    "a {\n  color: 1 2;\n}\n"
+);
+test!(
+    keywords_are_passed_on_to_variadic_parameter,
+    "@mixin inner($a, $rest...) {\n  color: inspect(keywords($rest));\n}\n@mixin outer($args...) {\n  @include inner($args...);\n}\na {\n  @include outer(1, $b: 2, $c: 3);\n}\n",
+    "a {\n  color: (b: 2, c: 3);\n}\n"
+);
+test!(
+    keywords_are_passed_to_function_through_call,
+    "@function foo($args...) {\n  @return inspect(keywords($args));\n}\na {\n  color: call(get-function(foo), 1, $b: 2);\n}\n",
+    "a {\n  color: (b: 2);\n}\n"
+);
+error!(
+    unused_keyword_argument_to_arglist,
+    "@function foo($args...) {\n  @return length($args);\n}\na {\n  color: foo(1, $b: 2);\n}\n",
+    "Error: No argument named $b."
+);
+error!(
+    unused_keyword_arguments_to_arglist,
+    "@mixin foo($args...) {\n  color: length($args);\n}\na {\n  @include foo($b: 1, $c: 2, $d: 3);\n}\n",
+    "Error: No arguments named $b, $c or $d."
+);
+error!(
+    keywords_of_non_arglist,
+    "a {\n  color: keywords((a: b));\n}\n", "Error: $args: (a: b) is not an argument list."
+);
@@ -72,3 +72,8 @@ error!(
     missing_closing_curly_brace,
     "@at-root {", "Error: expected \"}\"."
 );
+test!(
+    at_root_inside_unknown_at_rule,
+    "@foo {\n  @at-root {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@foo {\n  a {\n    color: red;\n  }\n}\n"
+);
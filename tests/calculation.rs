@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(
+    calc_simplifies_compatible_units,
+    "a {\n  color: calc(1in + 96px);\n}\n",
+    "a {\n  color: 2in;\n}\n"
+);
+test!(
+    calc_preserves_incompatible_units,
+    "a {\n  color: calc(100% - 10px);\n}\n",
+    "a {\n  color: calc(100% - 10px);\n}\n"
+);
+test!(
+    calc_with_variable,
+    "$a: 10px;\na {\n  color: calc($a * 2);\n}\n",
+    "a {\n  color: 20px;\n}\n"
+);
+test!(
+    calc_with_variable_incompatible,
+    "$a: 10px;\na {\n  color: calc(100% - $a);\n}\n",
+    "a {\n  color: calc(100% - 10px);\n}\n"
+);
+test!(
+    calc_nested_calc,
+    "a {\n  color: calc(calc(1px + 2px) * 3);\n}\n",
+    "a {\n  color: 9px;\n}\n"
+);
+test!(
+    calc_nested_parens_preserved,
+    "a {\n  color: calc(1px + (2% - 3em) * 2);\n}\n",
+    "a {\n  color: calc(1px + (2% - 3em) * 2);\n}\n"
+);
+test!(
+    calc_var_preserved,
+    "a {\n  color: calc(var(--foo) + 1px);\n}\n",
+    "a {\n  color: calc(var(--foo) + 1px);\n}\n"
+);
+test!(
+    calc_interpolation,
+    "$a: 10px;\na {\n  color: calc(#{$a} + 1px);\n}\n",
+    "a {\n  color: calc((10px) + 1px);\n}\n"
+);
+test!(
+    calc_in_variable_preserved,
+    "$a: calc(1% + 1px);\na {\n  color: $a;\n}\n",
+    "a {\n  color: calc(1% + 1px);\n}\n"
+);
+test!(
+    calc_inside_calc_variable,
+    "$a: calc(1% + 1px);\na {\n  color: calc($a * 2);\n}\n",
+    "a {\n  color: calc((1% + 1px) * 2);\n}\n"
+);
+test!(
+    clamp_simplifies,
+    "a {\n  color: clamp(1px, 2px, 3px);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    clamp_simplifies_to_min,
+    "a {\n  color: clamp(1px, 0px, 3px);\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    clamp_incompatible_units,
+    "a {\n  color: clamp(1px, 10%, 3px);\n}\n",
+    "a {\n  color: clamp(1px, 10%, 3px);\n}\n"
+);
+test!(
+    min_nested_calc,
+    "a {\n  color: min(calc(1px + 2px), 5px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    max_var_preserved,
+    "a {\n  color: max(var(--a), env(b));\n}\n",
+    "a {\n  color: max(var(--a), env(b));\n}\n"
+);
+test!(
+    type_of_calculation,
+    "a {\n  color: type-of(calc(1% + 1px));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    inspect_calculation,
+    "a {\n  color: inspect(calc(1% + 1px));\n}\n",
+    "a {\n  color: calc(1% + 1px);\n}\n"
+);
+test!(
+    calc_name,
+    "a {\n  color: calc-name(calc(1% + 1px));\n}\n",
+    "a {\n  color: \"calc\";\n}\n"
+);
+test!(
+    calc_name_clamp,
+    "a {\n  color: calc-name(clamp(1%, 1px, 2em));\n}\n",
+    "a {\n  color: \"clamp\";\n}\n"
+);
+test!(
+    calc_args,
+    "a {\n  color: calc-args(min(1%, 1px, var(--a)));\n}\n",
+    "a {\n  color: 1%, 1px, var(--a);\n}\n"
+);
+test!(
+    calc_equality,
+    "a {\n  color: calc(1% + 1px) == calc(1% + 1px);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    calc_unitless_division_without_whitespace,
+    "a {\n  color: calc(1/3);\n}\n",
+    "a {\n  color: 0.3333333333;\n}\n"
+);
+test!(
+    calc_unitless_multiplication_without_whitespace,
+    "a {\n  color: calc(2*3);\n}\n",
+    "a {\n  color: 6;\n}\n"
+);
+test!(
+    calc_division_without_whitespace_inside_sum,
+    "a {\n  color: calc(1px + 1/3 * 1%);\n}\n",
+    "a {\n  color: calc(1px + 0.3333333333%);\n}\n"
+);
+test!(
+    calc_variable_division_without_whitespace,
+    "$a: 3;\na {\n  color: calc(1/$a);\n}\n",
+    "a {\n  color: 0.3333333333;\n}\n"
+);
+error!(
+    calc_incompatible_units,
+    "a {\n  color: calc(1px + 1s);\n}\n", "Error: 1px and 1s are incompatible."
+);
+error!(
+    calc_name_not_calculation,
+    "a {\n  color: calc-name(1px);\n}\n", "Error: $calc: 1px is not a calculation."
+);
+error!(
+    calc_addition_to_number,
+    "a {\n  color: calc(1% + 1px) + 1px;\n}\n",
+    "Error: Undefined operation \"calc(1% + 1px) + 1px\"."
+);
@@ -0,0 +1,125 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(
+    luminance_white,
+    "@use \"sass:color\";\na {\n  color: color.luminance(white);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    luminance_black,
+    "@use \"sass:color\";\na {\n  color: color.luminance(black);\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    luminance_red,
+    "@use \"sass:color\";\na {\n  color: color.luminance(red);\n}\n",
+    "a {\n  color: 0.2126;\n}\n"
+);
+test!(
+    user_defined_luminance_takes_precedence,
+    "@function luminance($color) {\n  @return custom;\n}\na {\n  color: luminance(red);\n}\n",
+    "a {\n  color: custom;\n}\n"
+);
+test!(
+    contrast_black_white,
+    "@use \"sass:color\";\na {\n  color: color.contrast(black, white);\n}\n",
+    "a {\n  color: 21;\n}\n"
+);
+test!(
+    contrast_is_symmetric,
+    "@use \"sass:color\";\na {\n  color: color.contrast(white, #777) == color.contrast(#777, white);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    contrast_gray_on_white,
+    "@use \"sass:color\";\na {\n  color: color.contrast(#777, white);\n}\n",
+    "a {\n  color: 4.4780894536;\n}\n"
+);
+test!(
+    contrast_same_color,
+    "@use \"sass:color\";\na {\n  color: color.contrast(red, red);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    color_contrast_variadic,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(#222, #333, #ddd);\n}\n",
+    "a {\n  color: #ddd;\n}\n"
+);
+test!(
+    color_contrast_list_of_candidates,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(white, (#ccc, #eee));\n}\n",
+    "a {\n  color: #ccc;\n}\n"
+);
+test!(
+    color_contrast_css_syntax,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(wheat vs tan, sienna, #d2691e);\n}\n",
+    "a {\n  color: sienna;\n}\n"
+);
+test!(
+    color_contrast_target_aa,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(white, #ccc, #777, black, $target: AA);\n}\n",
+    "a {\n  color: black;\n}\n"
+);
+test!(
+    color_contrast_target_number,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(white, #ccc, #777, black, $target: 4);\n}\n",
+    "a {\n  color: #777;\n}\n"
+);
+test!(
+    color_contrast_target_not_reached,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(white, #eee, #ccc, $target: AAA);\n}\n",
+    "a {\n  color: #ccc;\n}\n"
+);
+test!(
+    color_contrast_special_fn,
+    "a {\n  color: color-contrast(var(--bg) vs red, blue);\n}\n"
+);
+test!(
+    error_when_contrast_fails_aa,
+    "@use \"sass:color\";\n@if color.contrast(#999, white) < 4.5 {\n  a {\n    color: fails;\n  }\n}\n",
+    "a {\n  color: fails;\n}\n"
+);
+error!(
+    luminance_not_color,
+    "@use \"sass:color\";\na {\n  color: color.luminance(1);\n}\n",
+    "Error: $color: 1 is not a color."
+);
+error!(
+    contrast_not_color,
+    "@use \"sass:color\";\na {\n  color: color.contrast(red, 1);\n}\n",
+    "Error: $color2: 1 is not a color."
+);
+error!(
+    color_contrast_no_candidates,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(red, ());\n}\n",
+    "Error: $candidates: At least one color must be passed."
+);
+error!(
+    color_contrast_candidate_not_color,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(red, blue, 1);\n}\n",
+    "Error: $candidates: 1 is not a color."
+);
+error!(
+    color_contrast_invalid_target,
+    "@use \"sass:color\";\na {\n  color: color.color-contrast(red, blue, $target: 1px);\n}\n",
+    "Error: $target: Expected 1px to be a unitless number, AA, or AAA."
+);
+test!(
+    plain_css_contrast_filter,
+    "a {\n  filter: contrast(150%);\n}\n"
+);
+test!(
+    plain_css_contrast_backdrop_filter,
+    "a {\n  backdrop-filter: contrast(2);\n}\n"
+);
+test!(
+    plain_css_color_contrast,
+    "a {\n  color: color-contrast(wheat vs tan, sienna, #d2691e);\n}\n"
+);
+test!(
+    global_luminance_is_plain_css,
+    "a {\n  color: luminance(red);\n}\n"
+);
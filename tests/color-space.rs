@@ -0,0 +1,221 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(lab_preserved, "a {\n  color: lab(50% 40 59.5);\n}\n");
+test!(
+    lab_negative_channel_slash_alpha,
+    "a {\n  color: lab(50% 40 -59.5 / 0.5);\n}\n"
+);
+test!(
+    lab_unitless_lightness,
+    "a {\n  color: lab(50 40 59.5);\n}\n",
+    "a {\n  color: lab(50% 40 59.5);\n}\n"
+);
+test!(
+    lab_percent_channels,
+    "a {\n  color: lab(50% 100% -50%);\n}\n",
+    "a {\n  color: lab(50% 125 -62.5);\n}\n"
+);
+test!(
+    lab_lightness_clamped,
+    "a {\n  color: lab(150% 0 0);\n}\n",
+    "a {\n  color: lab(100% 0 0);\n}\n"
+);
+test!(lch_preserved, "a {\n  color: lch(50% 30 120deg);\n}\n");
+test!(
+    lch_hue_converted_to_deg,
+    "a {\n  color: lch(50% 30 0.5turn);\n}\n",
+    "a {\n  color: lch(50% 30 180deg);\n}\n"
+);
+test!(lch_missing_hue, "a {\n  color: lch(50% 30 none);\n}\n");
+test!(
+    oklab_unitless_lightness,
+    "a {\n  color: oklab(0.5 0.1 -0.1);\n}\n",
+    "a {\n  color: oklab(50% 0.1 -0.1);\n}\n"
+);
+test!(oklch_preserved, "a {\n  color: oklch(70% 0.1 120deg);\n}\n");
+test!(
+    oklch_percent_chroma,
+    "a {\n  color: oklch(70% 50% 120);\n}\n",
+    "a {\n  color: oklch(70% 0.2 120deg);\n}\n"
+);
+test!(
+    oklch_special_fn_channel,
+    "a {\n  color: oklch(var(--l) 0.1 120);\n}\n"
+);
+test!(
+    color_display_p3,
+    "a {\n  color: color(display-p3 1 0 0);\n}\n"
+);
+test!(
+    color_srgb_slash_alpha,
+    "a {\n  color: color(srgb 1 0 0 / 0.5);\n}\n"
+);
+test!(
+    color_xyz_d65_is_xyz,
+    "a {\n  color: color(xyz-d65 0.5 0.5 0.5);\n}\n",
+    "a {\n  color: color(xyz 0.5 0.5 0.5);\n}\n"
+);
+test!(
+    color_special_fn,
+    "a {\n  color: color(var(--space) 1 0 0);\n}\n"
+);
+test!(
+    equality_same_space,
+    "a {\n  color: lab(50% 40 59.5) == lab(50% 40 59.5);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    equality_different_space,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, srgb) == red;\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    to_space_oklch,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, oklch);\n}\n",
+    "a {\n  color: oklch(62.7955363921% 0.2576833038 29.2338802796deg);\n}\n"
+);
+test!(
+    to_space_lab,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, lab);\n}\n",
+    "a {\n  color: lab(54.290542947% 80.8049203346 69.890988259);\n}\n"
+);
+test!(
+    to_space_powerless_hue_is_missing,
+    "@use \"sass:color\";\na {\n  color: color.to-space(gray, oklch);\n}\n",
+    "a {\n  color: oklch(59.9870805622% 0 none);\n}\n"
+);
+test!(
+    to_space_rectangular,
+    "@use \"sass:color\";\na {\n  color: color.to-space(oklch(70% 0.1 120deg), oklab);\n}\n",
+    "a {\n  color: oklab(70% -0.05 0.0866025404);\n}\n"
+);
+test!(
+    to_space_legacy,
+    "@use \"sass:color\";\na {\n  color: color.to-space(color(srgb 1 0 0), rgb);\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    to_space_roundtrip,
+    "@use \"sass:color\";\na {\n  color: color.to-space(color.to-space(red, xyz), srgb);\n}\n",
+    "a {\n  color: color(srgb 1 0 0);\n}\n"
+);
+test!(
+    is_in_gamut_true,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(oklch(50% 0.1 120deg), srgb);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    is_in_gamut_false,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(color(display-p3 1 0 0), srgb);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    is_in_gamut_unbounded_space,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(lab(50% 200 200));\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    to_gamut_clip,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(color(display-p3 1 0 0), srgb, $method: clip);\n}\n",
+    "a {\n  color: color(display-p3 0.9174875573 0.2002868077 0.1385605912);\n}\n"
+);
+test!(
+    to_gamut_local_minde,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(oklch(90% 0.4 120deg), srgb, $method: local-minde);\n}\n",
+    "a {\n  color: oklch(90.193188994% 0.2155357883 120.6245599209deg);\n}\n"
+);
+test!(
+    to_gamut_already_in_gamut,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(oklch(50% 0.1 120deg), srgb, $method: local-minde);\n}\n",
+    "a {\n  color: oklch(50% 0.1 120deg);\n}\n"
+);
+test!(
+    channel_own_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(oklch(70% 0.1 120deg), \"hue\");\n}\n",
+    "a {\n  color: 120deg;\n}\n"
+);
+test!(
+    channel_other_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"lightness\", $space: lab);\n}\n",
+    "a {\n  color: 54.290542947%;\n}\n"
+);
+test!(
+    channel_legacy,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"red\");\n}\n",
+    "a {\n  color: 255;\n}\n"
+);
+test!(
+    channel_alpha,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(50% 0 0 / 0.25), \"alpha\");\n}\n",
+    "a {\n  color: 0.25;\n}\n"
+);
+test!(
+    mix_method_oklch,
+    "a {\n  color: mix(red, blue, $method: oklch);\n}\n",
+    "a {\n  color: #ba00c2;\n}\n"
+);
+test!(
+    mix_method_positional,
+    "a {\n  color: mix(red, blue, 50%, srgb);\n}\n",
+    "a {\n  color: purple;\n}\n"
+);
+test!(
+    mix_method_shorter_hue,
+    "a {\n  color: mix(red, blue, $method: hsl);\n}\n",
+    "a {\n  color: fuchsia;\n}\n"
+);
+test!(
+    mix_method_longer_hue,
+    "a {\n  color: mix(red, blue, $method: hsl longer hue);\n}\n",
+    "a {\n  color: lime;\n}\n"
+);
+test!(
+    mix_preserves_space_of_first_color,
+    "a {\n  color: mix(oklch(70% 0.1 120deg), white, $method: oklch);\n}\n",
+    "a {\n  color: oklch(85% 0.05 120deg);\n}\n"
+);
+error!(
+    color_unknown_space,
+    "a {\n  color: color(foo 1 2 3);\n}\n", "Error: $description: Unknown color space \"foo\"."
+);
+error!(
+    lab_missing_channel,
+    "a {\n  color: lab(1 2);\n}\n", "Error: Missing element $b."
+);
+error!(
+    lab_invalid_unit,
+    "a {\n  color: lab(50% 1px 2);\n}\n", "Error: $a: Expected 1px to have no units or \"%\"."
+);
+error!(
+    lch_hue_not_angle,
+    "a {\n  color: lch(50% 1 2px);\n}\n",
+    "Error: $hue: Expected 2px to have an angle unit (deg, grad, rad, turn)."
+);
+error!(
+    mix_non_legacy_without_method,
+    "a {\n  color: mix(lab(50% 1 2), red);\n}\n",
+    "Error: $color1: To use color.mix() with non-legacy color lab(50% 1 2), you must provide a $method."
+);
+error!(
+    mix_hue_method_rectangular_space,
+    "a {\n  color: mix(red, blue, $method: srgb longer hue);\n}\n",
+    "Error: $method: Hue interpolation method \"longer hue\" may not be set for rectangular color space srgb."
+);
+error!(
+    to_gamut_without_method,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(red);\n}\n",
+    "Error: color.to-gamut() requires a $method argument for forwards-compatibility with changes in the CSS spec. Suggestion:"
+);
+error!(
+    to_space_unknown_space,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, foo);\n}\n",
+    "Error: $space: Unknown color space \"foo\"."
+);
+error!(
+    channel_unknown,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"foo\");\n}\n",
+    "Error: $channel: Color red has no channel named foo."
+);
@@ -96,6 +96,30 @@ test!(
     "a {\n  color: rgba(1 2 3);\n}\n",
     "a {\n  color: #010203;\n}\n"
 );
+test!(
+    rgb_one_arg_slash_alpha,
+    "a {\n  color: rgb(1 2 3 / 0.5);\n}\n",
+    "a {\n  color: rgba(1, 2, 3, 0.5);\n}\n"
+);
+test!(
+    rgba_one_arg_slash_alpha_percent,
+    "a {\n  color: rgba(255 0 0 / 50%);\n}\n",
+    "a {\n  color: rgba(255, 0, 0, 0.5);\n}\n"
+);
+test!(
+    rgb_one_arg_slash_alpha_special_fn,
+    "a {\n  color: rgb(1 2 3 / var(--alpha));\n}\n"
+);
+test!(
+    hsl_one_arg_slash_alpha,
+    "a {\n  color: hsl(120 50% 50% / 0.25);\n}\n",
+    "a {\n  color: rgba(64, 191, 64, 0.25);\n}\n"
+);
+error!(
+    rgb_one_arg_slash_alpha_has_unit,
+    "a {\n  color: rgb(1 2 3 / 1px);\n}\n",
+    "Error: $alpha: Expected 1px to have no units or \"%\"."
+);
 test!(
     rgb_two_args,
     "a {\n  color: rgb(#123, 0);\n}\n",
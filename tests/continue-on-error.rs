@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+fn errors(input: &str, options: &grass::Options) -> Vec<String> {
+    match grass::from_string_with_options(input.to_string(), options) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e
+            .errors()
+            .iter()
+            .map(|e| e.to_string().lines().next().unwrap().to_owned())
+            .collect(),
+    }
+}
+
+fn continue_on_error() -> grass::Options {
+    grass::Options::default().continue_on_error(true)
+}
+
+#[test]
+fn stops_at_first_error_by_default() {
+    assert_eq!(
+        vec!["Error: Undefined variable."],
+        errors("a { b: $c; }\nd { e: $f; }", &grass::Options::default())
+    );
+}
+
+#[test]
+fn reports_every_error() {
+    assert_eq!(
+        vec![
+            "Error: Undefined variable.",
+            "Error: \"foo\"",
+            "Error: Undefined mixin."
+        ],
+        errors(
+            "a { b: $c; }\n@error \"foo\";\nd { @include e; }",
+            &continue_on_error()
+        )
+    );
+}
+
+#[test]
+fn continues_within_block() {
+    assert_eq!(
+        vec!["Error: \"foo\"", "Error: \"bar\""],
+        errors(
+            "a { @error \"foo\"; b: c; @error \"bar\"; }",
+            &continue_on_error()
+        )
+    );
+}
+
+#[test]
+fn skips_interpolation() {
+    assert_eq!(
+        vec!["Error: Undefined variable.", "Error: \"foo\""],
+        errors(
+            "a { b: #{$c} d; e: f; }\n@error \"foo\";",
+            &continue_on_error()
+        )
+    );
+}
+
+#[test]
+fn skips_else_blocks() {
+    assert_eq!(
+        vec!["Error: Undefined variable.", "Error: \"foo\""],
+        errors(
+            "@if $a { b { c: d; } } @else if true { e { f: g; } } @else { h { i: j; } }\n@error \"foo\";",
+            &continue_on_error()
+        )
+    );
+}
+
+#[test]
+fn error_in_function_skips_call() {
+    assert_eq!(
+        vec!["Error: Undefined variable.", "Error: \"foo\""],
+        errors(
+            "@function foo() { @return $a; }\na { b: foo(); c: d; }\n@error \"foo\";",
+            &continue_on_error()
+        )
+    );
+}
+
+#[test]
+fn state_is_restored_after_error_in_mixin() {
+    assert_eq!(
+        vec!["Error: \"foo\"", "Error: 1"],
+        errors(
+            "@mixin foo { @content; }\n$a: 1;\na { @include foo { @error \"foo\"; } }\n@error $a;",
+            &continue_on_error()
+        )
+    );
+}
+
+#[test]
+fn recovered_error_has_trace() {
+    match grass::from_string_with_options(
+        "@mixin foo { @error \"foo\"; }\na { @include foo; }\n@error \"bar\";".to_string(),
+        &continue_on_error(),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            vec!["foo()", "root stylesheet"],
+            e.errors()[0]
+                .trace()
+                .iter()
+                .map(grass::StackFrame::member)
+                .collect::<Vec<&str>>()
+        ),
+    }
+}
+
+#[test]
+fn single_error_is_not_combined() {
+    let input = "a { b: $c; }";
+    assert_eq!(
+        grass::from_string(input.to_string())
+            .unwrap_err()
+            .to_string(),
+        grass::from_string_with_options(input.to_string(), &continue_on_error())
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn exceeding_max_steps_is_not_recovered_from() {
+    assert_eq!(
+        vec!["Error: Maximum number of evaluation steps exceeded."],
+        errors(
+            "@while true {}\n@error \"foo\";",
+            &continue_on_error().max_steps(100)
+        )
+    );
+}
+
+#[test]
+fn no_errors() {
+    assert_eq!(
+        "a {\n  b: c;\n}\n",
+        grass::from_string_with_options("a { b: c; }".to_string(), &continue_on_error()).unwrap()
+    );
+}
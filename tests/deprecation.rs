@@ -0,0 +1,117 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+use grass::Deprecation;
+
+fn fatal(deprecation: Deprecation) -> grass::Options {
+    grass::Options::default().fatal_deprecation(deprecation)
+}
+
+error!(
+    fatal_global_builtin,
+    "a { b: map-get((c: d), c); }",
+    fatal(Deprecation::GlobalBuiltin),
+    "Error: Global built-in functions are deprecated and will be removed in Dart Sass 3.0.0."
+);
+
+#[test]
+fn fatal_global_builtin_names_module_member() {
+    match grass::from_string_with_options(
+        "a { b: str-length(c); }".to_string(),
+        &fatal(Deprecation::GlobalBuiltin),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e.to_string().contains("\nUse string.length instead.\n")),
+    }
+}
+
+#[test]
+fn module_function_is_not_deprecated() {
+    assert_eq!(
+        "a {\n  b: d;\n}\n",
+        grass::from_string_with_options(
+            "@use \"sass:map\";\na { b: map.get((c: d), c); }".to_string(),
+            &fatal(Deprecation::GlobalBuiltin)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn css_function_is_not_deprecated() {
+    assert_eq!(
+        "a {\n  b: 2;\n  c: 1;\n}\n",
+        grass::from_string_with_options(
+            "a { b: round(1.5); c: min(1, 2); }".to_string(),
+            &fatal(Deprecation::GlobalBuiltin)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn silenced_global_builtin() {
+    assert_eq!(
+        "a {\n  b: d;\n}\n",
+        grass::from_string_with_options(
+            "a { b: map-get((c: d), c); }".to_string(),
+            &grass::Options::default().silence_deprecation(Deprecation::GlobalBuiltin)
+        )
+        .unwrap()
+    );
+}
+
+error!(
+    fatal_slash_div,
+    "a { b: (4px / 2); }",
+    fatal(Deprecation::SlashDiv),
+    "Error: Using / for division outside of calc() is deprecated."
+);
+
+error!(
+    fatal_import,
+    "@import \"foo\";",
+    fatal(Deprecation::Import),
+    "Error: Sass @import rules are deprecated and will be removed in Dart Sass 3.0.0."
+);
+
+#[test]
+fn css_import_is_not_deprecated() {
+    assert_eq!(
+        "@import \"foo.css\";\n",
+        grass::from_string_with_options(
+            "@import \"foo.css\";".to_string(),
+            &fatal(Deprecation::Import)
+        )
+        .unwrap()
+    );
+}
+
+error!(
+    fatal_elseif,
+    "@if false {} @elseif true {}",
+    fatal(Deprecation::Elseif),
+    "Error: @elseif is deprecated and will not be supported in future Sass versions."
+);
+
+#[test]
+fn fatal_message_names_deprecation() {
+    match grass::from_string_with_options(
+        "@if false {} @elseif true {}".to_string(),
+        &fatal(Deprecation::Elseif),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e.to_string().contains(
+            "This is only an error because you've set the elseif deprecation to be fatal."
+        )),
+    }
+}
+
+#[test]
+fn deprecation_from_id() {
+    for &deprecation in Deprecation::ALL {
+        assert_eq!(Some(deprecation), Deprecation::from_id(deprecation.id()));
+    }
+    assert_eq!(None, Deprecation::from_id("foo"));
+}
@@ -195,3 +195,81 @@ test!(
     }",
     "a {\n  color: foo;\n  color: bar;\n}\n"
 );
+test!(
+    memoized_function_sees_changed_global_variable,
+    "$base: 1;\n@function scale($n) {\n  @return $n * $base;\n}\na {\n  color: scale(2);\n}\n$base: 10;\nb {\n  color: scale(2);\n}\n",
+    "a {\n  color: 2;\n}\n\nb {\n  color: 20;\n}\n"
+);
+test!(
+    memoized_function_sees_redefined_function,
+    "@function inner() {\n  @return a;\n}\n@function outer() {\n  @return inner();\n}\na {\n  color: outer();\n}\n@function inner() {\n  @return b;\n}\nb {\n  color: outer();\n}\n",
+    "a {\n  color: a;\n}\n\nb {\n  color: b;\n}\n"
+);
+test!(
+    function_assigning_global_variable_is_not_memoized,
+    "$count: 0;\n@function count() {\n  $count: $count + 1 !global;\n  @return $count;\n}\na {\n  color: count();\n  color: count();\n  color: count();\n}\n",
+    "a {\n  color: 1;\n  color: 2;\n  color: 3;\n}\n"
+);
+test!(
+    function_calling_unique_id_is_not_memoized,
+    "@function id() {\n  @return unique-id();\n}\na {\n  color: id() == id();\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    function_using_parent_selector_is_not_memoized,
+    "@function parent() {\n  @return &;\n}\na {\n  color: parent();\n}\nb {\n  color: parent();\n}\n",
+    "a {\n  color: a;\n}\n\nb {\n  color: b;\n}\n"
+);
+test!(
+    memoized_function_distinguishes_equal_numbers_with_different_units,
+    "@function id($n) {\n  @return $n;\n}\na {\n  color: id(1in) id(96px);\n}\n",
+    "a {\n  color: 1in 96px;\n}\n"
+);
+test!(
+    memoized_function_distinguishes_quoted_and_unquoted_strings,
+    "@function id($s) {\n  @return $s;\n}\na {\n  color: id(a) id(\"a\");\n}\n",
+    "a {\n  color: a \"a\";\n}\n"
+);
+test!(
+    memoized_function_distinguishes_named_and_hex_colors,
+    "@function id($c) {\n  @return $c;\n}\na {\n  color: id(red) id(#ff0000);\n}\n",
+    "a {\n  color: red #ff0000;\n}\n"
+);
+error!(
+    style_rule_in_function,
+    "@function foo() {\n  a {\n    color: red;\n  }\n  @return 1;\n}\na {\n  color: foo();\n}\n",
+    "Error: Functions can only contain variable declarations and control directives."
+);
+error!(
+    unknown_named_argument,
+    "@function foo($a) {\n  @return $a;\n}\na {\n  color: foo(1, $b: 2);\n}\n",
+    "Error: No argument named $b."
+);
+error!(
+    misspelled_named_argument_to_builtin,
+    "a {\n  color: map-get($map: (c: d), $kye: c);\n}\n",
+    "Error: No argument named $kye. Did you mean $key?"
+);
+error!(
+    misspelled_optional_named_argument_to_builtin,
+    "a {\n  color: rgba(1, 2, 3, $alpah: 1);\n}\n",
+    "Error: No argument named $alpah. Did you mean $alpha?"
+);
+error!(
+    unknown_named_argument_to_builtin_with_optional_params,
+    "a {\n  color: rgb(1, 2, 3, $foo: 1);\n}\n", "Error: No argument named $foo."
+);
+error!(
+    misspelled_named_argument_to_builtin_through_call,
+    "a {\n  color: call(get-function(rgba), 1, 2, 3, $alpah: 1);\n}\n",
+    "Error: No argument named $alpah. Did you mean $alpha?"
+);
+test!(
+    named_arguments_to_builtin_through_call,
+    "a {\n  color: call(get-function(rgba), 1, 2, 3, $alpha: 0.5);\n}\n",
+    "a {\n  color: rgba(1, 2, 3, 0.5);\n}\n"
+);
+error!(
+    unknown_named_argument_to_builtin,
+    "a {\n  color: map-get($map: (c: d), $foo: c);\n}\n", "Error: Missing argument $key."
+);
@@ -121,3 +121,13 @@ test!(
     "a {\n  color: call(call(get-function(get-function), darken), red, 10%);\n}\n",
     "a {\n  color: #cc0000;\n}\n"
 );
+error!(
+    undefined_function_suggests_builtin,
+    "a {\n  color: get-function(lighen);\n}\n",
+    "Error: Function not found: lighen. Did you mean lighten?"
+);
+error!(
+    undefined_function_suggests_user_defined,
+    "@function my-function() {\n  @return 1;\n}\na {\n  color: get-function(my-fucntion);\n}\n",
+    "Error: Function not found: my-fucntion. Did you mean my-function?"
+);
@@ -0,0 +1,153 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(
+    inspect_mixin,
+    "@use \"sass:meta\";
+    @mixin foo {}
+    a {b: meta.inspect(meta.get-mixin(foo))}",
+    "a {\n  b: get-mixin(\"foo\");\n}\n"
+);
+test!(
+    type_of_mixin,
+    "@use \"sass:meta\";
+    @mixin foo {}
+    a {b: meta.type-of(meta.get-mixin(foo))}",
+    "a {\n  b: mixin;\n}\n"
+);
+test!(
+    same_mixin_equal,
+    "@use \"sass:meta\";
+    @mixin foo {}
+    a {b: meta.get-mixin(foo) == meta.get-mixin(\"foo\")}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    different_mixin_same_body_not_equal,
+    "@use \"sass:meta\";
+    @mixin foo {}
+    $first-reference: meta.get-mixin(foo);
+    @mixin foo {}
+    a {b: $first-reference == meta.get-mixin(foo)}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    accepts_content,
+    "@use \"sass:meta\";
+    @mixin with-content { @content; }
+    @mixin without-content {}
+    a {
+      b: meta.accepts-content(meta.get-mixin(with-content));
+      c: meta.accepts-content(meta.get-mixin(without-content));
+    }",
+    "a {\n  b: true;\n  c: false;\n}\n"
+);
+test!(
+    accepts_content_with_args,
+    "@use \"sass:meta\";
+    @mixin foo { @content(1); }
+    a {b: meta.accepts-content(meta.get-mixin(foo))}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    apply_no_args,
+    "@use \"sass:meta\";
+    @mixin foo { a { b: c; } }
+    @include meta.apply(meta.get-mixin(foo));",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    apply_positional_and_named_args,
+    "@use \"sass:meta\";
+    @mixin foo($a, $b: 2, $c: 3) { a { b: $a $b $c; } }
+    @include meta.apply(meta.get-mixin(foo), 1, $c: 4);",
+    "a {\n  b: 1 2 4;\n}\n"
+);
+test!(
+    apply_named_mixin_arg,
+    "@use \"sass:meta\";
+    @mixin foo($a) { a { b: $a; } }
+    @include meta.apply($mixin: meta.get-mixin(foo), $a: 1);",
+    "a {\n  b: 1;\n}\n"
+);
+test!(
+    apply_with_content_block,
+    "@use \"sass:meta\";
+    @mixin foo { a { @content; } }
+    @include meta.apply(meta.get-mixin(foo)) { b: c; }",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    apply_with_content_args,
+    "@use \"sass:meta\";
+    @mixin foo { @content(1); }
+    a { @include meta.apply(meta.get-mixin(foo)) using ($x) { b: $x; } }",
+    "a {\n  b: 1;\n}\n"
+);
+test!(
+    apply_mixin_from_map,
+    "@use \"sass:meta\";
+    @mixin primary { color: blue; }
+    $variants: (primary: meta.get-mixin(primary));
+    a { @include meta.apply(map-get($variants, primary)); }",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    apply_global_meta_module,
+    "@use \"sass:meta\" as *;
+    @mixin foo { a { b: c; } }
+    @include apply(get-mixin(foo));",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    user_defined_apply_shadows_global_meta_module,
+    "@use \"sass:meta\" as *;
+    @mixin apply($a) { a { b: $a; } }
+    @include apply(c);",
+    "a {\n  b: c;\n}\n"
+);
+error!(
+    get_mixin_not_found,
+    "@use \"sass:meta\";
+    a {b: meta.get-mixin(foo)}",
+    "Error: Mixin not found: foo"
+);
+error!(
+    get_mixin_name_not_string,
+    "@use \"sass:meta\";
+    a {b: meta.get-mixin(1)}",
+    "Error: $name: 1 is not a string."
+);
+test!(
+    get_mixin_not_global,
+    "@mixin foo {}
+    a {b: get-mixin(foo)}",
+    "a {\n  b: get-mixin(foo);\n}\n"
+);
+error!(
+    mixin_ref_not_valid_css,
+    "@use \"sass:meta\";
+    @mixin foo {}
+    a {b: meta.get-mixin(foo)}",
+    "Error: get-mixin(\"foo\") isn't a valid CSS value."
+);
+error!(
+    apply_not_mixin_reference,
+    "@use \"sass:meta\";
+    @include meta.apply(1);",
+    "Error: $mixin: 1 is not a mixin reference."
+);
+error!(
+    accepts_content_not_mixin_reference,
+    "@use \"sass:meta\";
+    a {b: meta.accepts-content(1)}",
+    "Error: $mixin: 1 is not a mixin reference."
+);
+error!(
+    undefined_module_mixin,
+    "@use \"sass:meta\";
+    @include meta.foo;",
+    "Error: Undefined mixin."
+);
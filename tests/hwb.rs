@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(
+    hwb_space_separated,
+    "a {\n  color: hwb(120deg 30% 40%);\n}\n",
+    "a {\n  color: #4d994d;\n}\n"
+);
+test!(
+    hwb_unitless_hue,
+    "a {\n  color: hwb(120 30% 40%);\n}\n",
+    "a {\n  color: #4d994d;\n}\n"
+);
+test!(
+    hwb_slash_alpha,
+    "a {\n  color: hwb(120deg 30% 40% / 0.5);\n}\n",
+    "a {\n  color: rgba(77, 153, 77, 0.5);\n}\n"
+);
+test!(
+    hwb_slash_alpha_percent,
+    "a {\n  color: hwb(120deg 30% 40% / 50%);\n}\n",
+    "a {\n  color: rgba(77, 153, 77, 0.5);\n}\n"
+);
+test!(
+    hwb_whiteness_and_blackness_sum_above_100,
+    "a {\n  color: hwb(0 60% 60%);\n}\n",
+    "a {\n  color: gray;\n}\n"
+);
+test!(
+    hwb_negative_hue,
+    "a {\n  color: hwb(-240 30% 40%);\n}\n",
+    "a {\n  color: #4d994d;\n}\n"
+);
+test!(
+    hwb_hue_turn,
+    "a {\n  color: hwb(0.5turn 0% 0%);\n}\n",
+    "a {\n  color: aqua;\n}\n"
+);
+test!(
+    hwb_hue_rad,
+    "a {\n  color: hwb(3.14159265359rad 0% 0%);\n}\n",
+    "a {\n  color: aqua;\n}\n"
+);
+test!(
+    hwb_hue_grad,
+    "@use \"sass:color\";\na {\n  color: color.hwb(200grad, 0%, 0%);\n}\n",
+    "a {\n  color: aqua;\n}\n"
+);
+test!(
+    hwb_special_fn_channel,
+    "a {\n  color: hwb(var(--hue) 30% 40%);\n}\n"
+);
+test!(
+    hwb_special_fn_alpha,
+    "a {\n  color: hwb(120deg 30% 40% / var(--alpha));\n}\n"
+);
+test!(
+    color_module_hwb_separate_args,
+    "@use \"sass:color\";\na {\n  color: color.hwb(210, 20%, 30%, 50%);\n}\n",
+    "a {\n  color: rgba(51, 115, 179, 0.5);\n}\n"
+);
+test!(
+    color_module_hwb_named_args,
+    "@use \"sass:color\";\na {\n  color: color.hwb($hue: 120, $whiteness: 30%, $blackness: 40%);\n}\n",
+    "a {\n  color: #4d994d;\n}\n"
+);
+test!(
+    whiteness,
+    "@use \"sass:color\";\na {\n  color: color.whiteness(#ccc);\n}\n",
+    "a {\n  color: 80%;\n}\n"
+);
+test!(
+    blackness,
+    "@use \"sass:color\";\na {\n  color: color.blackness(#4d994d);\n}\n",
+    "a {\n  color: 40%;\n}\n"
+);
+test!(
+    whiteness_roundtrip,
+    "@use \"sass:color\";\na {\n  color: color.whiteness(hwb(0 20% 30%));\n}\n",
+    "a {\n  color: 20%;\n}\n"
+);
+test!(
+    change_color_whiteness,
+    "a {\n  color: change-color(#4d994d, $whiteness: 0%);\n}\n",
+    "a {\n  color: #009900;\n}\n"
+);
+test!(
+    change_color_hue_and_blackness,
+    "a {\n  color: change-color(red, $hue: 120, $blackness: 50%);\n}\n",
+    "a {\n  color: green;\n}\n"
+);
+test!(
+    adjust_color_blackness,
+    "a {\n  color: adjust-color(#4d994d, $blackness: 20%);\n}\n",
+    "a {\n  color: #4d664d;\n}\n"
+);
+test!(
+    scale_color_whiteness,
+    "a {\n  color: scale-color(#4d994d, $whiteness: 50%);\n}\n",
+    "a {\n  color: #9e9e9e;\n}\n"
+);
+error!(
+    hwb_no_args,
+    "a {\n  color: hwb();\n}\n", "Error: Missing argument $channels."
+);
+error!(
+    hwb_comma_separated_channels,
+    "a {\n  color: hwb((1, 2%, 3%));\n}\n", "Error: $channels must be a space-separated list."
+);
+error!(
+    hwb_too_many_channels,
+    "a {\n  color: hwb(1 2% 3% 4%);\n}\n", "Error: Only 3 elements allowed, but 4 were passed."
+);
+error!(
+    hwb_missing_blackness,
+    "a {\n  color: hwb(1 2%);\n}\n", "Error: Missing element $blackness."
+);
+error!(
+    hwb_whiteness_without_percent,
+    "a {\n  color: hwb(1 2 3%);\n}\n", "Error: $whiteness: Expected 2 to have unit \"%\"."
+);
+error!(
+    hwb_blackness_out_of_bounds,
+    "a {\n  color: hwb(1 2% 101%);\n}\n",
+    "Error: $blackness: Expected 101% to be within 0% and 100%."
+);
+error!(
+    change_color_rgb_and_hwb,
+    "a {\n  color: change-color(red, $red: 1, $whiteness: 1%);\n}\n",
+    "Error: RGB parameters may not be passed along with HWB parameters."
+);
+error!(
+    scale_color_hsl_and_hwb,
+    "a {\n  color: scale-color(red, $lightness: 1%, $whiteness: 1%);\n}\n",
+    "Error: HSL parameters may not be passed along with HWB parameters."
+);
+error!(
+    change_color_whiteness_without_percent,
+    "a {\n  color: change-color(red, $whiteness: 1);\n}\n",
+    "Error: $whiteness: Expected 1 to have unit \"%\"."
+);
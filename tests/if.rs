@@ -62,6 +62,16 @@ test!(
     "a {\n  @if false {\n    color: red;\n} @else if true {\n    color: blue;\n} @else {\n    color: green;\n}\n}\n",
     "a {\n  color: blue;\n}\n"
 );
+test!(
+    if_false_elseif_true_else,
+    "a {\n  @if false {\n    color: red;\n} @elseif true {\n    color: blue;\n} @else {\n    color: green;\n}\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    if_true_elseif_true,
+    "a {\n  @if true {\n    color: red;\n} @elseif true {\n    color: blue;\n}\n}\n",
+    "a {\n  color: red;\n}\n"
+);
 test!(
     if_inner_style_missing_semicolon,
     "a {\n  @if true {\n    color: red\n  }\n}\n",
@@ -180,3 +190,8 @@ error!(
     invalid_toplevel_selector,
     "@if true { & { } }", "Error: Top-level selectors may not contain the parent selector \"&\"."
 );
+error!(
+    if_unknown_named_argument_suggests_parameter,
+    "a {\n  color: if(true, 1, $if-fasle: 2);\n}\n",
+    "Error: No argument named $if-fasle. Did you mean $if-false?"
+);
@@ -176,5 +176,124 @@ test!(
     "@import url(2..);\n"
 );
 
+#[test]
+fn import_self() {
+    let input = "@import \"import_self\";";
+    tempfile!("import_self.scss", "@import \"import_self\";");
+    match grass::from_string(input.to_string()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded.\n  import_self.scss 1:9  imports import_self.scss",
+            e.to_string().lines().take(2).collect::<Vec<&str>>().join("\n")
+        ),
+    }
+}
+
+#[test]
+fn import_cycle() {
+    let input = "@import \"import_cycle_a\";";
+    tempfile!("import_cycle_a.scss", "@import \"import_cycle_b\";");
+    tempfile!(
+        "import_cycle_b.scss",
+        "a {\n  color: red;\n}\n@import \"import_cycle_a\";"
+    );
+    match grass::from_string(input.to_string()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded.\n  import_cycle_a.scss 1:9  imports import_cycle_b.scss\n  import_cycle_b.scss 4:9  imports import_cycle_a.scss",
+            e.to_string().lines().take(3).collect::<Vec<&str>>().join("\n")
+        ),
+    }
+}
+
+#[test]
+fn import_same_file_twice() {
+    let input = "@import \"import_same_file_twice\";\n@import \"import_same_file_twice\";";
+    tempfile!("import_same_file_twice.scss", "a {\n  color: red;\n}\n");
+    assert_eq!(
+        "a {\n  color: red;\n}\n\na {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string()).expect(input)
+    );
+}
+
+#[test]
+fn error_in_import_has_trace() {
+    let input = "a {\n  color: red;\n}\n@import \"error_in_import_has_trace\";";
+    tempfile!("error_in_import_has_trace.scss", "@error \"foo\";");
+    match grass::from_string(input.to_string()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            vec![
+                ("error_in_import_has_trace.scss".to_owned(), 1, 1, "@import"),
+                ("stdin".to_owned(), 4, 9, "root stylesheet"),
+            ],
+            e.trace()
+                .iter()
+                .map(|f| (f.file().to_owned(), f.line(), f.column(), f.member()))
+                .collect::<Vec<_>>()
+        ),
+    }
+}
+
+#[test]
+fn import_from_load_path() {
+    let input = "@import \"import_from_load_path\";\na {\n color: $a;\n}";
+    tempfile!(
+        "import_from_load_path.scss",
+        "$a: red;",
+        dir = "import_from_load_path_dir"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string_with_options(
+            input.to_string(),
+            &grass::Options::default().load_path("import_from_load_path_dir")
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn relative_import_takes_precedence_over_load_path() {
+    let input = "@import \"relative_import_precedence\";\na {\n color: $a;\n}";
+    tempfile!("relative_import_precedence.scss", "$a: red;");
+    tempfile!(
+        "relative_import_precedence.scss",
+        "$a: blue;",
+        dir = "relative_import_precedence_dir"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string_with_options(
+            input.to_string(),
+            &grass::Options::default().load_path("relative_import_precedence_dir")
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn quiet_deps_still_errors_on_fatal_deprecation() {
+    let input = "@import \"quiet_deps_fatal\";";
+    tempfile!(
+        "quiet_deps_fatal.scss",
+        "a { b: (4px / 2); }",
+        dir = "quiet_deps_fatal_dir"
+    );
+    match grass::from_string_with_options(
+        input.to_string(),
+        &grass::Options::default()
+            .load_path("quiet_deps_fatal_dir")
+            .quiet_deps(true)
+            .fatal_deprecation(grass::Deprecation::SlashDiv),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Using / for division outside of calc() is deprecated.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
 // todo: test for calling paths, e.g. `grass b\index.scss`
 // todo: test for absolute paths (how?)
@@ -121,3 +121,18 @@ test!(
     }",
     "@keyframes {\n  to {\n    color: red;\n  }\n  from {\n    color: green;\n  }\n}\n"
 );
+test!(
+    keyframes_escaped_to_and_from,
+    "@keyframes {\n  \\74 o {\n    color: red;\n  }\n  \\66 rom {\n    color: green;\n  }\n}\n",
+    "@keyframes {\n  to {\n    color: red;\n  }\n  from {\n    color: green;\n  }\n}\n"
+);
+test!(
+    keyframes_ruleset_contains_multiline_comment,
+    "@keyframes {\n  to {\n    /* foo */\n    color: red;\n  }\n}\n",
+    "@keyframes {\n  to {\n    /* foo */\n    color: red;\n  }\n}\n"
+);
+error!(
+    keyframes_ruleset_contains_media,
+    "@keyframes {\n  to {\n    @media foo {\n      color: red;\n    }\n  }\n}\n",
+    "Error: Only declarations are allowed in @keyframes blocks."
+);
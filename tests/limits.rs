@@ -0,0 +1,220 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+use std::{io::Write, time::Duration};
+
+use tempfile::Builder;
+
+error!(
+    infinite_while_loop,
+    "@while true {}",
+    grass::Options::default().max_steps(1000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+error!(
+    infinite_while_loop_in_function,
+    "@function foo() { @while true { $a: 1; } @return 1; }\na { b: foo(); }",
+    grass::Options::default().max_steps(1000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+error!(
+    long_for_loop,
+    "@for $i from 1 through 1000000 { a { b: $i; } }",
+    grass::Options::default().max_steps(1000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+error!(
+    exponential_mixin_calls,
+    "@mixin foo($n) { @if $n > 0 { @include foo($n - 1); @include foo($n - 1); } }\na { @include foo(30); }",
+    grass::Options::default().max_steps(1000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+#[test]
+fn within_max_steps() {
+    assert_eq!(
+        "a {\n  b: 3;\n}\n",
+        grass::from_string_with_options(
+            "$i: 0; @while $i < 3 { $i: $i + 1; } a { b: $i; }".to_string(),
+            &grass::Options::default().max_steps(1000)
+        )
+        .unwrap()
+    );
+}
+
+error!(
+    exponential_string_growth,
+    "$s: a; @for $i from 1 through 40 { $s: $s + $s; }",
+    grass::Options::default().max_steps(1000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+error!(
+    exponential_list_growth,
+    "$l: a; @for $i from 1 through 40 { $l: join($l, $l); }",
+    grass::Options::default().max_steps(1000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+error!(
+    exponential_string_growth_with_timeout,
+    "$s: a; @for $i from 1 through 40 { $s: \"#{$s}#{$s}\"; }",
+    grass::Options::default().timeout(Duration::from_millis(100)),
+    "Error: Maximum compilation time exceeded."
+);
+
+error!(
+    timeout,
+    "@while true {}",
+    grass::Options::default().timeout(Duration::from_millis(10)),
+    "Error: Maximum compilation time exceeded."
+);
+
+error!(
+    max_output_size,
+    "@for $i from 1 through 100 { a { b: $i; } }",
+    grass::Options::default().max_output_size(100),
+    "Error: Output exceeds the maximum size of 100 bytes."
+);
+
+error!(
+    value_larger_than_max_output_size,
+    "$s: a; @for $i from 1 through 40 { $s: $s + $s; }",
+    grass::Options::default().max_output_size(1000),
+    "Error: Maximum value size exceeded."
+);
+
+error!(
+    nested_space_list_growth,
+    "$l: a; @for $i from 1 through 40 { $l: $l $l; } a { b: $l; }",
+    grass::Options::default()
+        .max_steps(10_000)
+        .timeout(Duration::from_secs(2))
+        .max_output_size(1_000_000),
+    "Error: Maximum value size exceeded."
+);
+
+error!(
+    nested_comma_list_growth,
+    "$l: a; @for $i from 1 through 40 { $l: ($l, $l); } a { b: $l; }",
+    grass::Options::default().max_output_size(1_000_000),
+    "Error: Maximum value size exceeded."
+);
+
+error!(
+    nested_list_growth_with_max_steps,
+    "$l: a; @for $i from 1 through 40 { $l: $l $l; } a { b: $l; }",
+    grass::Options::default().max_steps(10_000),
+    "Error: Maximum number of evaluation steps exceeded."
+);
+
+error!(
+    nested_list_growth_with_timeout,
+    "$l: a; @for $i from 1 through 40 { $l: $l $l; } a { b: $l; }",
+    grass::Options::default().timeout(Duration::from_millis(100)),
+    "Error: Maximum compilation time exceeded."
+);
+
+error!(
+    nested_map_growth_in_inspect,
+    "$m: (a: b); @for $i from 1 through 40 { $m: (a: $m, b: $m); } a { b: inspect($m); }",
+    grass::Options::default().max_output_size(1_000_000),
+    "Error: Maximum value size exceeded."
+);
+
+error!(
+    nested_growth_in_function_call,
+    "$l: a; @for $i from 1 through 19 { $l: $l $l; } a { b: inspect(join(join($l, $l), join($l, $l))); }",
+    grass::Options::default().max_output_size(1_000_000),
+    "Error: Maximum value size exceeded."
+);
+
+error!(
+    output_larger_than_max_output_size,
+    "$l: a; @for $i from 1 through 9 { $l: $l $l; } @for $i from 1 through 1000000 { a { b: $l; } }",
+    grass::Options::default().max_output_size(10_000),
+    "Error: Output exceeds the maximum size of 10000 bytes."
+);
+
+#[test]
+fn within_max_output_size() {
+    assert_eq!(
+        "a {\n  b: c;\n}\n",
+        grass::from_string_with_options(
+            "a { b: c; }".to_string(),
+            &grass::Options::default().max_output_size(14)
+        )
+        .unwrap()
+    );
+}
+
+error!(
+    deterministic_random,
+    "a { b: random(); }",
+    grass::Options::default().deterministic(true),
+    "Error: random() is not available in deterministic mode."
+);
+
+error!(
+    deterministic_unique_id,
+    "a { b: unique-id(); }",
+    grass::Options::default().deterministic(true),
+    "Error: unique-id() is not available in deterministic mode."
+);
+
+#[test]
+fn import_within_allowed_root() {
+    let dir = Builder::new()
+        .rand_bytes(0)
+        .prefix("")
+        .suffix("limits_allowed_root")
+        .tempdir_in("")
+        .unwrap();
+    let mut f = Builder::new()
+        .rand_bytes(0)
+        .prefix("")
+        .suffix("allowed.scss")
+        .tempfile_in("limits_allowed_root")
+        .unwrap();
+    write!(f, "a {{ color: red; }}").unwrap();
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string_with_options(
+            "@import \"limits_allowed_root/allowed\";".to_string(),
+            &grass::Options::default().allow_imports_from(dir.path())
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn import_outside_allowed_root() {
+    let _dir = Builder::new()
+        .rand_bytes(0)
+        .prefix("")
+        .suffix("limits_empty_root")
+        .tempdir_in("")
+        .unwrap();
+    let mut f = Builder::new()
+        .rand_bytes(0)
+        .prefix("")
+        .suffix("limits_outside_root.scss")
+        .tempfile_in("")
+        .unwrap();
+    write!(f, "a {{ color: red; }}").unwrap();
+
+    match grass::from_string_with_options(
+        "@import \"limits_outside_root\";".to_string(),
+        &grass::Options::default().allow_imports_from("limits_empty_root"),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e
+            .to_string()
+            .starts_with("Error: Can't find stylesheet to import.\n")),
+    }
+}
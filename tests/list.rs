@@ -340,3 +340,103 @@ error!(
     invalid_item_in_comma_separated_list_inside_interpolation,
     "a {\n  color: #{red, color * #abc};\n}\n", "Error: Undefined operation \"color * #abc\"."
 );
+test!(
+    list_slash_two_elements,
+    "@use \"sass:list\";\na {\n  color: list.slash(a, b);\n}\n",
+    "a {\n  color: a / b;\n}\n"
+);
+test!(
+    list_slash_numbers,
+    "@use \"sass:list\";\na {\n  color: list.slash(1px, 2px, 3px);\n}\n",
+    "a {\n  color: 1px / 2px / 3px;\n}\n"
+);
+test!(
+    slash_separated_numbers_in_function_args,
+    "a {\n  color: foo(1px/2px 3px);\n}\n",
+    "a {\n  color: foo(1px/2px 3px);\n}\n"
+);
+test!(
+    slash_separated_numbers_in_parens,
+    "a {\n  color: (1px/2px 3px);\n}\n",
+    "a {\n  color: 1px/2px 3px;\n}\n"
+);
+test!(
+    slash_separated_numbers_are_one_element,
+    "a {\n  color: length((1px/2px 3px));\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    slash_separated_number_operator_uses_quotient,
+    "a {\n  color: nth((1px/2px 3px), 1) + 1;\n}\n",
+    "a {\n  color: 1.5;\n}\n"
+);
+test!(
+    list_slash_nested_space_list,
+    "@use \"sass:list\";\na {\n  color: list.slash(1px 2px, 3px);\n}\n",
+    "a {\n  color: 1px 2px / 3px;\n}\n"
+);
+test!(
+    list_separator_slash,
+    "@use \"sass:list\";\na {\n  color: list-separator(list.slash(a, b));\n}\n",
+    "a {\n  color: slash;\n}\n"
+);
+test!(
+    length_of_slash_list,
+    "@use \"sass:list\";\na {\n  color: length(list.slash(a, b, c));\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    append_preserves_slash_separator,
+    "@use \"sass:list\";\na {\n  color: inspect(append(list.slash(a, b), c));\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+test!(
+    append_separator_slash,
+    "a {\n  color: append(a b, c, $separator: slash);\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+test!(
+    join_separator_slash,
+    "a {\n  color: join(a, b, $separator: slash);\n}\n",
+    "a {\n  color: a / b;\n}\n"
+);
+test!(
+    inspect_single_element_slash_list,
+    "a {\n  color: inspect(join((), a, $separator: slash));\n}\n",
+    "a {\n  color: (a/);\n}\n"
+);
+test!(
+    inspect_bracketed_single_element_slash_list,
+    "a {\n  color: inspect(join([], a, $separator: slash));\n}\n",
+    "a {\n  color: [a/];\n}\n"
+);
+error!(
+    list_slash_one_element,
+    "@use \"sass:list\";\na {\n  color: list.slash(a);\n}\n",
+    "Error: At least two elements are required."
+);
+test!(
+    slash_is_not_global,
+    "a {\n  color: slash(a, b);\n}\n",
+    "a {\n  color: slash(a, b);\n}\n"
+);
+error!(
+    append_invalid_separator,
+    "a {\n  color: append(a, b, $separator: foo);\n}\n",
+    "Error: $separator: Must be \"space\", \"comma\", \"slash\", or \"auto\"."
+);
+test!(
+    set_nth_does_not_modify_original_list,
+    "$a: 1 2 3;\n$b: set-nth($a, 1, 4);\na {\n  color: $a;\n  color: $b;\n}\n",
+    "a {\n  color: 1 2 3;\n  color: 4 2 3;\n}\n"
+);
+test!(
+    append_does_not_modify_original_list,
+    "$a: 1, 2;\n$b: append($a, 3);\na {\n  color: $a;\n  color: $b;\n}\n",
+    "a {\n  color: 1, 2;\n  color: 1, 2, 3;\n}\n"
+);
+test!(
+    join_does_not_modify_original_list,
+    "$a: 1 2;\n$b: join($a, 3 4);\na {\n  color: $a;\n  color: $b;\n}\n",
+    "a {\n  color: 1 2;\n  color: 1 2 3 4;\n}\n"
+);
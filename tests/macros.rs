@@ -30,7 +30,7 @@ macro_rules! test {
     };
 }
 
-/// Verify the error *message*
+/// Verify the error *message*, optionally compiling with the given `grass::Options`
 /// Span and scope information are not yet tested
 #[macro_export]
 macro_rules! error {
@@ -50,4 +50,20 @@ macro_rules! error {
             }
         }
     };
+    ($( #[$attr:meta] ),*$func:ident, $input:expr, $options:expr, $err:expr) => {
+        $(#[$attr])*
+        #[test]
+        #[allow(non_snake_case)]
+        fn $func() {
+            match grass::from_string_with_options($input.to_string(), &$options) {
+                Ok(..) => panic!("did not fail"),
+                Err(e) => assert_eq!($err, e.to_string()
+                                                .chars()
+                                                .take_while(|c| *c != '\n')
+                                                .collect::<String>()
+                                                .as_str()
+                ),
+            }
+        }
+    };
 }
@@ -197,3 +197,72 @@ test!(
     "a {\n  color: (a: b)==(a: c);\n}\n",
     "a {\n  color: false;\n}\n"
 );
+test!(
+    map_get_converts_units_of_key,
+    "a {\n  color: map-get((1in: a), 96px);\n}\n",
+    "a {\n  color: a;\n}\n"
+);
+test!(
+    map_get_fuzzy_key,
+    "a {\n  color: map-get((1: a), 1.000000000001);\n}\n",
+    "a {\n  color: a;\n}\n"
+);
+test!(
+    map_get_unitless_key_does_not_match_unit,
+    "a {\n  color: inspect(map-get((1: a), 1px));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    map_get_list_key,
+    "a {\n  color: map-get(((a b): c), (a b));\n}\n",
+    "a {\n  color: c;\n}\n"
+);
+test!(
+    map_get_color_key,
+    "a {\n  color: map-get((red: a), #f00);\n}\n",
+    "a {\n  color: a;\n}\n"
+);
+test!(
+    map_has_key_converts_units_of_key,
+    "a {\n  color: map-has-key((1turn: a), 360deg);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    map_merge_keeps_original_key_and_position,
+    "a {\n  color: inspect(map-merge((1in: a, b: c), (96px: d)));\n}\n",
+    "a {\n  color: (1in: d, b: c);\n}\n"
+);
+test!(
+    map_remove_preserves_order,
+    "a {\n  color: inspect(map-remove((a: 1, b: 2, c: 3, d: 4), b));\n}\n",
+    "a {\n  color: (a: 1, c: 3, d: 4);\n}\n"
+);
+test!(
+    map_keys_preserve_insertion_order,
+    "a {\n  color: map-keys(map-merge((c: 1, a: 2), (b: 3, c: 4)));\n}\n",
+    "a {\n  color: c, a, b;\n}\n"
+);
+test!(
+    map_equality_converts_units_of_keys,
+    "a {\n  color: (1in: a, b: c)==(b: c, 96px: a);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+error!(
+    duplicate_key_with_different_units_in_declaration,
+    "a {\n  color: (1in: a, 96px: b);\n}\n", "Error: Duplicate key."
+);
+test!(
+    map_merge_does_not_modify_original_map,
+    "$a: (a: 1);\n$b: map-merge($a, (b: 2));\na {\n  color: inspect($a);\n  color: inspect($b);\n}\n",
+    "a {\n  color: (a: 1);\n  color: (a: 1, b: 2);\n}\n"
+);
+test!(
+    map_remove_does_not_modify_original_map,
+    "$a: (a: 1, b: 2);\n$b: map-remove($a, a);\na {\n  color: inspect($a);\n  color: inspect($b);\n}\n",
+    "a {\n  color: (a: 1, b: 2);\n  color: (b: 2);\n}\n"
+);
+test!(
+    map_merge_into_empty_map,
+    "$a: ();\n$b: map-merge($a, (b: 2));\na {\n  color: inspect($a);\n  color: inspect($b);\n}\n",
+    "a {\n  color: ();\n  color: (b: 2);\n}\n"
+);
@@ -0,0 +1,307 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(
+    sqrt_integer,
+    "@use \"sass:math\";\na {\n  color: math.sqrt(16);\n}\n",
+    "a {\n  color: 4;\n}\n"
+);
+test!(
+    sqrt_decimal,
+    "@use \"sass:math\";\na {\n  color: math.sqrt(2);\n}\n",
+    "a {\n  color: 1.4142135624;\n}\n"
+);
+test!(
+    sqrt_negative,
+    "@use \"sass:math\";\na {\n  color: math.sqrt(-1);\n}\n",
+    "a {\n  color: NaN;\n}\n"
+);
+test!(
+    pow_integer,
+    "@use \"sass:math\";\na {\n  color: math.pow(2, 10);\n}\n",
+    "a {\n  color: 1024;\n}\n"
+);
+test!(
+    pow_negative_exponent,
+    "@use \"sass:math\";\na {\n  color: math.pow(2, -1);\n}\n",
+    "a {\n  color: 0.5;\n}\n"
+);
+test!(
+    pow_decimal_exponent,
+    "@use \"sass:math\";\na {\n  color: math.pow(4, 0.5);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    log_natural,
+    "@use \"sass:math\";\na {\n  color: math.log(math.$e);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    log_with_base,
+    "@use \"sass:math\";\na {\n  color: math.log(100, 10);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    log_zero,
+    "@use \"sass:math\";\na {\n  color: math.log(0);\n}\n",
+    "a {\n  color: -Infinity;\n}\n"
+);
+test!(
+    sin_unitless_is_radians,
+    "@use \"sass:math\";\na {\n  color: math.sin(1);\n}\n",
+    "a {\n  color: 0.8414709848;\n}\n"
+);
+test!(
+    sin_deg,
+    "@use \"sass:math\";\na {\n  color: math.sin(90deg);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    sin_turn,
+    "@use \"sass:math\";\na {\n  color: math.sin(0.25turn);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    cos_zero,
+    "@use \"sass:math\";\na {\n  color: math.cos(0);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    cos_grad,
+    "@use \"sass:math\";\na {\n  color: math.cos(200grad);\n}\n",
+    "a {\n  color: -1;\n}\n"
+);
+test!(
+    tan_deg,
+    "@use \"sass:math\";\na {\n  color: math.tan(45deg);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    asin_one,
+    "@use \"sass:math\";\na {\n  color: math.asin(1);\n}\n",
+    "a {\n  color: 90deg;\n}\n"
+);
+test!(
+    acos_zero,
+    "@use \"sass:math\";\na {\n  color: math.acos(0);\n}\n",
+    "a {\n  color: 90deg;\n}\n"
+);
+test!(
+    atan_one,
+    "@use \"sass:math\";\na {\n  color: math.atan(1);\n}\n",
+    "a {\n  color: 45deg;\n}\n"
+);
+test!(
+    atan2_unitless,
+    "@use \"sass:math\";\na {\n  color: math.atan2(1, -1);\n}\n",
+    "a {\n  color: 135deg;\n}\n"
+);
+test!(
+    atan2_compatible_units,
+    "@use \"sass:math\";\na {\n  color: math.atan2(1in, 96px);\n}\n",
+    "a {\n  color: 45deg;\n}\n"
+);
+test!(
+    hypot_unitless,
+    "@use \"sass:math\";\na {\n  color: math.hypot(3, 4);\n}\n",
+    "a {\n  color: 5;\n}\n"
+);
+test!(
+    hypot_units,
+    "@use \"sass:math\";\na {\n  color: math.hypot(3px, 4px);\n}\n",
+    "a {\n  color: 5px;\n}\n"
+);
+test!(
+    hypot_converts_to_first_unit,
+    "@use \"sass:math\";\na {\n  color: math.hypot(1in, 72pt);\n}\n",
+    "a {\n  color: 1.4142135624in;\n}\n"
+);
+test!(
+    clamp_below_min,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1px, 0px, 3px);\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    clamp_above_max,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1px, 5px, 3px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    clamp_between,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1px, 2px, 3px);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    clamp_keeps_units_of_result,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1in, 2px, 3in);\n}\n",
+    "a {\n  color: 1in;\n}\n"
+);
+test!(
+    div_numbers,
+    "@use \"sass:math\";\na {\n  color: math.div(10px, 4);\n}\n",
+    "a {\n  color: 2.5px;\n}\n"
+);
+test!(
+    div_cancels_units,
+    "@use \"sass:math\";\na {\n  color: math.div(10px, 5px);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    div_strings,
+    "@use \"sass:math\";\na {\n  color: math.div(a, b);\n}\n",
+    "a {\n  color: a/b;\n}\n"
+);
+test!(
+    compatible_true,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1px, 1in);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    compatible_false,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1px, 1s);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    is_unitless,
+    "@use \"sass:math\";\na {\n  color: math.is-unitless(1);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    unit_of_number,
+    "@use \"sass:math\";\na {\n  color: math.unit(1px);\n}\n",
+    "a {\n  color: \"px\";\n}\n"
+);
+test!(
+    global_function_through_module,
+    "@use \"sass:math\";\na {\n  color: math.round(1.5px);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    constant_pi,
+    "@use \"sass:math\";\na {\n  color: math.$pi;\n}\n",
+    "a {\n  color: 3.1415926536;\n}\n"
+);
+test!(
+    constant_e,
+    "@use \"sass:math\";\na {\n  color: math.$e;\n}\n",
+    "a {\n  color: 2.7182818285;\n}\n"
+);
+test!(
+    constant_epsilon_rounds_to_zero,
+    "@use \"sass:math\";\na {\n  color: math.$epsilon;\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    constant_max_safe_integer,
+    "@use \"sass:math\";\na {\n  color: math.$max-safe-integer;\n}\n",
+    "a {\n  color: 9007199254740991;\n}\n"
+);
+test!(
+    constant_min_safe_integer,
+    "@use \"sass:math\";\na {\n  color: math.$min-safe-integer;\n}\n",
+    "a {\n  color: -9007199254740991;\n}\n"
+);
+test!(
+    constant_min_number_rounds_to_zero,
+    "@use \"sass:math\";\na {\n  color: math.$min-number;\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    constant_underscore_is_hyphen,
+    "@use \"sass:math\";\na {\n  color: math.$max_safe_integer;\n}\n",
+    "a {\n  color: 9007199254740991;\n}\n"
+);
+error!(
+    sqrt_with_units,
+    "@use \"sass:math\";\na {\n  color: math.sqrt(4px);\n}\n",
+    "Error: $number: Expected 4px to have no units."
+);
+error!(
+    sqrt_not_number,
+    "@use \"sass:math\";\na {\n  color: math.sqrt(a);\n}\n", "Error: $number: a is not a number."
+);
+error!(
+    pow_base_with_units,
+    "@use \"sass:math\";\na {\n  color: math.pow(2px, 2);\n}\n",
+    "Error: $base: Expected 2px to have no units."
+);
+error!(
+    pow_exponent_with_units,
+    "@use \"sass:math\";\na {\n  color: math.pow(2, 2px);\n}\n",
+    "Error: $exponent: Expected 2px to have no units."
+);
+error!(
+    log_base_with_units,
+    "@use \"sass:math\";\na {\n  color: math.log(8, 2px);\n}\n",
+    "Error: $base: Expected 2px to have no units."
+);
+error!(
+    sin_non_angle_unit,
+    "@use \"sass:math\";\na {\n  color: math.sin(1px);\n}\n",
+    "Error: $number: Expected 1px to have an angle unit (deg, grad, rad, turn)."
+);
+error!(
+    asin_with_units,
+    "@use \"sass:math\";\na {\n  color: math.asin(1deg);\n}\n",
+    "Error: $number: Expected 1deg to have no units."
+);
+error!(
+    atan2_unitless_and_units,
+    "@use \"sass:math\";\na {\n  color: math.atan2(1px, 1);\n}\n",
+    "Error: $y has unit px but $x is unitless. Arguments must all have units or all be unitless."
+);
+error!(
+    atan2_incompatible_units,
+    "@use \"sass:math\";\na {\n  color: math.atan2(1px, 1s);\n}\n",
+    "Error: $x: Incompatible units s and px."
+);
+error!(
+    hypot_no_args,
+    "@use \"sass:math\";\na {\n  color: math.hypot();\n}\n",
+    "Error: At least one argument must be passed."
+);
+error!(
+    hypot_unitless_and_units,
+    "@use \"sass:math\";\na {\n  color: math.hypot(1px, 2);\n}\n",
+    "Error: Argument 1 has unit px but argument 2 is unitless. Arguments must all have units or all be unitless."
+);
+error!(
+    hypot_incompatible_units,
+    "@use \"sass:math\";\na {\n  color: math.hypot(1px, 2s);\n}\n",
+    "Error: Incompatible units s and px."
+);
+error!(
+    clamp_min_and_number_units,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1px, 2, 3px);\n}\n",
+    "Error: $min has unit px but $number is unitless. Arguments must all have units or all be unitless."
+);
+error!(
+    clamp_min_and_max_units,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1, 2, 3px);\n}\n",
+    "Error: $min is unitless but $max has unit px. Arguments must all have units or all be unitless."
+);
+error!(
+    clamp_incompatible_units,
+    "@use \"sass:math\";\na {\n  color: math.clamp(1px, 2s, 3px);\n}\n",
+    "Error: Incompatible units s and px."
+);
+error!(
+    undefined_module_function,
+    "@use \"sass:math\";\na {\n  color: math.foo(1);\n}\n", "Error: Undefined function."
+);
+error!(
+    undefined_module_variable,
+    "@use \"sass:math\";\na {\n  color: math.$foo;\n}\n", "Error: Undefined variable."
+);
+error!(
+    undefined_module_function_suggests_closest,
+    "@use \"sass:math\";\na {\n  color: math.sqr(4);\n}\n",
+    "Error: Undefined function. Did you mean math.sqrt?"
+);
+error!(
+    undefined_module_variable_suggests_closest,
+    "@use \"sass:math\";\na {\n  color: math.$pii;\n}\n",
+    "Error: Undefined variable. Did you mean math.$pi?"
+);
@@ -0,0 +1,106 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+error!(
+    nested_style_rules,
+    "a { b { c { d { e { f: g; } } } } }",
+    grass::Options::default().max_depth(3),
+    "Error: Maximum nesting depth exceeded."
+);
+
+#[test]
+fn nested_style_rules_within_limit() {
+    let options = grass::Options::default().max_depth(10);
+    assert_eq!(
+        "a b c d e {\n  f: g;\n}\n",
+        grass::from_string_with_options(
+            "a { b { c { d { e { f: g; } } } } }".to_string(),
+            &options
+        )
+        .unwrap()
+    );
+}
+
+error!(
+    nested_parens,
+    "a { b: ((((((((((1)))))))))); }",
+    grass::Options::default().max_depth(5),
+    "Error: Maximum nesting depth exceeded."
+);
+
+error!(
+    nested_plain_css_functions,
+    "a { b: foo(foo(foo(foo(foo(foo(foo(foo(1)))))))); }",
+    grass::Options::default().max_depth(5),
+    "Error: Maximum nesting depth exceeded."
+);
+
+error!(
+    nested_pseudo_selectors,
+    ":not(:not(:not(:not(:not(:not(a)))))) { b: c; }",
+    grass::Options::default().max_depth(3),
+    "Error: Maximum nesting depth exceeded."
+);
+
+error!(
+    infinitely_recursive_function,
+    "@function foo($a) { @return foo($a); }\na { b: foo(1); }",
+    grass::Options::default().max_call_depth(20),
+    "Error: Stack overflow."
+);
+
+error!(
+    infinitely_recursive_mixin,
+    "@mixin foo { @include foo; }\na { @include foo; }",
+    grass::Options::default().max_call_depth(20),
+    "Error: Stack overflow."
+);
+
+error!(
+    default_limit_recursive_function,
+    "@function foo($a) { @return foo($a); }\na { b: foo(1); }",
+    grass::Options::default(),
+    "Error: Stack overflow."
+);
+
+#[test]
+fn long_operator_chain_is_not_nesting() {
+    let input = format!("a {{ b: {}; }}", vec!["1"; 1000].join(" + "));
+    assert_eq!(
+        "a {\n  b: 1000;\n}\n",
+        grass::from_string_with_options(input, &grass::Options::default().max_depth(5)).unwrap()
+    );
+}
+
+error!(
+    long_operator_chain_with_error,
+    format!("a {{ b: {} +; }}", vec!["1"; 100_000].join(" + ")),
+    grass::Options::default(),
+    "Error: Expected expression."
+);
+
+#[test]
+fn function_body_is_not_nested_in_caller() {
+    let options = grass::Options::default().max_depth(10);
+    assert_eq!(
+        "a {\n  b: 200;\n}\n",
+        grass::from_string_with_options(
+            "@function sum($list, $i: 1) {
+                @if $i > length($list) {
+                    @return 0;
+                }
+                @return nth($list, $i) + sum($list, $i + 1);
+            }
+            $l: ();
+            @for $i from 1 through 200 {
+                $l: append($l, 1);
+            }
+            a { b: sum($l); }"
+                .to_string(),
+            &options
+        )
+        .unwrap()
+    );
+}
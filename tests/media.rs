@@ -63,3 +63,89 @@ test!(
     }",
     "@media screen and (:) {\n  a {\n    color: red;\n  }\n}\n"
 );
+test!(
+    range_context_single_comparison,
+    "@media (width >= 600px) {a {color: red;}}",
+    "@media (width >= 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    range_context_two_comparisons,
+    "@media (400px <= width < 700px) {a {color: red;}}",
+    "@media (400px <= width < 700px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    range_context_evaluates_sassscript,
+    "$a: 10px;\n@media (width >= $a * 2) {a {color: red;}}",
+    "@media (width >= 20px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    range_context_nested_parens_in_value,
+    "@media (width > calc(1px + 2px)) {a {color: red;}}",
+    "@media (width > 3px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    feature_value_is_sassscript,
+    "@media screen and (min-width: 1px + 2px) {a {color: red;}}",
+    "@media screen and (min-width: 3px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    condition_with_or,
+    "@media (color) or (hover) {a {color: red;}}",
+    "@media (color) or (hover) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    condition_with_multiple_and,
+    "@media (color) and (hover) and (pointer) {a {color: red;}}",
+    "@media (color) and (hover) and (pointer) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    not_condition,
+    "@media not (color) {a {color: red;}}",
+    "@media not (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    type_and_not_condition,
+    "@media screen and not (color) {a {color: red;}}",
+    "@media screen and not (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_condition_in_parens,
+    "@media ((color) or (hover)) {a {color: red;}}",
+    "@media ((color) or (hover)) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    modifier_type_and_feature,
+    "@media only screen and (color) {a {color: red;}}",
+    "@media only screen and (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_condition_after_and,
+    "$q: \"(color)\";\n@media screen and #{$q} {a {color: red;}}",
+    "@media screen and (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_feature_name,
+    "$f: min-width;\n@media (#{$f}: 10px) {a {color: red;}}",
+    "@media (min-width: 10px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_media_type_in_list,
+    "@media #{\"screen\"}, print {a {color: red;}}",
+    "@media screen, print {\n  a {\n    color: red;\n  }\n}\n"
+);
+error!(
+    feature_value_missing_closing_paren,
+    "@media (min-width: 1px {a {color: red;}}", "Error: expected \")\"."
+);
+error!(
+    mixed_and_or_without_parens,
+    "@media (color) and (hover) or (pointer) {a {color: red;}}", "Error: expected \"{\"."
+);
+error!(
+    hash_without_interpolation_in_condition,
+    "@media screen and #a {a {color: red;}}", "Error: expected \"{\"."
+);
+error!(
+    empty_feature,
+    "@media () {a {color: red;}}", "Error: Expected expression."
+);
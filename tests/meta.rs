@@ -28,6 +28,52 @@ test!(
     "a {\n  color: if(false, 1, 2);\n}\n",
     "a {\n  color: 2;\n}\n"
 );
+test!(
+    if_only_evaluates_chosen_branch,
+    "$map: null;\na {\n  color: if($map, map-get($map, a), null);\n}\n",
+    ""
+);
+test!(
+    if_does_not_evaluate_if_false_when_true,
+    "@function throw() {\n  @error \"evaluated\";\n}\n\na {\n  color: if(true, 1, throw());\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    if_does_not_evaluate_if_true_when_false,
+    "a {\n  color: if(false, $undefined, 2);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    if_named_args_only_evaluates_chosen_branch,
+    "a {\n  color: if($if-false: $undefined, $condition: 1, $if-true: 2);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    if_splat_args,
+    "$args: false, 1, 2;\na {\n  color: if($args...);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    if_through_call,
+    "a {\n  color: call(get-function(if), true, 1, 2);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+error!(
+    if_evaluates_chosen_branch,
+    "a {\n  color: if(true, $undefined, 2);\n}\n", "Error: Undefined variable."
+);
+error!(
+    if_missing_if_false,
+    "a {\n  color: if(true, 1);\n}\n", "Error: Missing argument $if-false."
+);
+error!(
+    if_too_many_args,
+    "a {\n  color: if(true, 1, 2, 3);\n}\n", "Error: Only 3 arguments allowed, but 4 were passed."
+);
+error!(
+    if_unknown_named_arg,
+    "a {\n  color: if(true, 1, $foo: 2);\n}\n", "Error: No argument named $foo."
+);
 test!(
     feature_exists_dbl_quoted,
     "a {\n  color: feature-exists(\"at-error\")\n}\n",
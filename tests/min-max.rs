@@ -4,19 +4,19 @@
 mod macros;
 
 test!(
-    min_not_evaluated_units_percent,
+    min_simplified_units_percent,
     "a {\n  color: min(1%, 2%);\n}\n",
-    "a {\n  color: min(1%, 2%);\n}\n"
+    "a {\n  color: 1%;\n}\n"
 );
 test!(
-    min_not_evaluated_units_px,
+    min_simplified_units_px,
     "a {\n  color: min(1px, 2px);\n}\n",
-    "a {\n  color: min(1px, 2px);\n}\n"
+    "a {\n  color: 1px;\n}\n"
 );
 test!(
-    min_not_evaluated_no_units,
+    min_simplified_no_units,
     "a {\n  color: min(1, 2);\n}\n",
-    "a {\n  color: min(1, 2);\n}\n"
+    "a {\n  color: 1;\n}\n"
 );
 test!(
     min_not_evaluated_incompatible_units,
@@ -53,19 +53,19 @@ error!(
     "$a: 1px;\n$b: 2%;\na {\n  color: min($a, $b);\n}\n", "Error: Incompatible units px and %."
 );
 test!(
-    max_not_evaluated_units_percent,
+    max_simplified_units_percent,
     "a {\n  color: max(1%, 2%);\n}\n",
-    "a {\n  color: max(1%, 2%);\n}\n"
+    "a {\n  color: 2%;\n}\n"
 );
 test!(
-    max_not_evaluated_units_px,
+    max_simplified_units_px,
     "a {\n  color: max(1px, 2px);\n}\n",
-    "a {\n  color: max(1px, 2px);\n}\n"
+    "a {\n  color: 2px;\n}\n"
 );
 test!(
-    max_not_evaluated_no_units,
+    max_simplified_no_units,
     "a {\n  color: max(1, 2);\n}\n",
-    "a {\n  color: max(1, 2);\n}\n"
+    "a {\n  color: 2;\n}\n"
 );
 test!(
     max_not_evaluated_incompatible_units,
@@ -478,3 +478,22 @@ error!(
     }",
     "Error: Mixins may not be declared in control directives."
 );
+test!(
+    content_args_inside_unknown_at_rule,
+    "@mixin foo {\n  @content(red);\n}\n\n@bar {\n  @include foo using ($a) {\n    a {\n      color: $a;\n    }\n  }\n}\n",
+    "@bar {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    default_var_in_content_inside_unknown_at_rule,
+    "@mixin foo {\n  @content;\n}\n\n@bar {\n  @include foo {\n    $a: red !default;\n  }\n}\n",
+    "@bar;\n"
+);
+error!(
+    undefined_mixin_suggests_closest,
+    "@mixin button {}\na {\n  @include buton;\n}\n", "Error: Undefined mixin. Did you mean button?"
+);
+error!(
+    unknown_named_argument_suggests_parameter,
+    "@mixin foo($color) {}\na {\n  @include foo($colr: red);\n}\n",
+    "Error: No argument named $colr. Did you mean $color?"
+);
@@ -149,10 +149,138 @@ test!(
                 + 999999999999999999
                 + 999999999999999999
                 + 999999999999999999;\n}\n",
-    "a {\n  color: 9999999999999999990;\n}\n"
+    "a {\n  color: 10000000000000000000;\n}\n"
 );
 test!(
     number_overflow_from_multiplication,
     "a {\n  color: 999999999999999999 * 10;\n}\n",
-    "a {\n  color: 9999999999999999990;\n}\n"
+    "a {\n  color: 10000000000000000000;\n}\n"
 );
+test!(
+    fuzzy_equality_floating_point_error,
+    "a {\n  color: 0.1 + 0.2 == 0.3;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    fuzzy_equality_within_epsilon,
+    "a {\n  color: 1 == 1.000000000001;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    fuzzy_equality_outside_epsilon,
+    "a {\n  color: 1 == 1.0000000001;\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    fuzzy_less_than_within_epsilon,
+    "a {\n  color: 1 < 1.000000000001;\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    repeating_decimal_rounded,
+    "a {\n  color: 1/3;\n  color: (2/3);\n}\n",
+    "a {\n  color: 1/3;\n  color: 0.6666666667;\n}\n"
+);
+test!(
+    trailing_zeros_removed_after_rounding,
+    "a {\n  color: 1.000000000049;\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    rounding_carries_into_integer,
+    "a {\n  color: 9.99999999996;\n}\n",
+    "a {\n  color: 10;\n}\n"
+);
+test!(
+    large_number_not_in_scientific_notation,
+    "a {\n  color: 1e21;\n}\n",
+    "a {\n  color: 1000000000000000000000;\n}\n"
+);
+test!(
+    small_number_not_in_scientific_notation,
+    "a {\n  color: 1e-7;\n}\n",
+    "a {\n  color: 0.0000001;\n}\n"
+);
+test!(
+    round_fuzzy_half,
+    "a {\n  color: round(0.499999999999);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    float_multiplication_rounded,
+    "a {\n  color: 0.1234567891 * 0.1234567891 * 0.1234567891 * 0.1234567891;\n}\n",
+    "a {\n  color: 0.0002323057;\n}\n"
+);
+
+fn with_precision(input: &str, precision: usize) -> String {
+    grass::from_string_with_options(
+        input.to_string(),
+        &grass::Options::default().precision(precision),
+    )
+    .expect(input)
+}
+
+#[test]
+fn custom_precision_rounds() {
+    assert_eq!(
+        "a {\n  color: 0.33333;\n}\n",
+        with_precision("a {\n  color: (1/3);\n}\n", 5)
+    );
+}
+
+#[test]
+fn custom_precision_rounds_up_to_integer() {
+    assert_eq!(
+        "a {\n  color: 1;\n}\n",
+        with_precision("a {\n  color: 0.999999;\n}\n", 5)
+    );
+}
+
+#[test]
+fn custom_precision_rounds_to_zero() {
+    assert_eq!(
+        "a {\n  color: 0;\n}\n",
+        with_precision("a {\n  color: -0.000001;\n}\n", 5)
+    );
+}
+
+#[test]
+fn zero_precision() {
+    assert_eq!(
+        "a {\n  color: 2px;\n}\n",
+        with_precision("a {\n  color: 1.5px;\n}\n", 0)
+    );
+}
+
+#[test]
+fn custom_precision_applies_to_color_alpha() {
+    assert_eq!(
+        "a {\n  color: rgba(0, 0, 0, 0.12346);\n}\n",
+        with_precision("a {\n  color: rgba(0, 0, 0, 0.123456789);\n}\n", 5)
+    );
+}
+
+#[test]
+fn custom_precision_applies_to_inspect() {
+    assert_eq!(
+        "a {\n  color: 1.23457;\n}\n",
+        with_precision("a {\n  color: inspect(1.23456789);\n}\n", 5)
+    );
+}
+
+#[test]
+fn custom_precision_does_not_affect_equality() {
+    assert_eq!(
+        "a {\n  color: false;\n}\n",
+        with_precision("a {\n  color: 1.000001 == 1;\n}\n", 5)
+    );
+}
+
+#[test]
+fn precision_is_restored_after_compilation() {
+    with_precision("a {\n  color: 1.5;\n}\n", 0);
+    assert_eq!(
+        "a {\n  color: 0.1234567891;\n}\n",
+        grass::from_string("a {\n  color: 0.1234567891;\n}\n".to_string()).unwrap()
+    );
+}
@@ -76,3 +76,18 @@ error!(
     properly_bubbles_error_when_invalid_char_after_or,
     "a {\n  color: true or? foo;\n}\n", "Error: expected \";\"."
 );
+test!(
+    short_circuits_undefined_variable,
+    "a {\n  color: true or $undefined;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    short_circuits_function_call,
+    "@function throw() {\n  @error \"evaluated\";\n}\n\na {\n  color: 1 or throw();\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    short_circuits_and_with_higher_precedence,
+    "a {\n  color: true or $undefined and false;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
@@ -43,3 +43,23 @@ test!(
     "a {\n  color: 1 / 2 * 1em;\n}\n",
     "a {\n  color: 0.5em;\n}\n"
 );
+test!(
+    and_then_or,
+    "a {\n  color: false and false or true;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    or_then_and,
+    "a {\n  color: true or false and false;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    or_then_chained_and,
+    "a {\n  color: true or false and false and false;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    addition_then_chained_multiplication,
+    "a {\n  color: 1 + 2 * 3 * 4;\n}\n",
+    "a {\n  color: 25;\n}\n"
+);
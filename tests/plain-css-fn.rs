@@ -43,3 +43,7 @@ test!(
     "a {\n  $primary: #f2ece4;\n  $accent: #e1d7d2;\n  color: radial-gradient($primary, $accent);\n}\n",
     "a {\n  color: radial-gradient(#f2ece4, #e1d7d2);\n}\n"
 );
+error!(
+    error_in_argument_is_not_masked,
+    "a {\n  color: foo(1px + 1em);\n}\n", "Error: Incompatible units em and px."
+);
@@ -13,3 +13,7 @@ test!(
     "a {\n  color: simple-selectors(\".foo.bar.baz\");\n}\n",
     "a {\n  color: .foo, .bar, .baz;\n}\n"
 );
+error!(
+    leading_combinator,
+    "a {\n  color: simple-selectors(\"> a\");\n}\n", "Error: $selector: expected selector."
+);
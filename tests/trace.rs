@@ -0,0 +1,81 @@
+#![cfg(test)]
+
+fn trace(input: &str) -> Vec<String> {
+    match grass::from_string(input.to_string()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e
+            .trace()
+            .iter()
+            .map(|frame| {
+                format!(
+                    "{} {}:{}  {}",
+                    frame.file(),
+                    frame.line(),
+                    frame.column(),
+                    frame.member()
+                )
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn error_at_root() {
+    assert_eq!(
+        vec!["stdin 1:5  root stylesheet"],
+        trace("a { @error \"foo\"; }")
+    );
+}
+
+#[test]
+fn error_in_mixin() {
+    assert_eq!(
+        vec!["stdin 2:3  foo()", "stdin 4:5  root stylesheet"],
+        trace("@mixin foo {\n  @error \"foo\";\n}\na { @include foo; }")
+    );
+}
+
+#[test]
+fn error_in_function_in_mixin() {
+    assert_eq!(
+        vec![
+            "stdin 2:3  foo()",
+            "stdin 5:10  bar()",
+            "stdin 7:5  root stylesheet"
+        ],
+        trace(
+            "@function foo() {\n  @error \"foo\";\n}\n@mixin bar {\n  b: foo();\n}\na { @include bar; }"
+        )
+    );
+}
+
+#[test]
+fn error_in_content_block() {
+    assert_eq!(
+        vec![
+            "stdin 5:3  @content",
+            "stdin 2:3  foo()",
+            "stdin 4:5  root stylesheet"
+        ],
+        trace("@mixin foo {\n  @content;\n}\na { @include foo {\n  @error \"foo\";\n} }")
+    );
+}
+
+#[test]
+fn error_after_mixin_returns() {
+    assert_eq!(
+        vec!["stdin 2:19  root stylesheet"],
+        trace("@mixin foo {}\na { @include foo; @error \"foo\"; }")
+    );
+}
+
+#[test]
+fn trace_is_displayed() {
+    match grass::from_string("@mixin foo {\n  @error \"foo\";\n}\na { @include foo; }".to_string())
+    {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e
+            .to_string()
+            .ends_with("  stdin 2:3  foo()\n  stdin 4:5  root stylesheet\n")),
+    }
+}
@@ -129,12 +129,79 @@ test!(
     "a {\n  color: unit((1 / 1in) * 1in);\n}\n",
     "a {\n  color: \"\";\n}\n"
 );
+test!(
+    unit_div_non_comparable,
+    "a {\n  color: unit(1px / 1em);\n}\n",
+    "a {\n  color: \"px/em\";\n}\n"
+);
+test!(
+    unit_mul_then_div_non_comparable,
+    "$a: 3;\na {\n  color: unit($a * 1px / 1em);\n}\n",
+    "a {\n  color: \"px/em\";\n}\n"
+);
+test!(
+    unit_mul_div_multiple_numerators,
+    "a {\n  color: unit(1px * 1em / 1s);\n}\n",
+    "a {\n  color: \"px*em/s\";\n}\n"
+);
+test!(
+    unit_div_multiple_denominators,
+    "a {\n  color: unit(1px / (1em * 1s));\n}\n",
+    "a {\n  color: \"px/em*s\";\n}\n"
+);
+test!(
+    unit_div_none_by_mul,
+    "a {\n  color: unit(1 / (1em * 1s));\n}\n",
+    "a {\n  color: \"(em*s)^-1\";\n}\n"
+);
+test!(
+    unit_div_cancels_numerator,
+    "a {\n  color: (1px * 1em) / 1em;\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    unit_mul_cancels_denominator,
+    "a {\n  color: (1px / 1em) * 1em;\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    unit_mul_cancels_comparable_denominator,
+    "a {\n  color: (1in / 1em) * (1em / 1px);\n}\n",
+    "a {\n  color: 96;\n}\n"
+);
+test!(
+    unit_div_cancels_comparable_complex,
+    "a {\n  color: (1in * 1em) / (1px * 1em);\n}\n",
+    "a {\n  color: 96;\n}\n"
+);
+test!(
+    unit_add_complex_units_different_order,
+    "a {\n  color: inspect(1px * 1em + 2em * 1px);\n}\n",
+    "a {\n  color: 3px*em;\n}\n"
+);
+test!(
+    unit_add_complex_units_comparable,
+    "a {\n  color: inspect(1in * 1em + 96px * 1em);\n}\n",
+    "a {\n  color: 2in*em;\n}\n"
+);
+test!(
+    unit_eq_complex_units_different_order,
+    "a {\n  color: (1px * 1em) == (1em * 1px);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+error!(
+    unit_add_complex_units_incompatible,
+    "a {\n  color: 1px * 1em + 1px * 1s;\n}\n", "Error: Incompatible units px*s and px*em."
+);
+error!(
+    display_mul_and_div,
+    "a {\n  color: 1px * 1em / 1s;\n}\n", "Error: 1px*em/s isn't a valid CSS value."
+);
 error!(
     display_single_div_with_none_numerator,
     "a {\n  color: (1 / 1em);\n}\n", "Error: 1em^-1 isn't a valid CSS value."
 );
 error!(
-    #[ignore = "non-comparable inverse units"]
     display_single_div_with_non_comparable_numerator,
     "a {\n  color: (1px / 1em);\n}\n", "Error: 1px/em isn't a valid CSS value."
 );
@@ -0,0 +1,121 @@
+#![cfg(test)]
+
+#[macro_use]
+mod macros;
+
+test!(
+    use_default_namespace,
+    "@use \"sass:math\";\na {\n  color: math.$pi;\n}\n",
+    "a {\n  color: 3.1415926536;\n}\n"
+);
+test!(
+    use_with_namespace,
+    "@use \"sass:math\" as m;\na {\n  color: m.sqrt(4);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    use_as_star_functions,
+    "@use \"sass:math\" as *;\na {\n  color: sqrt(4);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    use_as_star_variables,
+    "@use \"sass:math\" as *;\na {\n  color: $pi;\n}\n",
+    "a {\n  color: 3.1415926536;\n}\n"
+);
+test!(
+    use_single_quotes,
+    "@use 'sass:math';\na {\n  color: math.abs(-1);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(use_emits_nothing, "@use \"sass:math\";\n", "");
+test!(
+    use_list_module,
+    "@use \"sass:list\";\na {\n  color: list.separator((1, 2));\n}\n",
+    "a {\n  color: comma;\n}\n"
+);
+test!(
+    use_map_module,
+    "@use \"sass:map\";\na {\n  color: map.get((a: b), a);\n}\n",
+    "a {\n  color: b;\n}\n"
+);
+test!(
+    use_string_module,
+    "@use \"sass:string\";\na {\n  color: string.length(\"abc\");\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    use_selector_module,
+    "@use \"sass:selector\";\na {\n  color: selector.nest(\"a\", \"b\");\n}\n",
+    "a {\n  color: a b;\n}\n"
+);
+test!(
+    use_color_module,
+    "@use \"sass:color\";\na {\n  color: color.red(#ff0000);\n}\n",
+    "a {\n  color: 255;\n}\n"
+);
+test!(
+    use_meta_module,
+    "@use \"sass:meta\";\na {\n  color: meta.type-of(1);\n}\n",
+    "a {\n  color: number;\n}\n"
+);
+test!(
+    function_exists_with_module,
+    "@use \"sass:math\";\na {\n  color: function-exists(sqrt, math);\n  color: function-exists(sqrt);\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    global_variable_exists_with_module,
+    "@use \"sass:math\";\na {\n  color: global-variable-exists(pi, math);\n  color: global-variable-exists(pi);\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    get_function_with_module,
+    "@use \"sass:math\";\na {\n  color: call(get-function(sqrt, $module: math), 9);\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    module_function_equals_global_function,
+    "@use \"sass:math\";\na {\n  color: get-function(round) == get-function(round, $module: math);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+error!(
+    use_unknown_builtin_module,
+    "@use \"sass:foo\";\n", "Error: Can't find stylesheet to import."
+);
+error!(
+    use_configure_builtin_module,
+    "@use \"sass:math\" with ($a: 1);\n", "Error: Built-in modules can't be configured."
+);
+error!(
+    use_duplicate_namespace,
+    "@use \"sass:math\";\n@use \"sass:math\";\n",
+    "Error: There's already a module with namespace \"math\"."
+);
+error!(
+    use_nested,
+    "a {\n  @use \"sass:math\";\n}\n", "Error: This at-rule is not allowed here."
+);
+error!(use_not_string, "@use math;\n", "Error: Expected string.");
+error!(
+    undefined_namespace,
+    "a {\n  color: math.sqrt(4);\n}\n", "Error: There is no module with the namespace \"math\"."
+);
+error!(
+    namespace_not_in_scope_after_rename,
+    "@use \"sass:math\" as m;\na {\n  color: math.$pi;\n}\n",
+    "Error: There is no module with the namespace \"math\"."
+);
+error!(
+    module_argument_without_use,
+    "a {\n  color: function-exists(sqrt, math);\n}\n",
+    "Error: There is no module with the namespace \"math\"."
+);
+error!(
+    use_of_stylesheet,
+    "@use \"foo\";\n", "Error: @use of stylesheets is not yet supported."
+);
+error!(
+    forward_stylesheet,
+    "@forward \"foo\";\n", "Error: @forward is not yet supported."
+);
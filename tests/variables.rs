@@ -152,3 +152,16 @@ error!(
     nothing_after_hash_in_variable_decl,
     "$color: #", "Error: Expected identifier."
 );
+error!(
+    undefined_variable_suggests_closest,
+    "$color: red;\na {\n  color: $colr;\n}\n", "Error: Undefined variable. Did you mean $color?"
+);
+error!(
+    undefined_variable_suggests_from_outer_scope,
+    "a {\n  $primary-color: red;\n  b {\n    color: $primary_colour;\n  }\n}\n",
+    "Error: Undefined variable. Did you mean $primary-color?"
+);
+error!(
+    undefined_variable_no_close_match,
+    "$color: red;\na {\n  color: $foo;\n}\n", "Error: Undefined variable."
+);